@@ -0,0 +1,44 @@
+// 词法分析吞吐量基准：cargo bench --bench tokenizer
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use l6::tokenizer;
+
+// 真实的第三方库源码，均为 MIT 协议
+const FIXTURES: [(&str, &str); 3] = [
+    ("jquery.js", include_str!("fixtures/jquery.js")),
+    ("jquery.min.js", include_str!("fixtures/jquery.min.js")),
+    ("underscore.js", include_str!("fixtures/underscore.js")),
+];
+
+// 每个文件至少测量这么长时间
+const MEASURE_TIME: Duration = Duration::from_secs(2);
+
+fn main() {
+    for (name, source) in FIXTURES {
+        // 预热，同时确认能完整分析
+        let token_count = tokenizer(source)
+            .unwrap_or_else(|error| panic!("{}: {}", name, error))
+            .len();
+        for _ in 0..3 {
+            black_box(tokenizer(black_box(source)).unwrap());
+        }
+
+        let mut iterations = 0;
+        let start = Instant::now();
+        while start.elapsed() < MEASURE_TIME {
+            black_box(tokenizer(black_box(source)).unwrap());
+            iterations += 1;
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+        let megabytes = source.len() as f64 * iterations as f64 / 1024.0 / 1024.0;
+        println!(
+            "{:<16} {:>8.1} KB {:>8} tokens {:>9.3} ms/iter {:>8.2} MB/s",
+            name,
+            source.len() as f64 / 1024.0,
+            token_count,
+            elapsed * 1000.0 / iterations as f64,
+            megabytes / elapsed
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::fmt::Display;
+
+use crate::tokenizer::{Token, TokenValue};
+use crate::types::Position;
+use crate::utils::line_index::{is_line_terminator, LineIndex};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    // 词法错误，如未闭合的字符串、非法的正则标志
+    Lexical(String),
+    // 遇到了不符合语法的 token
+    UnexpectedToken,
+    // 源码在语法结构完整之前就结束了
+    UnexpectedEnd,
+    // token 序列符合文法，但违反了早期错误规则，如对右值赋值
+    Syntax(String),
+}
+
+/// 词法或语法分析失败时返回的错误
+///
+/// `offset` 是出错位置在源码中的字节偏移，`line` 从 1 开始、`column` 从 0 开始（与 acorn 一致）。
+#[derive(Debug, Clone)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    // 出错的 token，源码提前结束或词法错误时为 None
+    pub token: Option<Box<Token>>,
+    // 期望出现的语法结构，如 "';'"、"expression"
+    pub expected: Option<String>,
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+fn describe_token(token: &Token) -> String {
+    match &token.value {
+        TokenValue::String(value) => format!("'{}'", value),
+        TokenValue::RegexpValue(value) => format!("'{}'", value.value),
+        TokenValue::None => format!("'{}'", token._type.label),
+    }
+}
+
+impl ParseError {
+    pub fn lexical<T: Into<String>>(message: T, offset: usize, source: &str) -> Self {
+        let position = LineIndex::new(source).position(offset);
+        ParseError {
+            kind: ParseErrorKind::Lexical(message.into()),
+            token: None,
+            expected: None,
+            offset,
+            line: position.line,
+            column: position.column,
+        }
+    }
+
+    /// 出错位置由调用方给出，通常是 AST 节点的 start 和 loc.start
+    pub fn syntax<T: Into<String>>(message: T, offset: usize, position: Position) -> Self {
+        ParseError {
+            kind: ParseErrorKind::Syntax(message.into()),
+            token: None,
+            expected: None,
+            offset,
+            line: position.line,
+            column: position.column,
+        }
+    }
+
+    /// 在 `cursor` 处期望 `expected`，但遇到了其他 token 或源码已经结束
+    pub fn unexpected(tokens: &[Token], cursor: usize, expected: &str) -> Self {
+        match tokens.get(cursor) {
+            Some(token) => ParseError {
+                kind: ParseErrorKind::UnexpectedToken,
+                token: Some(Box::new(token.clone())),
+                expected: Some(String::from(expected)),
+                offset: token.start,
+                line: token.loc.start.line,
+                column: token.loc.start.column,
+            },
+            None => {
+                let position = tokens
+                    .last()
+                    .map_or(Position { line: 1, column: 0 }, |token| token.loc.end);
+                ParseError {
+                    kind: ParseErrorKind::UnexpectedEnd,
+                    token: None,
+                    expected: Some(String::from(expected)),
+                    offset: tokens.last().map_or(0, |token| token.end),
+                    line: position.line,
+                    column: position.column,
+                }
+            }
+        }
+    }
+
+    /// 形如 "expected ';', found 'let'" 的错误描述
+    pub fn description(&self) -> String {
+        let found = match &self.token {
+            Some(token) => describe_token(token),
+            None => String::from("end of input"),
+        };
+        match &self.kind {
+            ParseErrorKind::Lexical(message) | ParseErrorKind::Syntax(message) => message.clone(),
+            ParseErrorKind::UnexpectedToken | ParseErrorKind::UnexpectedEnd => {
+                match &self.expected {
+                    Some(expected) => format!("expected {}, found {}", expected, found),
+                    None => format!("unexpected {}", found),
+                }
+            }
+        }
+    }
+}
+
+impl ParseError {
+    /// 渲染带源码行和位置标记的诊断信息，`file_name` 为源码所在文件（标准输入时为 "<stdin>"）
+    pub fn render(&self, source: &str, file_name: &str) -> String {
+        let offset = self.offset.min(source.len());
+        let line_start = source[..offset]
+            .char_indices()
+            .rev()
+            .find(|(_, char)| is_line_terminator(*char))
+            .map_or(0, |(index, char)| index + char.len_utf8());
+        let line_end = source[offset..]
+            .char_indices()
+            .find(|(_, char)| is_line_terminator(*char))
+            .map_or(source.len(), |(index, _)| offset + index);
+        let line_number = self.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        let caret_indent: String = source[line_start..offset]
+            .chars()
+            .map(|char| if char == '\t' { '\t' } else { ' ' })
+            .collect();
+        return format!(
+            "error: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}^\n",
+            self.description(),
+            gutter,
+            file_name,
+            self.line,
+            self.column + 1,
+            gutter,
+            line_number,
+            &source[line_start..line_end],
+            gutter,
+            caret_indent
+        );
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({}:{})", self.description(), self.line, self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -0,0 +1,1193 @@
+// 把 AST 序列化为与 acorn / espree 一致的 ESTree JSON
+
+use crate::tokenizer::{Token, TokenValue};
+use crate::types::{
+    ASTType, ArrayExpression, ArrayPattern, ArrowFunctionBody, ArrowFunctionExpression,
+    AssignmentExpression, AssignmentPattern, AssignmentProperty, AwaitExpression, BinaryExpression,
+    BlockStatement, Body, BreakStatement, CallExpression, CatchClause, ChainExpression, ClassBody,
+    ClassDeclaration, ClassElement, ClassExpression, ConditionalExpression, ContinueStatement,
+    DebuggerStatement, DoWhileStatement, EmptyStatement, ExportAllDeclaration,
+    ExportDefaultDeclaration, ExportDefaultDeclarationKind, ExportNamedDeclaration,
+    ExportSpecifier, Expression, ExpressionOrSpread, ExpressionStatement, ForInOfLeft,
+    ForInStatement, ForOfStatement, ForStatement, ForStatementInit, FunctionDeclaration,
+    FunctionExpression, Identifier, IfStatement, ImportAttribute, ImportDeclaration,
+    ImportDeclarationSpecifier, ImportDefaultSpecifier, ImportExpression, ImportNamespaceSpecifier,
+    ImportSpecifier, LabeledStatement, Literal, LiteralValue, LogicalExpression, MemberExpression,
+    MetaProperty, MethodDefinition, ModuleExportName, NewExpression, ObjectExpression,
+    ObjectExpressionProperty, ObjectPattern, ObjectPatternProperty, Pattern, PrivateIdentifier,
+    Program, Property, PropertyDefinition, PropertyKey, RestElement, ReturnStatement,
+    SequenceExpression, SourceLocation, SpreadElement, StaticBlock, Super, SwitchCase,
+    SwitchStatement, TaggedTemplateExpression, TemplateElement, TemplateLiteral, ThisExpression,
+    ThrowStatement, TryStatement, UnaryExpression, UpdateExpression, VariableDeclaration,
+    VariableDeclarator, WhileStatement, WithStatement, YieldExpression,
+};
+
+#[derive(Default)]
+pub struct JsonOptions {
+    // 是否输出 loc 字段（对应 acorn 的 locations 选项）
+    pub locations: bool,
+    // 是否缩进输出（对应 JSON.stringify(ast, null, 2)）
+    pub pretty: bool,
+}
+
+/// 序列化 Program，`source` 用于把字节偏移换算成 acorn 使用的 UTF-16 偏移
+pub fn to_json(program: &Program, source: &str, options: &JsonOptions) -> String {
+    let mut writer = JsonWriter::new(source, options);
+    program.write_json(&mut writer);
+    return writer.output;
+}
+
+/// 序列化 token 列表，字段与 acorn 的 Token 对象一致
+pub fn tokens_to_json(tokens: &[Token], source: &str, options: &JsonOptions) -> String {
+    let mut writer = JsonWriter::new(source, options);
+    writer.array(tokens);
+    return writer.output;
+}
+
+pub struct JsonWriter<'a> {
+    output: String,
+    options: &'a JsonOptions,
+    // 下标为字节偏移，值为对应的 UTF-16 偏移；纯 ASCII 源码两者相同，不需要换算
+    utf16_offsets: Option<Vec<usize>>,
+    // 每一层对象或数组是否还没有写入过成员
+    first_member_stack: Vec<bool>,
+}
+
+impl<'a> JsonWriter<'a> {
+    fn new(source: &str, options: &'a JsonOptions) -> Self {
+        let utf16_offsets = if source.is_ascii() {
+            None
+        } else {
+            let mut offsets = vec![0; source.len() + 1];
+            let mut utf16_offset = 0;
+            for (index, char) in source.char_indices() {
+                offsets[index..index + char.len_utf8()].fill(utf16_offset);
+                utf16_offset += char.len_utf16();
+            }
+            offsets[source.len()] = utf16_offset;
+            Some(offsets)
+        };
+        JsonWriter {
+            output: String::new(),
+            options,
+            utf16_offsets,
+            first_member_stack: Vec::new(),
+        }
+    }
+
+    fn offset(&self, offset: usize) -> usize {
+        match &self.utf16_offsets {
+            Some(offsets) => *offsets.get(offset).unwrap_or(&offset),
+            None => offset,
+        }
+    }
+
+    fn newline(&mut self) {
+        if self.options.pretty {
+            self.output.push('\n');
+            for _ in 0..self.first_member_stack.len() {
+                self.output.push_str("  ");
+            }
+        }
+    }
+
+    fn begin(&mut self, bracket: char) {
+        self.output.push(bracket);
+        self.first_member_stack.push(true);
+    }
+
+    fn end(&mut self, bracket: char) {
+        let empty = self.first_member_stack.pop().unwrap_or(true);
+        if !empty {
+            self.newline();
+        }
+        self.output.push(bracket);
+    }
+
+    // 对象成员或数组元素之间的逗号与换行
+    fn separator(&mut self) {
+        if let Some(first) = self.first_member_stack.last_mut() {
+            if *first {
+                *first = false;
+            } else {
+                self.output.push(',');
+            }
+        }
+        self.newline();
+    }
+
+    fn key(&mut self, key: &str) {
+        self.separator();
+        self.string(key);
+        self.output.push(':');
+        if self.options.pretty {
+            self.output.push(' ');
+        }
+    }
+
+    pub fn string(&mut self, value: &str) {
+        self.output.push('"');
+        for char in value.chars() {
+            match char {
+                '"' => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                '\u{8}' => self.output.push_str("\\b"),
+                '\u{c}' => self.output.push_str("\\f"),
+                char if (char as u32) < 0x20 => {
+                    self.output.push_str(&format!("\\u{:04x}", char as u32))
+                }
+                char => self.output.push(char),
+            }
+        }
+        self.output.push('"');
+    }
+
+    pub fn number(&mut self, value: f64) {
+        self.output.push_str(&format_number(value));
+    }
+
+    pub fn null(&mut self) {
+        self.output.push_str("null");
+    }
+
+    pub fn begin_object(&mut self) {
+        self.begin('{');
+    }
+
+    pub fn end_object(&mut self) {
+        self.end('}');
+    }
+
+    /// 写入节点的公共字段 type、start、end 和 loc
+    pub fn begin_node(&mut self, _type: &ASTType, start: usize, end: usize, loc: &SourceLocation) {
+        self.begin_object();
+        self.string_field("type", _type.as_str());
+        self.position_fields(start, end, loc);
+    }
+
+    /// 写入 start、end 和 loc 字段
+    pub fn position_fields(&mut self, start: usize, end: usize, loc: &SourceLocation) {
+        self.number_field("start", self.offset(start) as f64);
+        self.number_field("end", self.offset(end) as f64);
+        if self.options.locations {
+            self.key("loc");
+            self.begin_object();
+            for (key, position) in [("start", &loc.start), ("end", &loc.end)] {
+                self.key(key);
+                self.begin_object();
+                self.number_field("line", position.line as f64);
+                self.number_field("column", position.column as f64);
+                self.end_object();
+            }
+            self.end_object();
+        }
+    }
+
+    pub fn string_field(&mut self, key: &str, value: &str) {
+        self.key(key);
+        self.string(value);
+    }
+
+    pub fn number_field(&mut self, key: &str, value: f64) {
+        self.key(key);
+        self.number(value);
+    }
+
+    pub fn bool_field(&mut self, key: &str, value: bool) {
+        self.key(key);
+        self.output.push_str(if value { "true" } else { "false" });
+    }
+
+    pub fn null_field(&mut self, key: &str) {
+        self.key(key);
+        self.null();
+    }
+
+    pub fn node_field<T: WriteJson>(&mut self, key: &str, value: &T) {
+        self.key(key);
+        value.write_json(self);
+    }
+
+    pub fn optional_field<T: WriteJson>(&mut self, key: &str, value: &Option<T>) {
+        match value {
+            Some(value) => self.node_field(key, value),
+            None => self.null_field(key),
+        }
+    }
+
+    pub fn array_field<T: WriteJson>(&mut self, key: &str, values: &[T]) {
+        self.key(key);
+        self.array(values);
+    }
+
+    pub fn array<T: WriteJson>(&mut self, values: &[T]) {
+        self.begin('[');
+        for value in values {
+            self.separator();
+            value.write_json(self);
+        }
+        self.end(']');
+    }
+}
+
+// 与 JavaScript 的 Number.prototype.toString 保持一致：整数不带小数点，无穷大在 JSON 中为 null
+fn format_number(value: f64) -> String {
+    if !value.is_finite() {
+        return String::from("null");
+    }
+    if value.fract() == 0.0 && value.abs() < 1e21 {
+        return format!("{:.0}", value);
+    }
+    return format!("{}", value);
+}
+
+pub trait WriteJson {
+    fn write_json(&self, writer: &mut JsonWriter);
+}
+
+impl<T: WriteJson> WriteJson for Box<T> {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        self.as_ref().write_json(writer);
+    }
+}
+
+impl<T: WriteJson> WriteJson for Option<T> {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            Some(value) => value.write_json(writer),
+            None => writer.null(),
+        }
+    }
+}
+
+impl WriteJson for Token {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_object();
+        writer.key("type");
+        writer.begin_object();
+        writer.string_field("label", &self._type.label);
+        // acorn 中非关键字 token 的 keyword 为 undefined，JSON 中不出现
+        if let Some(keyword) = &self._type.keyword.0 {
+            writer.string_field("keyword", keyword);
+        }
+        writer.bool_field("beforeExpr", self._type.beforeExpr);
+        writer.bool_field("startsExpr", self._type.startsExpr);
+        writer.bool_field("isLoop", self._type.isLoop);
+        writer.bool_field("isAssign", self._type.isAssign);
+        writer.bool_field("prefix", self._type.prefix);
+        writer.bool_field("postfix", self._type.postfix);
+        writer.end_object();
+        match &self.value {
+            TokenValue::String(value) => writer.string_field("value", value),
+            TokenValue::RegexpValue(value) => {
+                writer.key("value");
+                writer.begin_object();
+                writer.string_field("pattern", &value.pattern);
+                writer.string_field("flags", &value.flags);
+                writer.key("value");
+                writer.begin_object();
+                writer.end_object();
+                writer.end_object();
+            }
+            TokenValue::None => {}
+        }
+        writer.position_fields(self.start, self.end, &self.loc);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for Program {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("body", &self.body);
+        writer.string_field("sourceType", self.sourceType.as_str());
+        writer.end_object();
+    }
+}
+
+impl WriteJson for Body {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            Body::VariableDeclaration(ast) => ast.write_json(writer),
+            Body::FunctionDeclaration(ast) => ast.write_json(writer),
+            Body::ExpressionStatement(ast) => ast.write_json(writer),
+            Body::BlockStatement(ast) => ast.write_json(writer),
+            Body::IfStatement(ast) => ast.write_json(writer),
+            Body::ForStatement(ast) => ast.write_json(writer),
+            Body::ForInStatement(ast) => ast.write_json(writer),
+            Body::ForOfStatement(ast) => ast.write_json(writer),
+            Body::WhileStatement(ast) => ast.write_json(writer),
+            Body::DoWhileStatement(ast) => ast.write_json(writer),
+            Body::SwitchStatement(ast) => ast.write_json(writer),
+            Body::BreakStatement(ast) => ast.write_json(writer),
+            Body::ContinueStatement(ast) => ast.write_json(writer),
+            Body::LabeledStatement(ast) => ast.write_json(writer),
+            Body::EmptyStatement(ast) => ast.write_json(writer),
+            Body::ReturnStatement(ast) => ast.write_json(writer),
+            Body::ThrowStatement(ast) => ast.write_json(writer),
+            Body::TryStatement(ast) => ast.write_json(writer),
+            Body::DebuggerStatement(ast) => ast.write_json(writer),
+            Body::WithStatement(ast) => ast.write_json(writer),
+            Body::ClassDeclaration(ast) => ast.write_json(writer),
+            Body::ImportDeclaration(ast) => ast.write_json(writer),
+            Body::ExportNamedDeclaration(ast) => ast.write_json(writer),
+            Body::ExportDefaultDeclaration(ast) => ast.write_json(writer),
+            Body::ExportAllDeclaration(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for ForStatementInit {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            ForStatementInit::VariableDeclaration(ast) => ast.write_json(writer),
+            ForStatementInit::Expression(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for ForInOfLeft {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            ForInOfLeft::VariableDeclaration(ast) => ast.write_json(writer),
+            ForInOfLeft::Pattern(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for Expression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            Expression::Identifier(ast) => ast.write_json(writer),
+            Expression::ConditionalExpression(ast) => ast.write_json(writer),
+            Expression::BinaryExpression(ast) => ast.write_json(writer),
+            Expression::LogicalExpression(ast) => ast.write_json(writer),
+            Expression::UnaryExpression(ast) => ast.write_json(writer),
+            Expression::UpdateExpression(ast) => ast.write_json(writer),
+            Expression::AssignmentExpression(ast) => ast.write_json(writer),
+            Expression::ChainExpression(ast) => ast.write_json(writer),
+            Expression::ThisExpression(ast) => ast.write_json(writer),
+            Expression::CallExpression(ast) => ast.write_json(writer),
+            Expression::MemberExpression(ast) => ast.write_json(writer),
+            Expression::ArrayExpression(ast) => ast.write_json(writer),
+            Expression::ObjectExpression(ast) => ast.write_json(writer),
+            Expression::Literal(ast) => ast.write_json(writer),
+            Expression::NewExpression(ast) => ast.write_json(writer),
+            Expression::FunctionExpression(ast) => ast.write_json(writer),
+            Expression::ArrowFunctionExpression(ast) => ast.write_json(writer),
+            Expression::ClassExpression(ast) => ast.write_json(writer),
+            Expression::PrivateIdentifier(ast) => ast.write_json(writer),
+            Expression::Super(ast) => ast.write_json(writer),
+            Expression::ImportExpression(ast) => ast.write_json(writer),
+            Expression::MetaProperty(ast) => ast.write_json(writer),
+            Expression::TemplateLiteral(ast) => ast.write_json(writer),
+            Expression::TaggedTemplateExpression(ast) => ast.write_json(writer),
+            Expression::AwaitExpression(ast) => ast.write_json(writer),
+            Expression::YieldExpression(ast) => ast.write_json(writer),
+            Expression::SequenceExpression(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for Pattern {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            Pattern::Identifier(ast) => ast.write_json(writer),
+            Pattern::MemberExpression(ast) => ast.write_json(writer),
+            Pattern::ArrayPattern(ast) => ast.write_json(writer),
+            Pattern::ObjectPattern(ast) => ast.write_json(writer),
+            Pattern::RestElement(ast) => ast.write_json(writer),
+            Pattern::AssignmentPattern(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for ObjectExpressionProperty {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            ObjectExpressionProperty::Property(ast) => ast.write_json(writer),
+            ObjectExpressionProperty::SpreadElement(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for ObjectPatternProperty {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            ObjectPatternProperty::Property(ast) => ast.write_json(writer),
+            ObjectPatternProperty::RestElement(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for ArrowFunctionBody {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            ArrowFunctionBody::BlockStatement(ast) => ast.write_json(writer),
+            ArrowFunctionBody::Expression(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for ClassElement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            ClassElement::MethodDefinition(ast) => ast.write_json(writer),
+            ClassElement::PropertyDefinition(ast) => ast.write_json(writer),
+            ClassElement::StaticBlock(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for PropertyKey {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            PropertyKey::Identifier(ast) => ast.write_json(writer),
+            PropertyKey::Literal(ast) => ast.write_json(writer),
+            PropertyKey::ComputedPropertyKey(ast) => ast.write_json(writer),
+            PropertyKey::PrivateIdentifier(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for Identifier {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.string_field("name", &self.name);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for VariableDeclaration {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("declarations", &self.declarations);
+        writer.string_field("kind", self.kind.as_str());
+        writer.end_object();
+    }
+}
+
+impl WriteJson for VariableDeclarator {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("id", &self.id);
+        writer.optional_field("init", &self.init);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ArrayExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("elements", &self.elements);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ObjectExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("properties", &self.properties);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for Property {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.bool_field("method", self.method);
+        writer.bool_field("shorthand", self.shorthand);
+        writer.bool_field("computed", self.computed);
+        writer.node_field("key", &self.key);
+        writer.node_field("value", &self.value);
+        writer.string_field("kind", self.kind.as_str());
+        writer.end_object();
+    }
+}
+
+impl WriteJson for Literal {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.key("value");
+        match &self.value {
+            LiteralValue::String(value) => writer.string(value),
+            LiteralValue::Number(value) => writer.number(*value),
+            LiteralValue::Boolean(value) => {
+                writer
+                    .output
+                    .push_str(if *value { "true" } else { "false" })
+            }
+            LiteralValue::Null | LiteralValue::BigInt => writer.null(),
+            // acorn 中正则字面量的 value 是 RegExp 对象，JSON.stringify 的结果为 {}
+            LiteralValue::RegExp => {
+                writer.begin_object();
+                writer.end_object();
+            }
+        }
+        writer.string_field("raw", &self.raw);
+        if let Some(bigint) = &self.bigint {
+            writer.string_field("bigint", bigint);
+        }
+        if let Some(regex) = &self.regex {
+            writer.key("regex");
+            writer.begin_object();
+            writer.string_field("pattern", &regex.pattern);
+            writer.string_field("flags", &regex.flags);
+            writer.end_object();
+        }
+        writer.end_object();
+    }
+}
+
+impl WriteJson for FunctionDeclaration {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("id", &self.id);
+        writer.bool_field("expression", self.expression);
+        writer.bool_field("generator", self.generator);
+        writer.bool_field("async", self._async);
+        writer.array_field("params", &self.params);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for FunctionExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("id", &self.id);
+        writer.bool_field("expression", self.expression);
+        writer.bool_field("generator", self.generator);
+        writer.bool_field("async", self._async);
+        writer.array_field("params", &self.params);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ArrowFunctionExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("id", &self.id);
+        writer.bool_field("expression", self.expression);
+        writer.bool_field("generator", self.generator);
+        writer.bool_field("async", self._async);
+        writer.array_field("params", &self.params);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for BlockStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ExpressionStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("expression", &self.expression);
+        // 与 acorn 一致，只有指令才有 directive 字段
+        if let Some(directive) = &self.directive {
+            writer.string_field("directive", directive);
+        }
+        writer.end_object();
+    }
+}
+
+impl WriteJson for AssignmentPattern {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("left", &self.left);
+        writer.node_field("right", &self.right);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for MemberExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("object", &self.object);
+        writer.node_field("property", &self.property);
+        writer.bool_field("computed", self.computed);
+        writer.bool_field("optional", self.optional);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for CallExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("callee", &self.callee);
+        writer.array_field("arguments", &self.arguments);
+        writer.bool_field("optional", self.optional);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for NewExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("callee", &self.callee);
+        writer.array_field("arguments", &self.arguments);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ConditionalExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("test", &self.test);
+        writer.node_field("consequent", &self.consequent);
+        writer.node_field("alternate", &self.alternate);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for BinaryExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("left", &self.left);
+        writer.string_field("operator", self.operator);
+        writer.node_field("right", &self.right);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for LogicalExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("left", &self.left);
+        writer.string_field("operator", self.operator);
+        writer.node_field("right", &self.right);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for UnaryExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.string_field("operator", self.operator);
+        writer.bool_field("prefix", self.prefix);
+        writer.node_field("argument", &self.argument);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for UpdateExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.string_field("operator", self.operator);
+        writer.bool_field("prefix", self.prefix);
+        writer.node_field("argument", &self.argument);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for AssignmentExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.string_field("operator", self.operator);
+        writer.node_field("left", &self.left);
+        writer.node_field("right", &self.right);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ArrayPattern {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("elements", &self.elements);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ChainExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("expression", &self.expression);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ThisExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ExpressionOrSpread {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            ExpressionOrSpread::Expression(ast) => ast.write_json(writer),
+            ExpressionOrSpread::SpreadElement(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for SpreadElement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("argument", &self.argument);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ObjectPattern {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("properties", &self.properties);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for AssignmentProperty {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.bool_field("method", self.method);
+        writer.bool_field("shorthand", self.shorthand);
+        writer.bool_field("computed", self.computed);
+        writer.node_field("key", &self.key);
+        writer.node_field("value", &self.value);
+        writer.string_field("kind", self.kind.as_str());
+        writer.end_object();
+    }
+}
+
+impl WriteJson for RestElement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("argument", &self.argument);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for IfStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("test", &self.test);
+        writer.node_field("consequent", &self.consequent);
+        writer.optional_field("alternate", &self.alternate);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ForStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("init", &self.init);
+        writer.optional_field("test", &self.test);
+        writer.optional_field("update", &self.update);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ForInStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("left", &self.left);
+        writer.node_field("right", &self.right);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ForOfStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.bool_field("await", self._await);
+        writer.node_field("left", &self.left);
+        writer.node_field("right", &self.right);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for WhileStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("test", &self.test);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for DoWhileStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("body", &self.body);
+        writer.node_field("test", &self.test);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for SwitchStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("discriminant", &self.discriminant);
+        writer.array_field("cases", &self.cases);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for SwitchCase {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("consequent", &self.consequent);
+        writer.optional_field("test", &self.test);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for BreakStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("label", &self.label);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ContinueStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("label", &self.label);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for LabeledStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("body", &self.body);
+        writer.node_field("label", &self.label);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for EmptyStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ReturnStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("argument", &self.argument);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ThrowStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("argument", &self.argument);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for TryStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("block", &self.block);
+        writer.optional_field("handler", &self.handler);
+        writer.optional_field("finalizer", &self.finalizer);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for CatchClause {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("param", &self.param);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for DebuggerStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for WithStatement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("object", &self.object);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ClassDeclaration {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("id", &self.id);
+        writer.optional_field("superClass", &self.superClass);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ClassExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("id", &self.id);
+        writer.optional_field("superClass", &self.superClass);
+        writer.node_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ClassBody {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for MethodDefinition {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.bool_field("static", self._static);
+        writer.bool_field("computed", self.computed);
+        writer.node_field("key", &self.key);
+        writer.string_field("kind", self.kind.as_str());
+        writer.node_field("value", &self.value);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for PropertyDefinition {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.bool_field("static", self._static);
+        writer.bool_field("computed", self.computed);
+        writer.node_field("key", &self.key);
+        writer.optional_field("value", &self.value);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for StaticBlock {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("body", &self.body);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for PrivateIdentifier {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.string_field("name", &self.name);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for Super {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ModuleExportName {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            ModuleExportName::Identifier(ast) => ast.write_json(writer),
+            ModuleExportName::Literal(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for ImportDeclarationSpecifier {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            ImportDeclarationSpecifier::ImportSpecifier(ast) => ast.write_json(writer),
+            ImportDeclarationSpecifier::ImportDefaultSpecifier(ast) => ast.write_json(writer),
+            ImportDeclarationSpecifier::ImportNamespaceSpecifier(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for ExportDefaultDeclarationKind {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        match self {
+            ExportDefaultDeclarationKind::FunctionDeclaration(ast) => ast.write_json(writer),
+            ExportDefaultDeclarationKind::ClassDeclaration(ast) => ast.write_json(writer),
+            ExportDefaultDeclarationKind::Expression(ast) => ast.write_json(writer),
+        }
+    }
+}
+
+impl WriteJson for ImportDeclaration {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("specifiers", &self.specifiers);
+        writer.node_field("source", &self.source);
+        writer.array_field("attributes", &self.attributes);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ImportSpecifier {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("imported", &self.imported);
+        writer.node_field("local", &self.local);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ImportDefaultSpecifier {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("local", &self.local);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ImportNamespaceSpecifier {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("local", &self.local);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ImportAttribute {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("key", &self.key);
+        writer.node_field("value", &self.value);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ExportNamedDeclaration {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("declaration", &self.declaration);
+        writer.array_field("specifiers", &self.specifiers);
+        writer.optional_field("source", &self.source);
+        writer.array_field("attributes", &self.attributes);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ExportSpecifier {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("local", &self.local);
+        writer.node_field("exported", &self.exported);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ExportDefaultDeclaration {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("declaration", &self.declaration);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ExportAllDeclaration {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.optional_field("exported", &self.exported);
+        writer.node_field("source", &self.source);
+        writer.array_field("attributes", &self.attributes);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for ImportExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("source", &self.source);
+        writer.optional_field("options", &self.options);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for MetaProperty {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("meta", &self.meta);
+        writer.node_field("property", &self.property);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for TemplateLiteral {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("expressions", &self.expressions);
+        writer.array_field("quasis", &self.quasis);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for TemplateElement {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.key("value");
+        writer.begin_object();
+        writer.string_field("raw", &self.value.raw);
+        match &self.value.cooked {
+            Some(cooked) => writer.string_field("cooked", cooked),
+            None => writer.null_field("cooked"),
+        }
+        writer.end_object();
+        writer.bool_field("tail", self.tail);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for TaggedTemplateExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("tag", &self.tag);
+        writer.node_field("quasi", &self.quasi);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for AwaitExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.node_field("argument", &self.argument);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for YieldExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.bool_field("delegate", self.delegate);
+        writer.optional_field("argument", &self.argument);
+        writer.end_object();
+    }
+}
+
+impl WriteJson for SequenceExpression {
+    fn write_json(&self, writer: &mut JsonWriter) {
+        writer.begin_node(&self._type, self.start, self.end, &self.loc);
+        writer.array_field("expressions", &self.expressions);
+        writer.end_object();
+    }
+}
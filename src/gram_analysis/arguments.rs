@@ -0,0 +1,65 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{Context, ExpressionOrSpread, GramAnalysisResult},
+};
+
+use super::{
+    assignment_expression::assignment_expression, spread_element::spread_element,
+    statement::nested_context,
+};
+
+/*
+* <Arguments> ::= '(' ')' | '(' <ArgumentList> ','? ')'
+* <ArgumentList> ::= '...'? <AssignmentExpression> (',' '...'? <AssignmentExpression>)*
+* `cursor` 指向 '('，返回的 next_cursor 指向 ')' 之后
+*/
+pub fn arguments(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Vec<ExpressionOrSpread>>, ParseError> {
+    match tokens.get(cursor) {
+        Some(token) if token._type.label == "(" => {}
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'('")),
+    }
+    let context = &nested_context(tokens, cursor, context)?;
+    let mut next_cursor = cursor + 1;
+    let mut argument_list = Vec::new();
+    loop {
+        // 允许末尾的逗号，如 f(a, b,)
+        if let Some(token) = tokens.get(next_cursor) {
+            if token._type.label == ")" {
+                break;
+            }
+        }
+        let argument_next_cursor = if matches!(tokens.get(next_cursor), Some(token) if token._type.label == "...")
+        {
+            let spread_result = spread_element(tokens, next_cursor, false, context)?;
+            argument_list.push(ExpressionOrSpread::SpreadElement(spread_result.ast));
+            spread_result.next_cursor
+        } else {
+            let argument_result = assignment_expression(tokens, next_cursor, false, context)?;
+            argument_list.push(ExpressionOrSpread::Expression(argument_result.ast));
+            argument_result.next_cursor
+        };
+        match tokens.get(argument_next_cursor) {
+            Some(token) if token._type.label == "," => next_cursor = argument_next_cursor + 1,
+            Some(token) if token._type.label == ")" => {
+                next_cursor = argument_next_cursor;
+                break;
+            }
+            _ => {
+                return Err(ParseError::unexpected(
+                    tokens,
+                    argument_next_cursor,
+                    "',' or ')'",
+                ))
+            }
+        }
+    }
+    return Ok(GramAnalysisResult {
+        ast: argument_list,
+        next_cursor: next_cursor + 1,
+    });
+}
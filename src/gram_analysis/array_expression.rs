@@ -1,6 +1,7 @@
 use crate::{
+    error::ParseError,
     tokenizer::Token,
-    types::{ASTType, ArrayExpression, Expression, GramAnalysisResult},
+    types::{ASTType, ArrayExpression, Context, Expression, GramAnalysisResult, SourceLocation},
 };
 
 use super::element_list::element_list;
@@ -8,50 +9,42 @@ use super::element_list::element_list;
 pub fn array_expression(
     tokens: &[Token],
     cursor: usize,
-) -> Result<GramAnalysisResult<Expression>, &str> {
-    if let Some(token1) = tokens.get(cursor) {
-        if let Some(token2) = tokens.get(cursor + 1) {
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    match tokens.get(cursor) {
+        Some(token) if token._type.label == "[" => {
             let mut array_expression_ast = ArrayExpression {
                 _type: ASTType::ArrayExpression,
-                start: token1.start,
-                end: token2.end,
+                start: token.start,
+                end: token.end,
+                loc: SourceLocation {
+                    start: token.loc.start,
+                    end: token.loc.end,
+                },
                 elements: Vec::new(),
             };
-            if token2._type.label == "]" {
-                return Ok(GramAnalysisResult {
-                    ast: Expression::ArrayExpression(Some(Box::new(array_expression_ast))),
-                    next_cursor: cursor + 2,
-                });
-            } else {
-                if let Ok(mut element_list_result) = element_list(tokens, cursor + 1) {
-                    if let Some(token) = tokens.get(element_list_result.next_cursor) {
-                        if token._type.label == "]" {
-                            array_expression_ast
-                                .elements
-                                .append(&mut element_list_result.elements);
-                            array_expression_ast.end = token.end;
-                            return Ok(GramAnalysisResult {
-                                ast: Expression::ArrayExpression(Some(Box::new(
-                                    array_expression_ast,
-                                ))),
-                                next_cursor: element_list_result.next_cursor + 1,
-                            });
-                        } else if token._type.label == "," {
-                            if let Some(token) = tokens.get(element_list_result.next_cursor + 1) {
-                                if token._type.label == "]" {
-                                    return Ok(GramAnalysisResult {
-                                        ast: Expression::ArrayExpression(Some(Box::new(
-                                            array_expression_ast,
-                                        ))),
-                                        next_cursor: element_list_result.next_cursor + 2,
-                                    });
-                                }
-                            }
-                        }
-                    }
+            let mut element_list_result = element_list(tokens, cursor + 1, context)?;
+            match tokens.get(element_list_result.next_cursor) {
+                Some(token) if token._type.label == "]" => {
+                    array_expression_ast
+                        .elements
+                        .append(&mut element_list_result.elements);
+                    array_expression_ast.end = token.end;
+                    array_expression_ast.loc.end = token.loc.end;
+                    return Ok(GramAnalysisResult {
+                        ast: Expression::ArrayExpression(Some(Box::new(array_expression_ast))),
+                        next_cursor: element_list_result.next_cursor + 1,
+                    });
+                }
+                _ => {
+                    return Err(ParseError::unexpected(
+                        tokens,
+                        element_list_result.next_cursor,
+                        "',' or ']'",
+                    ))
                 }
             }
         }
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'['")),
     }
-    return Err("");
 }
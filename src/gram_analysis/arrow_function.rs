@@ -0,0 +1,138 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, ArrowFunctionBody, ArrowFunctionExpression, Context, Expression,
+        GramAnalysisResult, Pattern, SourceLocation,
+    },
+};
+
+use super::{
+    assignment_expression::assignment_expression,
+    formal_parameters::{check_parameters, formal_parameters},
+    function_body::function_body,
+    identifier::{binding_identifier, is_contextual},
+};
+
+// 找到与 cursor 处 '(' 匹配的 ')'
+fn matching_paren(tokens: &[Token], cursor: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate().skip(cursor) {
+        match token._type.label.as_str() {
+            "(" | "[" | "{" | "${" => depth += 1,
+            ")" | "]" | "}" => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    return None;
+}
+
+// `=>` 与参数之间不能换行
+fn is_arrow(tokens: &[Token], cursor: usize) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == "=>" && !token.newline_before);
+}
+
+/*
+* <ArrowFunction> ::= ('async')? <ArrowParameters> '=>' <ConciseBody>
+* <ArrowParameters> ::= <Identifier> | '(' <FormalParameters> ')'
+* <ConciseBody> ::= '{' <FunctionBody> '}' | <AssignmentExpression>
+* 不是箭头函数时返回 None，由调用方按普通表达式解析
+*/
+pub fn arrow_function(
+    tokens: &[Token],
+    cursor: usize,
+    no_in: bool,
+    context: &Context,
+) -> Result<Option<GramAnalysisResult<Expression>>, ParseError> {
+    let mut params_cursor = cursor;
+    let _async = is_contextual(tokens, cursor, "async")
+        && matches!(
+            tokens.get(cursor + 1),
+            Some(next_token)
+                if (next_token._type.label == "name" || next_token._type.label == "(")
+                    && !next_token.newline_before
+        );
+    if _async {
+        params_cursor += 1;
+    }
+    // 箭头函数继承外层的 super，但不是生成器，只有 async 箭头函数中 await 是运算符
+    let arrow_context = Context {
+        in_async: _async,
+        in_generator: false,
+        in_parameters: false,
+        in_static_block: false,
+        ..context.clone()
+    };
+    // 参数还受外层的限制，异步函数和生成器中的箭头函数参数不能是 await 和 yield
+    let params_context = Context {
+        in_async: _async || context.in_async,
+        ..context.clone()
+    };
+    let (params, arrow_cursor) = match tokens.get(params_cursor) {
+        Some(token) if token._type.label == "name" && is_arrow(tokens, params_cursor + 1) => {
+            let identifier_result = binding_identifier(tokens, params_cursor, &params_context)?;
+            (
+                vec![Pattern::Identifier(identifier_result.ast)],
+                identifier_result.next_cursor,
+            )
+        }
+        Some(token) if token._type.label == "(" => match matching_paren(tokens, params_cursor) {
+            Some(right_paren_cursor) if is_arrow(tokens, right_paren_cursor + 1) => {
+                let params_result = formal_parameters(tokens, params_cursor, &params_context)?;
+                (params_result.ast, params_result.next_cursor)
+            }
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+    let (body, expression, next_cursor) = match tokens.get(arrow_cursor + 1) {
+        Some(token) if token._type.label == "{" => {
+            let body_result = function_body(tokens, arrow_cursor + 1, &arrow_context)?;
+            (
+                ArrowFunctionBody::BlockStatement(body_result.ast),
+                false,
+                body_result.next_cursor,
+            )
+        }
+        _ => {
+            let body_result =
+                assignment_expression(tokens, arrow_cursor + 1, no_in, &arrow_context)?;
+            (
+                ArrowFunctionBody::Expression(body_result.ast),
+                true,
+                body_result.next_cursor,
+            )
+        }
+    };
+    let block = match &body {
+        ArrowFunctionBody::BlockStatement(block) => Some(block),
+        ArrowFunctionBody::Expression(_) => None,
+    };
+    check_parameters(tokens, cursor, None, &params, block, true, context)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = ArrowFunctionExpression {
+        _type: ASTType::ArrowFunctionExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        id: None,
+        expression,
+        generator: false,
+        _async,
+        params,
+        body,
+    };
+    return Ok(Some(GramAnalysisResult {
+        ast: Expression::ArrowFunctionExpression(Some(Box::new(ast))),
+        next_cursor,
+    }));
+}
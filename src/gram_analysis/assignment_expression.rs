@@ -1,3 +1,524 @@
-use crate::{tokenizer::Token, types::GramAnalysisResult};
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, ArrayPattern, AssignmentExpression, AssignmentPattern, AssignmentProperty,
+        Context, Expression, ExpressionOrSpread, GramAnalysisResult, Kind,
+        ObjectExpressionProperty, ObjectPattern, ObjectPatternProperty, Pattern, RestElement,
+        SourceLocation,
+    },
+};
 
-pub fn assignment_expression(tokens: &[Token], cursor: usize) {}
+use super::{
+    arrow_function::arrow_function,
+    binding_pattern::bound_identifiers,
+    conditional_expression::conditional_expression,
+    identifier::{is_contextual, is_eval_or_arguments},
+    statement::nested_context,
+    yield_expression::yield_expression,
+};
+
+fn assignment_operator(label: &str) -> Option<&'static str> {
+    match label {
+        "=" => Some("="),
+        "+=" => Some("+="),
+        "-=" => Some("-="),
+        "*=" => Some("*="),
+        "/=" => Some("/="),
+        "%=" => Some("%="),
+        "**=" => Some("**="),
+        "<<=" => Some("<<="),
+        ">>=" => Some(">>="),
+        ">>>=" => Some(">>>="),
+        "&=" => Some("&="),
+        "|=" => Some("|="),
+        "^=" => Some("^="),
+        "&&=" => Some("&&="),
+        "||=" => Some("||="),
+        "??=" => Some("??="),
+        _ => None,
+    }
+}
+
+// 起始位置为 `start` 的 token，用于找到嵌套在字面量中的元素对应的 token
+fn token_at(tokens: &[Token], start: usize) -> usize {
+    return tokens.partition_point(|token| token.start < start);
+}
+
+fn is_label(tokens: &[Token], cursor: usize, label: &str) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == label);
+}
+
+/// 检查数组和对象字面量中是否残留只能用于解构的 { a = 1 }，
+/// 只深入字面量的元素，其余位置的子表达式在解析时已经检查过
+pub fn check_cover_initialized_name(expression: &Expression) -> Result<(), ParseError> {
+    match expression {
+        Expression::ObjectExpression(Some(ast)) => {
+            for property in &ast.properties {
+                if let ObjectExpressionProperty::Property(property) = property {
+                    if property.shorthand
+                        && matches!(property.value, Expression::AssignmentExpression(_))
+                    {
+                        return Err(ParseError::syntax(
+                            "Shorthand property assignments are valid only in destructuring patterns",
+                            property.start,
+                            property.loc.start,
+                        ));
+                    }
+                    check_cover_initialized_name(&property.value)?;
+                }
+            }
+        }
+        Expression::ArrayExpression(Some(ast)) => {
+            for element in ast.elements.iter().flatten() {
+                match element {
+                    ExpressionOrSpread::Expression(element) => {
+                        check_cover_initialized_name(element)?
+                    }
+                    ExpressionOrSpread::SpreadElement(spread) => {
+                        check_cover_initialized_name(&spread.argument)?
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+    return Ok(());
+}
+
+// 把已经按表达式解析的左侧转换为赋值目标，只有 `=` 允许解构
+pub fn assignment_target(
+    tokens: &[Token],
+    cursor: usize,
+    expression: Expression,
+    allow_pattern: bool,
+) -> Result<Pattern, ParseError> {
+    let token = &tokens[cursor];
+    // `([a]) = b` 中带括号的解构不合法
+    if allow_pattern
+        && token._type.label == "("
+        && matches!(
+            expression,
+            Expression::ArrayExpression(_) | Expression::ObjectExpression(_)
+        )
+    {
+        return Err(ParseError::syntax(
+            "Parenthesized pattern",
+            token.start,
+            token.loc.start,
+        ));
+    }
+    match expression {
+        Expression::Identifier(ast) => return Ok(Pattern::Identifier(ast)),
+        Expression::MemberExpression(ast) => return Ok(Pattern::MemberExpression(ast)),
+        Expression::ArrayExpression(Some(ast)) if allow_pattern => {
+            let close_cursor = token_at(tokens, ast.end - 1);
+            let trailing_comma = is_label(tokens, close_cursor - 1, ",");
+            let count = ast.elements.len();
+            let mut elements = Vec::new();
+            for (index, element) in ast.elements.into_iter().enumerate() {
+                elements.push(match element {
+                    Some(ExpressionOrSpread::Expression(element)) => {
+                        Some(assignment_element(tokens, element)?)
+                    }
+                    Some(ExpressionOrSpread::SpreadElement(spread)) => {
+                        if index + 1 < count || trailing_comma {
+                            return Err(ParseError::syntax(
+                                "Comma is not permitted after the rest element",
+                                spread.start,
+                                spread.loc.start,
+                            ));
+                        }
+                        if let Expression::AssignmentExpression(_) = spread.argument {
+                            return Err(ParseError::syntax(
+                                "Rest elements cannot have a default value",
+                                spread.start,
+                                spread.loc.start,
+                            ));
+                        }
+                        // 数组解构中的 rest 可以继续解构，如 [...[a, b]] = c
+                        let argument_cursor = token_at(tokens, spread.start) + 1;
+                        let argument =
+                            assignment_target(tokens, argument_cursor, spread.argument, true)?;
+                        Some(Pattern::RestElement(Some(Box::new(RestElement {
+                            _type: ASTType::RestElement,
+                            start: spread.start,
+                            end: spread.end,
+                            loc: spread.loc,
+                            argument,
+                        }))))
+                    }
+                    None => None,
+                });
+            }
+            return Ok(Pattern::ArrayPattern(ArrayPattern {
+                _type: ASTType::ArrayPattern,
+                start: ast.start,
+                end: ast.end,
+                loc: ast.loc,
+                elements,
+            }));
+        }
+        Expression::ObjectExpression(Some(ast)) if allow_pattern => {
+            // rest 元素之后不能有逗号，包括末尾的逗号
+            let close_cursor = token_at(tokens, ast.end - 1);
+            let trailing_comma = is_label(tokens, close_cursor - 1, ",");
+            let count = ast.properties.len();
+            let mut properties = Vec::new();
+            for (index, property) in ast.properties.into_iter().enumerate() {
+                match property {
+                    ObjectExpressionProperty::Property(property) => {
+                        if property.method || !matches!(property.kind, Kind::Init) {
+                            return Err(ParseError::syntax(
+                                "Object pattern can't contain getter or setter",
+                                property.start,
+                                property.loc.start,
+                            ));
+                        }
+                        properties.push(ObjectPatternProperty::Property(AssignmentProperty {
+                            _type: ASTType::Property,
+                            start: property.start,
+                            end: property.end,
+                            loc: property.loc,
+                            method: false,
+                            shorthand: property.shorthand,
+                            computed: property.computed,
+                            key: property.key,
+                            value: assignment_element(tokens, property.value)?,
+                            kind: Kind::Init,
+                        }));
+                    }
+                    ObjectExpressionProperty::SpreadElement(spread) => {
+                        if index + 1 < count || trailing_comma {
+                            return Err(ParseError::syntax(
+                                "Comma is not permitted after the rest element",
+                                spread.start,
+                                spread.loc.start,
+                            ));
+                        }
+                        // 对象解构中的 rest 只能是标识符或成员表达式
+                        let argument_cursor = token_at(tokens, spread.start) + 1;
+                        let argument =
+                            assignment_target(tokens, argument_cursor, spread.argument, false)?;
+                        properties.push(ObjectPatternProperty::RestElement(RestElement {
+                            _type: ASTType::RestElement,
+                            start: spread.start,
+                            end: spread.end,
+                            loc: spread.loc,
+                            argument,
+                        }));
+                    }
+                }
+            }
+            return Ok(Pattern::ObjectPattern(ObjectPattern {
+                _type: ASTType::ObjectPattern,
+                start: ast.start,
+                end: ast.end,
+                loc: ast.loc,
+                properties,
+            }));
+        }
+        _ => {
+            return Err(ParseError::syntax(
+                "Assigning to rvalue",
+                token.start,
+                token.loc.start,
+            ))
+        }
+    }
+}
+
+// 严格模式中不能给 eval 和 arguments 赋值，包括解构赋值中的目标
+pub fn check_strict_assignment(pattern: &Pattern, context: &Context) -> Result<(), ParseError> {
+    if !context.strict {
+        return Ok(());
+    }
+    let mut identifiers = Vec::new();
+    bound_identifiers(pattern, &mut identifiers);
+    for identifier in identifiers {
+        if is_eval_or_arguments(&identifier.name) {
+            return Err(ParseError::syntax(
+                format!("Assigning to {} in strict mode", identifier.name),
+                identifier.start,
+                identifier.loc.start,
+            ));
+        }
+    }
+    return Ok(());
+}
+
+// 解构模式中的元素和属性值，`a = 1` 表示默认值
+fn assignment_element(tokens: &[Token], expression: Expression) -> Result<Pattern, ParseError> {
+    if let Expression::AssignmentExpression(Some(ast)) = expression {
+        let cursor = token_at(tokens, ast.start);
+        if ast.operator != "=" {
+            let token = &tokens[cursor];
+            return Err(ParseError::syntax(
+                "Assigning to rvalue",
+                token.start,
+                token.loc.start,
+            ));
+        }
+        // [(a = 1)] = b 中带括号的默认值不合法，而 [(a) = 1] = b 中的括号属于左侧
+        if cursor > 0 && is_label(tokens, cursor - 1, "(") {
+            let token = &tokens[cursor - 1];
+            return Err(ParseError::syntax(
+                "Parenthesized pattern",
+                token.start,
+                token.loc.start,
+            ));
+        }
+        return Ok(Pattern::AssignmentPattern(Some(Box::new(
+            AssignmentPattern {
+                _type: ASTType::AssignmentPattern,
+                start: ast.start,
+                end: ast.end,
+                loc: ast.loc,
+                left: ast.left,
+                right: ast.right,
+            },
+        ))));
+    }
+    let mut cursor = token_at(tokens, expression.start());
+    // 括号不属于表达式节点，[([a])] = b 中的括号由前一个 token 判断
+    if cursor > 0 && is_label(tokens, cursor - 1, "(") {
+        cursor -= 1;
+    }
+    return assignment_target(tokens, cursor, expression, true);
+}
+
+/*
+* <AssignmentExpression> ::= <ConditionalExpression> | <YieldExpression> | <ArrowFunction> | <LeftHandSideExpression> <AssignmentOperator> <AssignmentExpression>
+* 右结合：`a = b = c` 等价于 `a = (b = c)`
+*/
+pub fn assignment_expression(
+    tokens: &[Token],
+    cursor: usize,
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let result = cover_assignment_expression(tokens, cursor, no_in, context)?;
+    check_cover_initialized_name(&result.ast)?;
+    return Ok(result);
+}
+
+/// 与 assignment_expression 相同，但保留 { a = 1 } 这样只能用于解构的简写，用于之后可能
+/// 转换为解构模式的位置：数组和对象字面量的元素、for 语句的头部，由调用方负责检查
+pub fn cover_assignment_expression(
+    tokens: &[Token],
+    cursor: usize,
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    // 括号、数组、参数、赋值和条件表达式的嵌套都会经过这里
+    let context = &nested_context(tokens, cursor, context)?;
+    if context.in_generator && is_contextual(tokens, cursor, "yield") {
+        return yield_expression(tokens, cursor, no_in, context);
+    }
+    if let Some(arrow_result) = arrow_function(tokens, cursor, no_in, context)? {
+        return Ok(arrow_result);
+    }
+    let left_result = conditional_expression(tokens, cursor, no_in, context)?;
+    match tokens
+        .get(left_result.next_cursor)
+        .and_then(|token| assignment_operator(&token._type.label))
+    {
+        Some(operator) => {
+            return assignment_operation(tokens, cursor, left_result, operator, no_in, context)
+        }
+        None => return Ok(left_result),
+    }
+}
+
+// 已经解析出赋值运算符左侧的表达式，将其转换为赋值目标后解析右侧
+fn assignment_operation(
+    tokens: &[Token],
+    cursor: usize,
+    left_result: GramAnalysisResult<Expression>,
+    operator: &'static str,
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let left = assignment_target(tokens, cursor, left_result.ast, operator == "=")?;
+    check_strict_assignment(&left, context)?;
+    let right_result = assignment_expression(tokens, left_result.next_cursor + 1, no_in, context)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[right_result.next_cursor - 1];
+    let ast = AssignmentExpression {
+        _type: ASTType::AssignmentExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        operator,
+        left,
+        right: right_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::AssignmentExpression(Some(Box::new(ast))),
+        next_cursor: right_result.next_cursor,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn strict_mode_assignment() {
+        assert_errors(
+            script,
+            &[
+                ("'use strict'; eval = 1", "Assigning to eval in strict mode"),
+                (
+                    "'use strict'; arguments += 1",
+                    "Assigning to arguments in strict mode",
+                ),
+                (
+                    "'use strict'; [eval] = a",
+                    "Assigning to eval in strict mode",
+                ),
+                (
+                    "'use strict'; ({ a: arguments } = b)",
+                    "Assigning to arguments in strict mode",
+                ),
+                ("'use strict'; eval++", "Assigning to eval in strict mode"),
+                (
+                    "'use strict'; --arguments",
+                    "Assigning to arguments in strict mode",
+                ),
+                (
+                    "'use strict'; for (eval in a);",
+                    "Assigning to eval in strict mode",
+                ),
+                ("'use strict'; var eval", "Binding eval in strict mode"),
+                (
+                    "'use strict'; try {} catch (arguments) {}",
+                    "Binding arguments in strict mode",
+                ),
+                ("class A { m(eval) {} }", "Binding eval in strict mode"),
+            ],
+        );
+        assert_json(
+            script,
+            &[
+                ("eval = 1; arguments++", r#""name":"arguments""#),
+                ("'use strict'; a.eval = eval(arguments)", r#""name":"eval""#),
+            ],
+        );
+    }
+
+    #[test]
+    fn assignment_patterns() {
+        // 先按对象和数组字面量解析，遇到 `=` 后再转换为解构模式
+        assert_json(
+            script,
+            &[
+                (
+                    "[a, {b: c.d}, ...e[0]] = f",
+                    r#""left":{"type":"ArrayPattern","start":0,"end":22,"elements":[{"type":"Identifier","start":1,"end":2,"name":"a"},{"type":"ObjectPattern","start":4,"end":12,"#,
+                ),
+                (
+                    "[a, {b: c.d}, ...e[0]] = f",
+                    r#"{"type":"RestElement","start":14,"end":21,"argument":{"type":"MemberExpression""#,
+                ),
+                (
+                    "({a, b: [c], ...d} = e)",
+                    r#"{"type":"RestElement","start":13,"end":17,"argument":{"type":"Identifier","start":16,"end":17,"name":"d"}}"#,
+                ),
+                (
+                    "({a = 1} = b)",
+                    r#""value":{"type":"AssignmentPattern","start":2,"end":7,"#,
+                ),
+                ("[(a) = 1, (b.c)] = d", r#""type":"AssignmentPattern""#),
+                ("[[a], ...[b]] = c", r#""argument":{"type":"ArrayPattern""#),
+                ("for ([a, b] of c);", r#""left":{"type":"ArrayPattern""#),
+                // 不赋值时仍然是字面量
+                ("[a, {b}]", r#""type":"ArrayExpression""#),
+            ],
+        );
+        assert_errors(
+            script,
+            &[
+                (
+                    "[...a, b] = c",
+                    "Comma is not permitted after the rest element",
+                ),
+                (
+                    "[...a,] = c",
+                    "Comma is not permitted after the rest element",
+                ),
+                ("({a: 1} = b)", "Assigning to rvalue"),
+                ("[a + b] = c", "Assigning to rvalue"),
+                (
+                    "({a() {}} = b)",
+                    "Object pattern can't contain getter or setter",
+                ),
+                ("[(a = 1)] = b", "Parenthesized pattern"),
+                ("({a: (b = 1)} = c)", "Parenthesized pattern"),
+                ("([a]) = b", "Parenthesized pattern"),
+                (
+                    "({a = 1})",
+                    "Shorthand property assignments are valid only in destructuring patterns",
+                ),
+                (
+                    "[...a = 1] = b",
+                    "Rest elements cannot have a default value",
+                ),
+                ("({...{a}} = b)", "Assigning to rvalue"),
+                ("[a] += b", "Assigning to rvalue"),
+            ],
+        );
+    }
+
+    #[test]
+    fn logical_assignment() {
+        assert_json(
+            script,
+            &[
+                // 右结合：a ||= (b &&= (c ??= d))
+                (
+                    "a ||= b &&= c ??= d",
+                    r#""operator":"||=","left":{"type":"Identifier","start":0,"end":1,"name":"a"},"right":{"type":"AssignmentExpression","start":6,"end":19,"operator":"&&=","#,
+                ),
+                (
+                    "a ||= b &&= c ??= d",
+                    r#""right":{"type":"AssignmentExpression","start":12,"end":19,"operator":"??=","#,
+                ),
+                (
+                    "a.b ??= c",
+                    r#""operator":"??=","left":{"type":"MemberExpression","start":0,"end":3,"#,
+                ),
+                ("a[0] ||= b", r#""computed":true"#),
+                // 括号中的简单目标同样可以赋值
+                (
+                    "(a) &&= b",
+                    r#""operator":"&&=","left":{"type":"Identifier","start":1,"end":2,"name":"a"}"#,
+                ),
+                (
+                    "a ??= b ?? c",
+                    r#""right":{"type":"LogicalExpression","start":6,"end":12,"#,
+                ),
+                ("async ||= 1", r#""name":"async""#),
+            ],
+        );
+        assert_errors(
+            script,
+            &[
+                // 逻辑赋值不能解构
+                ("[a] ||= b", "Assigning to rvalue"),
+                ("({a} ??= b)", "Assigning to rvalue"),
+                ("a() ??= b", "Assigning to rvalue"),
+                ("a?.b ||= c", "Assigning to rvalue"),
+                ("a++ ||= b", "Assigning to rvalue"),
+                ("a ?? = b", "expected expression, found '='"),
+                (
+                    "'use strict'; eval ||= 1",
+                    "Assigning to eval in strict mode",
+                ),
+            ],
+        );
+    }
+}
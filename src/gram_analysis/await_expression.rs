@@ -0,0 +1,49 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, AwaitExpression, Context, Expression, GramAnalysisResult, SourceLocation},
+};
+
+use super::{
+    assignment_expression::check_cover_initialized_name, identifier::is_contextual,
+    unary_expression::unary_expression,
+};
+
+/*
+* <AwaitExpression> ::= 'await' <UnaryExpression>
+* 只在异步函数和模块的顶层解析，其余位置的 await 由调用方按标识符处理
+*/
+pub fn await_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let start_token = match tokens.get(cursor) {
+        Some(token) if is_contextual(tokens, cursor, "await") => token,
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'await'")),
+    };
+    if context.in_parameters {
+        return Err(ParseError::syntax(
+            "Await expression cannot be a default value",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+    let argument_result = unary_expression(tokens, cursor + 1, context)?;
+    check_cover_initialized_name(&argument_result.ast)?;
+    let end_token = &tokens[argument_result.next_cursor - 1];
+    let ast = AwaitExpression {
+        _type: ASTType::AwaitExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        argument: argument_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::AwaitExpression(Some(Box::new(ast))),
+        next_cursor: argument_result.next_cursor,
+    });
+}
@@ -0,0 +1,196 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, BinaryExpression, Context, Expression, GramAnalysisResult, LogicalExpression,
+        SourceLocation,
+    },
+};
+
+use super::{
+    assignment_expression::check_cover_initialized_name, statement::nested_context,
+    unary_expression::unary_expression,
+};
+
+// 二元运算符及其优先级，数值越大结合越紧（与 acorn 的 binop 一致）
+fn binary_operator(label: &str) -> Option<(&'static str, u8)> {
+    match label {
+        "??" => Some(("??", 1)),
+        "||" => Some(("||", 1)),
+        "&&" => Some(("&&", 2)),
+        "|" => Some(("|", 3)),
+        "^" => Some(("^", 4)),
+        "&" => Some(("&", 5)),
+        "==" => Some(("==", 6)),
+        "!=" => Some(("!=", 6)),
+        "===" => Some(("===", 6)),
+        "!==" => Some(("!==", 6)),
+        "<" => Some(("<", 7)),
+        ">" => Some((">", 7)),
+        "<=" => Some(("<=", 7)),
+        ">=" => Some((">=", 7)),
+        "instanceof" => Some(("instanceof", 7)),
+        "in" => Some(("in", 7)),
+        "<<" => Some(("<<", 8)),
+        ">>" => Some((">>", 8)),
+        ">>>" => Some((">>>", 8)),
+        "+" => Some(("+", 9)),
+        "-" => Some(("-", 9)),
+        "*" => Some(("*", 10)),
+        "/" => Some(("/", 10)),
+        "%" => Some(("%", 10)),
+        "**" => Some(("**", 11)),
+        _ => None,
+    }
+}
+
+fn is_logical_operator(operator: &str) -> bool {
+    return operator == "||" || operator == "&&" || operator == "??";
+}
+
+// `??` 不能和未加括号的 `||`、`&&` 混用
+fn is_mixed_coalesce(operator: &str, operand_operator: Option<&str>) -> bool {
+    match operand_operator {
+        Some(operand_operator) => {
+            (operator == "??" && (operand_operator == "||" || operand_operator == "&&"))
+                || (operand_operator == "??" && (operator == "||" || operator == "&&"))
+        }
+        None => false,
+    }
+}
+
+struct OperationResult {
+    ast: Expression,
+    next_cursor: usize,
+    // 最外层由本次解析生成的运算符，操作数来自括号或一元表达式时为 None
+    operator: Option<&'static str>,
+}
+
+/*
+* <BinaryExpression> ::= <UnaryExpression> | <BinaryExpression> <Operator> <BinaryExpression>
+* 使用优先级爬升法解析，`**` 为右结合，其余运算符左结合
+* `no_in` 为 true 时不把 `in` 当作运算符，用于 for 语句头部的初始化表达式
+*/
+pub fn binary_expression(
+    tokens: &[Token],
+    cursor: usize,
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let result = binary_operation(tokens, cursor, 0, no_in, context)?;
+    return Ok(GramAnalysisResult {
+        ast: result.ast,
+        next_cursor: result.next_cursor,
+    });
+}
+
+// 解析优先级高于 `min_precedence` 的运算
+fn binary_operation(
+    tokens: &[Token],
+    cursor: usize,
+    min_precedence: u8,
+    no_in: bool,
+    context: &Context,
+) -> Result<OperationResult, ParseError> {
+    let operand_result = unary_expression(tokens, cursor, context)?;
+    let operand = OperationResult {
+        ast: operand_result.ast,
+        next_cursor: operand_result.next_cursor,
+        operator: None,
+    };
+    return binary_operators(tokens, cursor, operand, min_precedence, no_in, context);
+}
+
+// 解析第一个操作数之后的运算符和右侧操作数，右侧优先级更高的运算先结合
+fn binary_operators(
+    tokens: &[Token],
+    cursor: usize,
+    operand: OperationResult,
+    min_precedence: u8,
+    no_in: bool,
+    context: &Context,
+) -> Result<OperationResult, ParseError> {
+    let mut left = operand.ast;
+    let mut left_operator = operand.operator;
+    let mut next_cursor = operand.next_cursor;
+    let start_token = &tokens[cursor];
+    while let Some(token) = tokens.get(next_cursor) {
+        let (operator, precedence) = match binary_operator(&token._type.label) {
+            Some(binary_operator)
+                if binary_operator.1 > min_precedence && !(no_in && binary_operator.0 == "in") =>
+            {
+                binary_operator
+            }
+            _ => break,
+        };
+        if operator == "**"
+            && left_operator.is_none()
+            && matches!(left, Expression::UnaryExpression(_))
+            && start_token._type.label != "("
+        {
+            return Err(ParseError::syntax(
+                "Unary operator used immediately before exponentiation expression. Parenthesis must be used to disambiguate operator precedence",
+                start_token.start,
+                start_token.loc.start,
+            ));
+        }
+        let right_precedence = if operator == "**" {
+            precedence - 1
+        } else {
+            precedence
+        };
+        // `**` 右结合，a ** b ** c 的右侧可以任意嵌套
+        let right_context = nested_context(tokens, next_cursor + 1, context)?;
+        let right_result = binary_operation(
+            tokens,
+            next_cursor + 1,
+            right_precedence,
+            no_in,
+            &right_context,
+        )?;
+        check_cover_initialized_name(&left)?;
+        check_cover_initialized_name(&right_result.ast)?;
+        if is_mixed_coalesce(operator, left_operator)
+            || is_mixed_coalesce(operator, right_result.operator)
+        {
+            return Err(ParseError::syntax(
+                "Logical expressions and coalesce expressions cannot be mixed. Wrap either by parentheses",
+                token.start,
+                token.loc.start,
+            ));
+        }
+        let end_token = &tokens[right_result.next_cursor - 1];
+        let loc = SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        };
+        if is_logical_operator(operator) {
+            left = Expression::LogicalExpression(Some(Box::new(LogicalExpression {
+                _type: ASTType::LogicalExpression,
+                start: start_token.start,
+                end: end_token.end,
+                loc,
+                left,
+                operator,
+                right: right_result.ast,
+            })));
+        } else {
+            left = Expression::BinaryExpression(Some(Box::new(BinaryExpression {
+                _type: ASTType::BinaryExpression,
+                start: start_token.start,
+                end: end_token.end,
+                loc,
+                left,
+                operator,
+                right: right_result.ast,
+            })));
+        }
+        left_operator = Some(operator);
+        next_cursor = right_result.next_cursor;
+    }
+    return Ok(OperationResult {
+        ast: left,
+        next_cursor,
+        operator: left_operator,
+    });
+}
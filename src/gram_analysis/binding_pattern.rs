@@ -0,0 +1,374 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, ArrayPattern, AssignmentPattern, AssignmentProperty, Context, GramAnalysisResult,
+        Identifier, Kind, ObjectPattern, ObjectPatternProperty, Pattern, PropertyKey, RestElement,
+        SourceLocation,
+    },
+};
+
+use super::{
+    assignment_expression::assignment_expression, identifier::binding_identifier,
+    object_property::property_key, statement::nested_context,
+};
+
+fn is_label(tokens: &[Token], cursor: usize, label: &str) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == label);
+}
+
+/// 模式中声明的所有标识符，如 { a, b: [c = 1, ...d] } 中的 a、c、d
+pub fn bound_identifiers<'a>(pattern: &'a Pattern, identifiers: &mut Vec<&'a Identifier>) {
+    match pattern {
+        Pattern::Identifier(identifier) => identifiers.push(identifier),
+        Pattern::MemberExpression(_) => {}
+        Pattern::ArrayPattern(ast) => {
+            for element in ast.elements.iter().flatten() {
+                bound_identifiers(element, identifiers);
+            }
+        }
+        Pattern::ObjectPattern(ast) => {
+            for property in &ast.properties {
+                match property {
+                    ObjectPatternProperty::Property(property) => {
+                        bound_identifiers(&property.value, identifiers)
+                    }
+                    ObjectPatternProperty::RestElement(rest) => {
+                        bound_identifiers(&rest.argument, identifiers)
+                    }
+                }
+            }
+        }
+        Pattern::RestElement(Some(ast)) => bound_identifiers(&ast.argument, identifiers),
+        Pattern::AssignmentPattern(Some(ast)) => bound_identifiers(&ast.left, identifiers),
+        Pattern::RestElement(None) | Pattern::AssignmentPattern(None) => {}
+    }
+}
+
+/*
+* <BindingElement> ::= <BindingTarget> | <BindingTarget> '=' <AssignmentExpression>
+*/
+pub fn binding_element(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Pattern>, ParseError> {
+    let target_result = binding_target(tokens, cursor, context)?;
+    if !is_label(tokens, target_result.next_cursor, "=") {
+        return Ok(target_result);
+    }
+    let right_result =
+        assignment_expression(tokens, target_result.next_cursor + 1, false, context)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[right_result.next_cursor - 1];
+    let ast = AssignmentPattern {
+        _type: ASTType::AssignmentPattern,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        left: target_result.ast,
+        right: right_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Pattern::AssignmentPattern(Some(Box::new(ast))),
+        next_cursor: right_result.next_cursor,
+    });
+}
+
+/*
+* <BindingTarget> ::= <Identifier> | <ArrayBindingPattern> | <ObjectBindingPattern>
+*/
+pub fn binding_target(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Pattern>, ParseError> {
+    if is_label(tokens, cursor, "[") {
+        let context = &nested_context(tokens, cursor, context)?;
+        return array_binding_pattern(tokens, cursor, context);
+    } else if is_label(tokens, cursor, "{") {
+        let context = &nested_context(tokens, cursor, context)?;
+        return object_binding_pattern(tokens, cursor, context);
+    }
+    let identifier_result = binding_identifier(tokens, cursor, context)?;
+    return Ok(GramAnalysisResult {
+        ast: Pattern::Identifier(identifier_result.ast),
+        next_cursor: identifier_result.next_cursor,
+    });
+}
+
+/// `...` 之后的绑定目标，rest 元素必须是最后一个并且不能有默认值和末尾逗号
+pub fn rest_element(
+    tokens: &[Token],
+    cursor: usize,
+    close_label: &str,
+    context: &Context,
+) -> Result<GramAnalysisResult<RestElement>, ParseError> {
+    let argument_result = binding_target(tokens, cursor + 1, context)?;
+    match tokens.get(argument_result.next_cursor) {
+        Some(token) if token._type.label == close_label => {}
+        Some(token) if token._type.label == "," => {
+            return Err(ParseError::syntax(
+                "Comma is not permitted after the rest element",
+                token.start,
+                token.loc.start,
+            ));
+        }
+        _ => {
+            return Err(ParseError::unexpected(
+                tokens,
+                argument_result.next_cursor,
+                &format!("'{}'", close_label),
+            ))
+        }
+    }
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[argument_result.next_cursor - 1];
+    let ast = RestElement {
+        _type: ASTType::RestElement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        argument: argument_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: argument_result.next_cursor,
+    });
+}
+
+/*
+* <ArrayBindingPattern> ::= '[' (<BindingElement>? ',')* (<BindingElement> | '...' <BindingTarget>)? ']'
+*/
+fn array_binding_pattern(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Pattern>, ParseError> {
+    let mut next_cursor = cursor + 1;
+    let mut elements = Vec::new();
+    loop {
+        match tokens.get(next_cursor) {
+            Some(token) if token._type.label == "]" => break,
+            // 空位，如 [a, , b]
+            Some(token) if token._type.label == "," => {
+                elements.push(None);
+                next_cursor += 1;
+                continue;
+            }
+            Some(token) if token._type.label == "..." => {
+                let rest_result = rest_element(tokens, next_cursor, "]", context)?;
+                elements.push(Some(Pattern::RestElement(Some(Box::new(rest_result.ast)))));
+                next_cursor = rest_result.next_cursor;
+                break;
+            }
+            _ => {}
+        }
+        let element_result = binding_element(tokens, next_cursor, context)?;
+        elements.push(Some(element_result.ast));
+        match tokens.get(element_result.next_cursor) {
+            Some(token) if token._type.label == "," => next_cursor = element_result.next_cursor + 1,
+            Some(token) if token._type.label == "]" => {
+                next_cursor = element_result.next_cursor;
+                break;
+            }
+            _ => {
+                return Err(ParseError::unexpected(
+                    tokens,
+                    element_result.next_cursor,
+                    "',' or ']'",
+                ))
+            }
+        }
+    }
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor];
+    let ast = ArrayPattern {
+        _type: ASTType::ArrayPattern,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        elements,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Pattern::ArrayPattern(ast),
+        next_cursor: next_cursor + 1,
+    });
+}
+
+/*
+* <ObjectBindingPattern> ::= '{' (<BindingProperty> ',')* (<BindingProperty> | '...' <Identifier>)? '}'
+* <BindingProperty> ::= <Identifier> ('=' <AssignmentExpression>)? | <PropertyName> ':' <BindingElement>
+*/
+fn object_binding_pattern(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Pattern>, ParseError> {
+    let mut next_cursor = cursor + 1;
+    let mut properties = Vec::new();
+    loop {
+        match tokens.get(next_cursor) {
+            Some(token) if token._type.label == "}" => break,
+            Some(token) if token._type.label == "..." => {
+                // 对象中的 rest 只能是标识符
+                if !is_label(tokens, next_cursor + 1, "name") {
+                    return Err(ParseError::unexpected(
+                        tokens,
+                        next_cursor + 1,
+                        "identifier",
+                    ));
+                }
+                let rest_result = rest_element(tokens, next_cursor, "}", context)?;
+                properties.push(ObjectPatternProperty::RestElement(rest_result.ast));
+                next_cursor = rest_result.next_cursor;
+                break;
+            }
+            _ => {}
+        }
+        let property_result = binding_property(tokens, next_cursor, context)?;
+        properties.push(ObjectPatternProperty::Property(property_result.ast));
+        match tokens.get(property_result.next_cursor) {
+            Some(token) if token._type.label == "," => {
+                next_cursor = property_result.next_cursor + 1
+            }
+            Some(token) if token._type.label == "}" => {
+                next_cursor = property_result.next_cursor;
+                break;
+            }
+            _ => {
+                return Err(ParseError::unexpected(
+                    tokens,
+                    property_result.next_cursor,
+                    "',' or '}'",
+                ))
+            }
+        }
+    }
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor];
+    let ast = ObjectPattern {
+        _type: ASTType::ObjectPattern,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        properties,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Pattern::ObjectPattern(ast),
+        next_cursor: next_cursor + 1,
+    });
+}
+
+fn binding_property(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<AssignmentProperty>, ParseError> {
+    let key_result = property_key(tokens, cursor, context)?;
+    let key = key_result.ast;
+    let computed = matches!(key, PropertyKey::ComputedPropertyKey(_));
+    let mut shorthand = false;
+    let value_result = if is_label(tokens, key_result.next_cursor, ":") {
+        binding_element(tokens, key_result.next_cursor + 1, context)?
+    } else if is_label(tokens, cursor, "name") {
+        // 简写属性 { a } 或 { a = 1 }，值是与属性名相同的标识符
+        shorthand = true;
+        binding_element(tokens, cursor, context)?
+    } else {
+        return Err(ParseError::unexpected(
+            tokens,
+            key_result.next_cursor,
+            "':'",
+        ));
+    };
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[value_result.next_cursor - 1];
+    let ast = AssignmentProperty {
+        _type: ASTType::Property,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        method: false,
+        shorthand,
+        computed,
+        key,
+        value: value_result.ast,
+        kind: Kind::Init,
+    };
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: value_result.next_cursor,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn binding_patterns() {
+        assert_json(
+            script,
+            &[
+                (
+                    "const {a, b: [c = 1, ...d]} = x",
+                    r#""value":{"type":"ArrayPattern","start":13,"end":26,"elements":[{"type":"AssignmentPattern","start":14,"end":19,"left":{"type":"Identifier","start":14,"end":15,"name":"c"},"right":{"type":"Literal","start":18,"end":19,"value":1,"raw":"1"}},{"type":"RestElement","start":21,"end":25,"argument":{"type":"Identifier","start":24,"end":25,"name":"d"}}]}"#,
+                ),
+                (
+                    "const {a, b: [c = 1, ...d]} = x",
+                    r#""properties":[{"type":"Property","start":7,"end":8,"method":false,"shorthand":true,"computed":false,"key":{"type":"Identifier","start":7,"end":8,"name":"a"},"value":{"type":"Identifier","start":7,"end":8,"name":"a"},"kind":"init"}"#,
+                ),
+                (
+                    "let [a, , b] = c",
+                    r#""elements":[{"type":"Identifier","start":5,"end":6,"name":"a"},null,{"type":"Identifier","start":10,"end":11,"name":"b"}]"#,
+                ),
+                (
+                    "try {} catch ({a, b: [c]}) {}",
+                    r#""param":{"type":"ObjectPattern","start":14,"end":25,"#,
+                ),
+                (
+                    "function f({a = 1}, [b, ...c]) {}",
+                    r#""value":{"type":"AssignmentPattern","start":12,"end":17,"left":{"type":"Identifier","start":12,"end":13,"name":"a"}"#,
+                ),
+                (
+                    "function f({a = 1}, [b, ...c]) {}",
+                    r#"{"type":"ArrayPattern","start":20,"end":29,"elements":[{"type":"Identifier","start":21,"end":22,"name":"b"},{"type":"RestElement","start":24,"end":28,"#,
+                ),
+                ("(({a: [b]}) => b)", r#""params":[{"type":"ObjectPattern""#),
+                ("var {[a]: b, ...c} = d", r#""computed":true"#),
+            ],
+        );
+        assert_errors(
+            script,
+            &[
+                (
+                    "let [a, ...b,] = c",
+                    "Comma is not permitted after the rest element",
+                ),
+                ("let {...[a]} = b", "expected identifier, found '['"),
+                ("let {a: b.c} = d", "expected ',' or '}', found '.'"),
+                (
+                    "let [a]",
+                    "Complex binding patterns require an initialization value",
+                ),
+                ("function f([a], a) {}", "Argument name clash"),
+            ],
+        );
+    }
+}
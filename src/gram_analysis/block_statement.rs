@@ -1,37 +1,55 @@
 use crate::{
+    error::ParseError,
     tokenizer::Token,
-    types::{ASTType, BlockStatement, Body, GramAnalysisResult},
+    types::{ASTType, BlockStatement, Body, Context, GramAnalysisResult, SourceLocation},
 };
 
 use super::statement_list::statement_list;
 
-pub fn block_statement(tokens: &[Token], cursor: usize) -> Result<GramAnalysisResult<Body>, &str> {
-    if let Some(token) = tokens.get(cursor) {
-        println!("block_statement");
-        let mut ast = BlockStatement {
-            _type: ASTType::BlockStatement,
-            start: token.start,
-            end: token.end,
-            body: Vec::new(),
-        };
-        if let Ok(mut statement_list_result) = statement_list(tokens, cursor + 1) {
-            ast.body.append(&mut statement_list_result.statements);
-            if let Some(next_token) = tokens.get(statement_list_result.next_cursor) {
-                if next_token._type.label == ";" {
-                    return Ok(GramAnalysisResult {
-                        ast: Body::BlockStatement(ast),
-                        next_cursor: statement_list_result.next_cursor + 1,
-                    });
-                }
-            }
+/*
+* <BlockStatement> ::= '{' <StatementList>? '}'
+*/
+pub fn block_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let block_result = block(tokens, cursor, context)?;
+    return Ok(GramAnalysisResult {
+        ast: Body::BlockStatement(block_result.ast),
+        next_cursor: block_result.next_cursor,
+    });
+}
+
+/// 花括号包裹的语句列表，函数体、try/catch/finally 等也由它解析
+pub fn block(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<BlockStatement>, ParseError> {
+    let start_token = match tokens.get(cursor) {
+        Some(token) if token._type.label == "{" => token,
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'{'")),
+    };
+    let statement_list_result = statement_list(tokens, cursor + 1, context)?;
+    let right_brace_cursor = statement_list_result.next_cursor;
+    match tokens.get(right_brace_cursor) {
+        Some(end_token) if end_token._type.label == "}" => {
+            let ast = BlockStatement {
+                _type: ASTType::BlockStatement,
+                start: start_token.start,
+                end: end_token.end,
+                loc: SourceLocation {
+                    start: start_token.loc.start,
+                    end: end_token.loc.end,
+                },
+                body: statement_list_result.statements,
+            };
             return Ok(GramAnalysisResult {
-                ast: Body::BlockStatement(ast),
-                next_cursor: statement_list_result.next_cursor,
+                ast,
+                next_cursor: right_brace_cursor + 1,
             });
-        } else {
-            return Err("");
         }
-    } else {
-        return Err("");
+        _ => return Err(ParseError::unexpected(tokens, right_brace_cursor, "'}'")),
     }
 }
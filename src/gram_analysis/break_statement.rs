@@ -0,0 +1,57 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, BreakStatement, Context, GramAnalysisResult, SourceLocation},
+};
+
+use super::{identifier::identifier, statement::consume_semicolon};
+
+/*
+* <BreakStatement> ::= 'break' ';' | 'break' <Identifier> ';'
+* 标签必须与 break 在同一行，否则视为省略了分号
+*/
+pub fn break_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let start_token = &tokens[cursor];
+    let mut next_cursor = cursor + 1;
+    let mut label = None;
+    match tokens.get(next_cursor) {
+        Some(token) if token._type.label == "name" && !token.newline_before => {
+            let identifier_result = identifier(tokens, next_cursor)?;
+            next_cursor = identifier_result.next_cursor;
+            label = Some(identifier_result.ast);
+        }
+        _ => {}
+    }
+    // 带标签时标签必须存在，不带标签时必须在循环或 switch 中
+    let valid = match &label {
+        Some(label) => context.labels.iter().any(|item| item.name == label.name),
+        None => context.in_iteration || context.in_switch,
+    };
+    if !valid {
+        return Err(ParseError::syntax(
+            "Unsyntactic break",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+    next_cursor = consume_semicolon(tokens, next_cursor)?;
+    let end_token = &tokens[next_cursor - 1];
+    let ast = BreakStatement {
+        _type: ASTType::BreakStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        label,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::BreakStatement(ast),
+        next_cursor,
+    });
+}
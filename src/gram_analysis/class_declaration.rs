@@ -0,0 +1,50 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, ClassDeclaration, Context, GramAnalysisResult},
+};
+
+use super::class_expression::class_expression;
+
+/*
+* <ClassDeclaration> ::= 'class' <Identifier> ('extends' <LeftHandSideExpression>)? '{' <ClassBody> '}'
+*/
+pub fn class_declaration(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let declaration_result = class_declaration_node(tokens, cursor, false, context)?;
+    return Ok(GramAnalysisResult {
+        ast: Body::ClassDeclaration(declaration_result.ast),
+        next_cursor: declaration_result.next_cursor,
+    });
+}
+
+/// 类声明，`allow_anonymous` 为 true 时用于 export default，可以省略类名
+pub fn class_declaration_node(
+    tokens: &[Token],
+    cursor: usize,
+    allow_anonymous: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<ClassDeclaration>, ParseError> {
+    let class_result = class_expression(tokens, cursor, context)?;
+    let class_ast = class_result.ast;
+    // 声明必须有类名，报错位置在 class 之后
+    if class_ast.id.is_none() && !allow_anonymous {
+        return Err(ParseError::unexpected(tokens, cursor + 1, "identifier"));
+    }
+    let ast = ClassDeclaration {
+        _type: ASTType::ClassDeclaration,
+        start: class_ast.start,
+        end: class_ast.end,
+        loc: class_ast.loc,
+        id: class_ast.id,
+        superClass: class_ast.superClass,
+        body: class_ast.body,
+    };
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: class_result.next_cursor,
+    });
+}
@@ -0,0 +1,605 @@
+use crate::{
+    error::ParseError,
+    tokenizer::{Token, TokenValue},
+    types::{
+        ASTType, ClassBody, ClassElement, Context, GramAnalysisResult, Literal, LiteralValue,
+        MethodDefinition, MethodKind, PropertyDefinition, PropertyKey, SourceLocation, StaticBlock,
+    },
+};
+
+use super::{
+    assignment_expression::assignment_expression, function_expression::method_function,
+    identifier::is_contextual, identifier::private_identifier,
+    object_property::check_accessor_params, object_property::property_key,
+    statement::consume_semicolon, statement::nested_context, statement_list::statement_list,
+};
+
+fn is_label(tokens: &[Token], cursor: usize, label: &str) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == label);
+}
+
+// static/async/get/set 后面是成员名时才是修饰符，否则它们本身就是成员名，如 static() {}、get = 1
+fn is_class_element_name_start(token: Option<&Token>) -> bool {
+    match token {
+        Some(token) => {
+            return matches!(
+                token._type.label.as_str(),
+                "name" | "privateId" | "string" | "number" | "["
+            ) || token._type.keyword.0.is_some()
+        }
+        None => return false,
+    }
+}
+
+// 非计算属性名的字符串值，用于检查 constructor、prototype 这些特殊名称
+fn static_key_name(key: &PropertyKey) -> Option<&str> {
+    match key {
+        PropertyKey::Identifier(identifier) => return Some(&identifier.name),
+        PropertyKey::Literal(Literal {
+            value: LiteralValue::String(value),
+            ..
+        }) => return Some(value),
+        _ => return None,
+    }
+}
+
+// 类体中声明的私有名称，成员可以引用在其后声明的名称，所以在分析类体之前先找出来。
+// 类体直接包含的 privateId 中，只有 this.#x 和 #x in o 是引用，其余都是成员名
+fn declared_private_names(tokens: &[Token], cursor: usize) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut depth = 0;
+    for (index, token) in tokens.iter().enumerate().skip(cursor) {
+        match token._type.label.as_str() {
+            "{" | "(" | "[" | "${" => depth += 1,
+            "}" | ")" | "]" => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            "privateId" if depth == 1 => {
+                let after_dot =
+                    is_label(tokens, index - 1, ".") || is_label(tokens, index - 1, "?.");
+                let before_in = matches!(tokens.get(index + 1), Some(next) if next._type.label == "in" && !next.newline_before);
+                if let (false, false, TokenValue::String(name)) =
+                    (after_dot, before_in, &token.value)
+                {
+                    names.push(name.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    return names;
+}
+
+/*
+* <ClassBody> ::= '{' (<ClassElement> | ';')* '}'
+*/
+pub fn class_body(
+    tokens: &[Token],
+    cursor: usize,
+    has_super_class: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<ClassBody>, ParseError> {
+    let start_token = match tokens.get(cursor) {
+        Some(token) if token._type.label == "{" => token,
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'{'")),
+    };
+    let mut body_context = nested_context(tokens, cursor, context)?;
+    body_context
+        .private_names
+        .extend(declared_private_names(tokens, cursor));
+    let mut next_cursor = cursor + 1;
+    let mut body = Vec::new();
+    let mut has_constructor = false;
+    // 已声明的私有名称：(名称, 是否 static, 种类)，getter 和 setter 可以成对出现
+    let mut private_names: Vec<(String, bool, MethodKind)> = Vec::new();
+    loop {
+        match tokens.get(next_cursor) {
+            Some(token) if token._type.label == "}" => break,
+            Some(token) if token._type.label == ";" => {
+                next_cursor += 1;
+                continue;
+            }
+            None => return Err(ParseError::unexpected(tokens, next_cursor, "'}'")),
+            _ => {}
+        }
+        let element_result = class_element(tokens, next_cursor, has_super_class, &body_context)?;
+        let (key, _static, kind, start, position) = match &element_result.ast {
+            ClassElement::MethodDefinition(method) => (
+                &method.key,
+                method._static,
+                method.kind,
+                method.start,
+                method.loc.start,
+            ),
+            ClassElement::PropertyDefinition(property) => (
+                &property.key,
+                property._static,
+                MethodKind::Method,
+                property.start,
+                property.loc.start,
+            ),
+            ClassElement::StaticBlock(_) => {
+                body.push(element_result.ast);
+                next_cursor = element_result.next_cursor;
+                continue;
+            }
+        };
+        if kind == MethodKind::Constructor {
+            if has_constructor {
+                return Err(ParseError::syntax(
+                    "Duplicate constructor in the same class",
+                    start,
+                    position,
+                ));
+            }
+            has_constructor = true;
+        }
+        if let PropertyKey::PrivateIdentifier(private) = key {
+            let redeclared = private_names
+                .iter()
+                .any(|(name, other_static, other_kind)| {
+                    name == &private.name
+                        && !(*other_static == _static
+                            && matches!(
+                                (other_kind, kind),
+                                (MethodKind::Get, MethodKind::Set)
+                                    | (MethodKind::Set, MethodKind::Get)
+                            ))
+                });
+            if redeclared {
+                return Err(ParseError::syntax(
+                    format!("Identifier '#{}' has already been declared", private.name),
+                    private.start,
+                    private.loc.start,
+                ));
+            }
+            private_names.push((private.name.clone(), _static, kind));
+        }
+        body.push(element_result.ast);
+        next_cursor = element_result.next_cursor;
+    }
+    let end_token = &tokens[next_cursor];
+    let ast = ClassBody {
+        _type: ASTType::ClassBody,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        body,
+    };
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: next_cursor + 1,
+    });
+}
+
+/*
+* <ClassElement> ::= 'static'? <MethodDefinition>
+*                  | 'static'? <ClassElementName> ('=' <AssignmentExpression>)? ';'
+*                  | 'static' '{' <StatementList>? '}'
+* <ClassElementName> ::= <PropertyName> | <PrivateIdentifier>
+*/
+fn class_element(
+    tokens: &[Token],
+    cursor: usize,
+    has_super_class: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<ClassElement>, ParseError> {
+    let start_token = &tokens[cursor];
+    let mut next_cursor = cursor;
+    let mut _static = false;
+    if is_contextual(tokens, cursor, "static") {
+        if is_label(tokens, cursor + 1, "{") {
+            return static_block(tokens, cursor, context);
+        }
+        if is_class_element_name_start(tokens.get(cursor + 1)) || is_label(tokens, cursor + 1, "*")
+        {
+            _static = true;
+            next_cursor += 1;
+        }
+    }
+
+    let mut _async = false;
+    let mut generator = false;
+    let mut kind = MethodKind::Method;
+    // `async` 和成员名之间不能换行
+    if is_contextual(tokens, next_cursor, "async")
+        && (is_class_element_name_start(tokens.get(next_cursor + 1))
+            || is_label(tokens, next_cursor + 1, "*"))
+        && !tokens[next_cursor + 1].newline_before
+    {
+        _async = true;
+        next_cursor += 1;
+    } else if (is_contextual(tokens, next_cursor, "get")
+        || is_contextual(tokens, next_cursor, "set"))
+        && is_class_element_name_start(tokens.get(next_cursor + 1))
+    {
+        kind = if is_contextual(tokens, next_cursor, "get") {
+            MethodKind::Get
+        } else {
+            MethodKind::Set
+        };
+        next_cursor += 1;
+    }
+    if kind == MethodKind::Method && is_label(tokens, next_cursor, "*") {
+        generator = true;
+        next_cursor += 1;
+    }
+
+    let key_result = if is_label(tokens, next_cursor, "privateId") {
+        let private_result = private_identifier(tokens, next_cursor)?;
+        if private_result.ast.name == "constructor" {
+            return Err(ParseError::syntax(
+                "Classes can't have an element named '#constructor'",
+                private_result.ast.start,
+                private_result.ast.loc.start,
+            ));
+        }
+        GramAnalysisResult {
+            ast: PropertyKey::PrivateIdentifier(private_result.ast),
+            next_cursor: private_result.next_cursor,
+        }
+    } else {
+        property_key(tokens, next_cursor, context)?
+    };
+    let key = key_result.ast;
+    next_cursor = key_result.next_cursor;
+    let computed = matches!(key, PropertyKey::ComputedPropertyKey(_));
+    let key_name = static_key_name(&key);
+    if _static && key_name == Some("prototype") {
+        return Err(ParseError::syntax(
+            "Classes may not have a static property named prototype",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+
+    // 方法
+    if is_label(tokens, next_cursor, "(") {
+        if !_static && key_name == Some("constructor") {
+            let message = if kind != MethodKind::Method {
+                Some("Constructor can't have get/set modifier")
+            } else if generator {
+                Some("Constructor can't be a generator")
+            } else if _async {
+                Some("Constructor can't be an async method")
+            } else {
+                None
+            };
+            if let Some(message) = message {
+                return Err(ParseError::syntax(
+                    message,
+                    start_token.start,
+                    start_token.loc.start,
+                ));
+            }
+            kind = MethodKind::Constructor;
+        }
+        let allow_super_call = kind == MethodKind::Constructor && has_super_class;
+        let function_result = method_function(
+            tokens,
+            next_cursor,
+            _async,
+            generator,
+            allow_super_call,
+            context,
+        )?;
+        check_accessor_params(&function_result.ast, kind.as_str())?;
+        let end_token = &tokens[function_result.next_cursor - 1];
+        let ast = MethodDefinition {
+            _type: ASTType::MethodDefinition,
+            start: start_token.start,
+            end: end_token.end,
+            loc: SourceLocation {
+                start: start_token.loc.start,
+                end: end_token.loc.end,
+            },
+            _static,
+            computed,
+            key,
+            kind,
+            value: function_result.ast,
+        };
+        return Ok(GramAnalysisResult {
+            ast: ClassElement::MethodDefinition(ast),
+            next_cursor: function_result.next_cursor,
+        });
+    }
+    if _async || generator || kind != MethodKind::Method {
+        return Err(ParseError::unexpected(tokens, next_cursor, "'('"));
+    }
+
+    // 字段
+    if key_name == Some("constructor") {
+        return Err(ParseError::syntax(
+            "Classes can't have a field named 'constructor'",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+    let mut value = None;
+    if is_label(tokens, next_cursor, "=") {
+        // 字段初始值中可以使用 super.x，但不能调用 super()
+        let value_context = Context {
+            allow_super_call: false,
+            allow_super_property: true,
+            allow_new_target: true,
+            in_async: false,
+            in_generator: false,
+            in_parameters: false,
+            in_class_initializer: true,
+            in_static_block: false,
+            ..context.clone()
+        };
+        let value_result = assignment_expression(tokens, next_cursor + 1, false, &value_context)?;
+        value = Some(value_result.ast);
+        next_cursor = value_result.next_cursor;
+    }
+    // 字段与语句一样以 ';' 结束，在 '}' 前或换行时可以省略
+    next_cursor = consume_semicolon(tokens, next_cursor)?;
+    let ast = PropertyDefinition {
+        _type: ASTType::PropertyDefinition,
+        start: start_token.start,
+        end: tokens[next_cursor - 1].end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: tokens[next_cursor - 1].loc.end,
+        },
+        _static,
+        computed,
+        key,
+        value,
+    };
+    return Ok(GramAnalysisResult {
+        ast: ClassElement::PropertyDefinition(ast),
+        next_cursor,
+    });
+}
+
+// static { ... }，其中不能 return，可以使用 super.x
+fn static_block(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<ClassElement>, ParseError> {
+    let block_context = Context {
+        strict: context.strict,
+        allow_super_property: true,
+        allow_new_target: true,
+        module: context.module,
+        in_class_initializer: true,
+        in_static_block: true,
+        private_names: context.private_names.clone(),
+        depth: context.depth,
+        ..Context::default()
+    };
+    let statement_list_result = statement_list(tokens, cursor + 2, &block_context)?;
+    let right_brace_cursor = statement_list_result.next_cursor;
+    if !is_label(tokens, right_brace_cursor, "}") {
+        return Err(ParseError::unexpected(tokens, right_brace_cursor, "'}'"));
+    }
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[right_brace_cursor];
+    let ast = StaticBlock {
+        _type: ASTType::StaticBlock,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        body: statement_list_result.statements,
+    };
+    return Ok(GramAnalysisResult {
+        ast: ClassElement::StaticBlock(ast),
+        next_cursor: right_brace_cursor + 1,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn class_elements() {
+        assert_json(
+            script,
+            &[
+                (
+                    "class A extends B { constructor() { super() } }",
+                    r#""kind":"constructor""#,
+                ),
+                ("class A { static constructor() {} }", r#""static":true"#),
+                ("class A { m() { super.x } }", r#""type":"Super""#),
+                (
+                    "class A { static { this.x = 1 } }",
+                    r#""type":"StaticBlock""#,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn class_early_errors() {
+        assert_errors(
+            script,
+            &[
+                (
+                    "class A { constructor() {} constructor() {} }",
+                    "Duplicate constructor in the same class",
+                ),
+                (
+                    "class A { constructor() { super() } }",
+                    "super() call outside constructor of a subclass",
+                ),
+                (
+                    "class A extends B { m() { super() } }",
+                    "super() call outside constructor of a subclass",
+                ),
+                (
+                    "class A extends B { x = super() }",
+                    "super() call outside constructor of a subclass",
+                ),
+                (
+                    "function f() { super.x }",
+                    "'super' keyword outside a method",
+                ),
+                (
+                    "class A { get constructor() {} }",
+                    "Constructor can't have get/set modifier",
+                ),
+                (
+                    "class A { *constructor() {} }",
+                    "Constructor can't be a generator",
+                ),
+                (
+                    "class A { async constructor() {} }",
+                    "Constructor can't be an async method",
+                ),
+                (
+                    "class A { static prototype() {} }",
+                    "Classes may not have a static property named prototype",
+                ),
+                (
+                    "class A { static prototype = 1 }",
+                    "Classes may not have a static property named prototype",
+                ),
+                (
+                    "class A { constructor = 1 }",
+                    "Classes can't have a field named 'constructor'",
+                ),
+                (
+                    "class A { #constructor() {} }",
+                    "Classes can't have an element named '#constructor'",
+                ),
+                (
+                    "class A { static { return } }",
+                    "'return' outside of function",
+                ),
+                (
+                    "class A { x = arguments }",
+                    "'arguments' is not allowed in class field initializer",
+                ),
+                (
+                    "class A { x = () => arguments }",
+                    "'arguments' is not allowed in class field initializer",
+                ),
+                (
+                    "class A { static { arguments } }",
+                    "'arguments' is not allowed in class field initializer",
+                ),
+                (
+                    "class A { static { await } }",
+                    "Cannot use 'await' as identifier inside a class static block",
+                ),
+                (
+                    "class A { static { var await } }",
+                    "Cannot use 'await' as identifier inside a class static block",
+                ),
+            ],
+        );
+        // 普通函数中的 arguments 和 await 与外层的类无关
+        assert_json(
+            script,
+            &[
+                (
+                    "class A { x = function () { return arguments } }",
+                    r#""type":"PropertyDefinition""#,
+                ),
+                (
+                    "class A { static { (function () { arguments; await }) } }",
+                    r#""type":"StaticBlock""#,
+                ),
+                (
+                    "class A { static { () => await } }",
+                    r#""type":"StaticBlock""#,
+                ),
+                ("class A { [arguments] = 1 }", r#""computed":true"#),
+            ],
+        );
+    }
+
+    #[test]
+    fn private_name_references() {
+        assert_json(
+            script,
+            &[
+                (
+                    "class A { m() { return this.#x } #x = 1 }",
+                    r#""property":{"type":"PrivateIdentifier","#,
+                ),
+                (
+                    "class A { #x; m() { class B { n(o) { return o.#x + this.#y } #y } } }",
+                    r#""name":"y""#,
+                ),
+                (
+                    "class A { #x; static s(o) { return #x in o } }",
+                    r#""left":{"type":"PrivateIdentifier","#,
+                ),
+                (
+                    "class A { #x = () => this.#x; static { this.#x } }",
+                    r#""type":"StaticBlock""#,
+                ),
+                ("class A { [this.#k] = 1; #k }", r#""computed":true"#),
+                (
+                    "class A { get #a() {} set #a(v) {} m() { this.#a = 1 } }",
+                    r#""kind":"set""#,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn private_name_errors() {
+        assert_errors(
+            script,
+            &[
+                (
+                    "a.#b",
+                    "Private field '#b' must be declared in an enclosing class",
+                ),
+                ("#b in a", "Private field '#b' must be declared"),
+                (
+                    "class A { m() { this.#undeclared } }",
+                    "Private field '#undeclared'",
+                ),
+                ("function f() { return this.#x }", "Private field '#x'"),
+                // 内层类的私有名称在外层不可见
+                (
+                    "class A { m() { class B { #y } return this.#y } }",
+                    "Private field '#y'",
+                ),
+                // extends 子句属于外层
+                (
+                    "class A extends (class { #z }) { m() { this.#z } }",
+                    "Private field '#z'",
+                ),
+                (
+                    "class A { #x; #x }",
+                    "Identifier '#x' has already been declared",
+                ),
+                (
+                    "class A { get #a() {} static set #a(v) {} }",
+                    "Identifier '#a' has already been declared",
+                ),
+                (
+                    "class A { #a; m() { delete this.#a } }",
+                    "Private fields can not be deleted",
+                ),
+                (
+                    "class A { #a; m() { delete this?.#a } }",
+                    "Private fields can not be deleted",
+                ),
+                (
+                    "class A { #a; m() { delete (this.b.#a) } }",
+                    "Private fields can not be deleted",
+                ),
+            ],
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, ClassExpression, Context, GramAnalysisResult, SourceLocation},
+};
+
+use super::{
+    class_element::class_body, identifier::binding_identifier,
+    left_hand_side_expression::left_hand_side_expression,
+};
+
+/*
+* <ClassExpression> ::= 'class' <Identifier>? ('extends' <LeftHandSideExpression>)? '{' <ClassBody> '}'
+* 类声明与类表达式结构相同，声明时由调用方检查类名；类的所有部分都是严格模式
+*/
+pub fn class_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<ClassExpression>, ParseError> {
+    match tokens.get(cursor) {
+        Some(token) if token._type.label == "class" => {}
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'class'")),
+    }
+    let class_context = Context {
+        strict: true,
+        ..context.clone()
+    };
+    let mut next_cursor = cursor + 1;
+    let mut id = None;
+    if matches!(tokens.get(next_cursor), Some(token) if token._type.label == "name") {
+        let identifier_result = binding_identifier(tokens, next_cursor, &class_context)?;
+        id = Some(identifier_result.ast);
+        next_cursor = identifier_result.next_cursor;
+    }
+    let mut super_class = None;
+    if matches!(tokens.get(next_cursor), Some(token) if token._type.label == "extends") {
+        let super_class_result =
+            left_hand_side_expression(tokens, next_cursor + 1, &class_context)?;
+        super_class = Some(super_class_result.ast);
+        next_cursor = super_class_result.next_cursor;
+    }
+    let body_result = class_body(tokens, next_cursor, super_class.is_some(), &class_context)?;
+    let start_token = &tokens[cursor];
+    let body = body_result.ast;
+    let ast = ClassExpression {
+        _type: ASTType::ClassExpression,
+        start: start_token.start,
+        end: body.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: body.loc.end,
+        },
+        id,
+        superClass: super_class,
+        body,
+    };
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: body_result.next_cursor,
+    });
+}
@@ -1,5 +1,133 @@
-use crate::tokenizer::Token;
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, ConditionalExpression, Context, Expression, GramAnalysisResult, SourceLocation,
+    },
+};
 
-pub fn conditional_expression(tokens: &[Token], cursor: usize) {
-    
+use super::{
+    assignment_expression::{assignment_expression, check_cover_initialized_name},
+    binary_expression::binary_expression,
+};
+
+/*
+* <ConditionalExpression> ::= <BinaryExpression> | <BinaryExpression> '?' <AssignmentExpression> ':' <AssignmentExpression>
+* 右结合：`a ? b : c ? d : e` 等价于 `a ? b : (c ? d : e)`
+* 条件和 alternate 继承外层的 `no_in`，consequent 处于 `?` 和 `:` 之间，总是允许 `in`
+*/
+pub fn conditional_expression(
+    tokens: &[Token],
+    cursor: usize,
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let test_result = binary_expression(tokens, cursor, no_in, context)?;
+    match tokens.get(test_result.next_cursor) {
+        Some(token) if token._type.label == "?" => {
+            return conditional_branches(tokens, cursor, test_result, no_in, context)
+        }
+        _ => return Ok(test_result),
+    }
+}
+
+// 已经解析出条件，从 '?' 开始解析两个分支
+fn conditional_branches(
+    tokens: &[Token],
+    cursor: usize,
+    test_result: GramAnalysisResult<Expression>,
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    check_cover_initialized_name(&test_result.ast)?;
+    let consequent_result =
+        assignment_expression(tokens, test_result.next_cursor + 1, false, context)?;
+    match tokens.get(consequent_result.next_cursor) {
+        Some(token) if token._type.label == ":" => {}
+        _ => {
+            return Err(ParseError::unexpected(
+                tokens,
+                consequent_result.next_cursor,
+                "':'",
+            ))
+        }
+    }
+    let alternate_result =
+        assignment_expression(tokens, consequent_result.next_cursor + 1, no_in, context)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[alternate_result.next_cursor - 1];
+    let ast = ConditionalExpression {
+        _type: ASTType::ConditionalExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        test: test_result.ast,
+        consequent: consequent_result.ast,
+        alternate: alternate_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::ConditionalExpression(Some(Box::new(ast))),
+        next_cursor: alternate_result.next_cursor,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn nested_conditionals() {
+        assert_json(
+            script,
+            &[
+                // consequent 和 alternate 中的条件表达式各自嵌套
+                (
+                    "a ? b ? c : d : e ? f : g",
+                    r#""consequent":{"type":"ConditionalExpression","start":4,"end":13,"#,
+                ),
+                (
+                    "a ? b ? c : d : e ? f : g",
+                    r#""alternate":{"type":"ConditionalExpression","start":16,"end":25,"#,
+                ),
+                // 右结合：a ? b : (c ? d : e)
+                (
+                    "a ? b : c ? d : e",
+                    r#""alternate":{"type":"ConditionalExpression","start":8,"end":17,"test":{"type":"Identifier","start":8,"end":9,"name":"c"}"#,
+                ),
+                (
+                    "a ?? b ? c : d",
+                    r#""test":{"type":"LogicalExpression","start":0,"end":6,"#,
+                ),
+                // 两个分支都是 AssignmentExpression
+                (
+                    "x = a ? b = 1 : c = 2",
+                    r#""alternate":{"type":"AssignmentExpression","start":16,"end":21,"#,
+                ),
+                (
+                    "a ? b : c, d",
+                    r#"{"type":"SequenceExpression","start":0,"end":12,"expressions":[{"type":"ConditionalExpression","start":0,"end":9,"#,
+                ),
+                (
+                    "a ? () => b : c => d",
+                    r#""type":"ArrowFunctionExpression""#,
+                ),
+                // `?.` 后面是数字时是条件表达式
+                ("a?.5:1", r#""value":0.5"#),
+                // for 头部中 consequent 允许 in
+                ("for (a ? b in c : d;;);", r#""operator":"in""#),
+            ],
+        );
+        assert_errors(
+            script,
+            &[
+                ("a ? b, c : d", "expected ':', found ','"),
+                ("a ? b", "expected ':', found end of input"),
+                ("a ? : c", "expected expression, found ':'"),
+                ("(a ? b : c) = d", "Assigning to rvalue"),
+            ],
+        );
+    }
 }
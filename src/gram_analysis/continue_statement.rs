@@ -0,0 +1,61 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, Context, ContinueStatement, GramAnalysisResult, SourceLocation},
+};
+
+use super::{identifier::identifier, statement::consume_semicolon};
+
+/*
+* <ContinueStatement> ::= 'continue' ';' | 'continue' <Identifier> ';'
+* 标签必须与 continue 在同一行，否则视为省略了分号
+*/
+pub fn continue_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let start_token = &tokens[cursor];
+    let mut next_cursor = cursor + 1;
+    let mut label = None;
+    match tokens.get(next_cursor) {
+        Some(token) if token._type.label == "name" && !token.newline_before => {
+            let identifier_result = identifier(tokens, next_cursor)?;
+            next_cursor = identifier_result.next_cursor;
+            label = Some(identifier_result.ast);
+        }
+        _ => {}
+    }
+    // continue 只能用在循环中，带标签时标签必须标记一个循环
+    let valid = context.in_iteration
+        && match &label {
+            Some(label) => context
+                .labels
+                .iter()
+                .any(|item| item.name == label.name && item.is_loop),
+            None => true,
+        };
+    if !valid {
+        return Err(ParseError::syntax(
+            "Unsyntactic continue",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+    next_cursor = consume_semicolon(tokens, next_cursor)?;
+    let end_token = &tokens[next_cursor - 1];
+    let ast = ContinueStatement {
+        _type: ASTType::ContinueStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        label,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::ContinueStatement(ast),
+        next_cursor,
+    });
+}
@@ -0,0 +1,32 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, DebuggerStatement, GramAnalysisResult, SourceLocation},
+};
+
+use super::statement::consume_semicolon;
+
+/*
+* <DebuggerStatement> ::= 'debugger' ';'
+*/
+pub fn debugger_statement(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let start_token = &tokens[cursor];
+    let next_cursor = consume_semicolon(tokens, cursor + 1)?;
+    let end_token = &tokens[next_cursor - 1];
+    let ast = DebuggerStatement {
+        _type: ASTType::DebuggerStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::DebuggerStatement(ast),
+        next_cursor,
+    });
+}
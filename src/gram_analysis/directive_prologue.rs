@@ -0,0 +1,89 @@
+use crate::{
+    error::ParseError,
+    tokenizer::{Token, TokenValue},
+    types::{Body, Context, Expression, ExpressionStatement},
+    utils::escape::{cook_string, CookedString},
+};
+
+use super::expression_statement::expression_statement;
+
+pub struct DirectivePrologueResult {
+    pub statements: Vec<Body>,
+    pub next_cursor: usize,
+    // 指令中是否有 "use strict"
+    pub strict: bool,
+}
+
+/*
+* <DirectivePrologue> ::= (<StringLiteral> ';')*
+* 程序和函数体开头只由字符串字面量组成的表达式语句是指令，如 "use strict"；
+* 遇到第一条不是指令的语句即停止，由调用方继续解析
+*/
+pub fn directive_prologue(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<DirectivePrologueResult, ParseError> {
+    let mut statements = Vec::new();
+    let mut next_cursor = cursor;
+    let mut strict = false;
+    // 第一条含有八进制转义的指令
+    let mut octal_directive: Option<(&Token, &str)> = None;
+    while let Some(token) = tokens.get(next_cursor) {
+        let raw = match &token.value {
+            TokenValue::String(raw) if token._type.label == "string" => raw,
+            _ => break,
+        };
+        // "use strict" 之后的指令也按严格模式解析
+        let statement_context = Context {
+            strict: context.strict || strict,
+            ..context.clone()
+        };
+        let statement_result = expression_statement(tokens, next_cursor, &statement_context)?;
+        let mut ast = statement_result.ast;
+        next_cursor = statement_result.next_cursor;
+        // 整条语句只有这个字符串时才是指令，如 "a".length 或 "a" + b 是普通的表达式语句
+        let is_directive = matches!(
+            &ast,
+            Body::ExpressionStatement(ExpressionStatement {
+                expression: Expression::Literal(Some(literal)),
+                ..
+            }) if literal.start == token.start && literal.end == token.end
+        );
+        if !is_directive {
+            statements.push(ast);
+            break;
+        }
+        // 与 acorn 一致，directive 是去掉引号的原始文本，不处理转义
+        let directive = String::from(&raw[1..raw.len() - 1]);
+        if directive == "use strict" {
+            // "use strict" 之前的指令也要按严格模式检查，如 "\01"; "use strict"
+            if let Some((octal_token, message)) = octal_directive {
+                return Err(ParseError::syntax(
+                    message,
+                    octal_token.start,
+                    octal_token.loc.start,
+                ));
+            }
+            strict = true;
+        }
+        if octal_directive.is_none() && !strict {
+            if let Ok(CookedString {
+                octal: Some((_, message)),
+                ..
+            }) = cook_string(&directive)
+            {
+                octal_directive = Some((token, message));
+            }
+        }
+        if let Body::ExpressionStatement(statement) = &mut ast {
+            statement.directive = Some(directive);
+        }
+        statements.push(ast);
+    }
+    return Ok(DirectivePrologueResult {
+        statements,
+        next_cursor,
+        strict,
+    });
+}
@@ -1,31 +1,52 @@
-use crate::{tokenizer::Token, types::Expression};
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{Context, ExpressionOrSpread},
+};
 
-use super::expression::expression;
+use super::{assignment_expression::cover_assignment_expression, spread_element::spread_element};
 
-pub struct ElementListResult<'a> {
-    pub elements: Vec<Expression<'a>>,
+pub struct ElementListResult {
+    pub elements: Vec<Option<ExpressionOrSpread>>,
     pub next_cursor: usize,
 }
 
-pub fn element_list(tokens: &[Token], cursor: usize) -> Result<ElementListResult, &str> {
+pub fn element_list(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<ElementListResult, ParseError> {
     let mut next_cursor = cursor;
     let mut elements = Vec::new();
     loop {
-        let result = expression(tokens, next_cursor);
-        if let Ok(value) = result {
-            elements.push(value.ast);
-            if let Some(token) = tokens.get(value.next_cursor) {
-                if token._type.label == "," {
-                    next_cursor = value.next_cursor + 1
-                } else {
-                    next_cursor = value.next_cursor;
-                    break;
-                }
-            } else {
-                return Err("");
+        match tokens.get(next_cursor) {
+            // 允许末尾的逗号，如 [1, 2,]
+            Some(token) if token._type.label == "]" => break,
+            // 空位，如 [a, , b]
+            Some(token) if token._type.label == "," => {
+                elements.push(None);
+                next_cursor += 1;
+                continue;
             }
+            _ => {}
+        }
+        // 元素可能在之后被转换为解构模式，如 [{ a = 1 }] = b、[...a] = b
+        let value_next_cursor = if matches!(tokens.get(next_cursor), Some(token) if token._type.label == "...")
+        {
+            let spread_result = spread_element(tokens, next_cursor, true, context)?;
+            elements.push(Some(ExpressionOrSpread::SpreadElement(spread_result.ast)));
+            spread_result.next_cursor
         } else {
-            break;
+            let value = cover_assignment_expression(tokens, next_cursor, false, context)?;
+            elements.push(Some(ExpressionOrSpread::Expression(value.ast)));
+            value.next_cursor
+        };
+        match tokens.get(value_next_cursor) {
+            Some(token) if token._type.label == "," => next_cursor = value_next_cursor + 1,
+            _ => {
+                next_cursor = value_next_cursor;
+                break;
+            }
         }
     }
     return Ok(ElementListResult {
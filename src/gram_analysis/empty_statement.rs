@@ -0,0 +1,32 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, EmptyStatement, GramAnalysisResult, SourceLocation},
+};
+
+/*
+* <EmptyStatement> ::= ';'
+*/
+pub fn empty_statement(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    match tokens.get(cursor) {
+        Some(token) if token._type.label == ";" => {
+            let ast = EmptyStatement {
+                _type: ASTType::EmptyStatement,
+                start: token.start,
+                end: token.end,
+                loc: SourceLocation {
+                    start: token.loc.start,
+                    end: token.loc.end,
+                },
+            };
+            return Ok(GramAnalysisResult {
+                ast: Body::EmptyStatement(ast),
+                next_cursor: cursor + 1,
+            });
+        }
+        _ => return Err(ParseError::unexpected(tokens, cursor, "';'")),
+    }
+}
@@ -0,0 +1,452 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::ParseError,
+    tokenizer::{MyOption, Token},
+    types::{
+        ASTType, Body, Context, ExportAllDeclaration, ExportDefaultDeclaration,
+        ExportDefaultDeclarationKind, ExportNamedDeclaration, ExportSpecifier, GramAnalysisResult,
+        ModuleExportName, Position, SourceLocation,
+    },
+};
+
+use super::{
+    assignment_expression::assignment_expression,
+    binding_pattern::bound_identifiers,
+    class_declaration::{class_declaration, class_declaration_node},
+    function_declaration::{function_declaration, function_declaration_node},
+    function_expression::is_async_function,
+    identifier::is_contextual,
+    import_declaration::{from_clause, module_export_name, module_export_name_string, with_clause},
+    statement::consume_semicolon,
+    variable_statement::{is_let_declaration, variable_statement},
+};
+
+fn is_label(tokens: &[Token], cursor: usize, label: &str) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == label);
+}
+
+/*
+* <ExportDeclaration> ::= 'export' '*' ('as' <ModuleExportName>)? <FromClause> <WithClause>? ';'
+*                       | 'export' <NamedExports> (<FromClause> <WithClause>?)? ';'
+*                       | 'export' <VariableStatement>
+*                       | 'export' <Declaration>
+*                       | 'export' 'default' (<HoistableDeclaration> | <ClassDeclaration>)
+*                       | 'export' 'default' [lookahead ∉ { 'function', 'async function', 'class' }] <AssignmentExpression> ';'
+*/
+pub fn export_declaration(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    if !is_label(tokens, cursor, "export") {
+        return Err(ParseError::unexpected(tokens, cursor, "'export'"));
+    }
+    if is_label(tokens, cursor + 1, "*") {
+        return export_all_declaration(tokens, cursor);
+    } else if is_label(tokens, cursor + 1, "default") {
+        return export_default_declaration(tokens, cursor, context);
+    } else if is_label(tokens, cursor + 1, "{") {
+        return export_named_specifiers(tokens, cursor);
+    }
+    // export var a、export function f() {}、export class A {}
+    let declaration_cursor = cursor + 1;
+    let declaration_result = match tokens.get(declaration_cursor) {
+        Some(token) => match &token._type.keyword {
+            _ if is_let_declaration(tokens, declaration_cursor) => {
+                variable_statement(tokens, declaration_cursor, context)?
+            }
+            _ if is_async_function(tokens, declaration_cursor) => {
+                function_declaration(tokens, declaration_cursor, context)?
+            }
+            MyOption(Some(keyword)) if keyword == "var" || keyword == "const" => {
+                variable_statement(tokens, declaration_cursor, context)?
+            }
+            MyOption(Some(keyword)) if keyword == "class" => {
+                class_declaration(tokens, declaration_cursor, context)?
+            }
+            MyOption(Some(keyword)) if keyword == "function" => {
+                function_declaration(tokens, declaration_cursor, context)?
+            }
+            _ => {
+                return Err(ParseError::unexpected(
+                    tokens,
+                    declaration_cursor,
+                    "declaration",
+                ))
+            }
+        },
+        None => {
+            return Err(ParseError::unexpected(
+                tokens,
+                declaration_cursor,
+                "declaration",
+            ))
+        }
+    };
+    let next_cursor = declaration_result.next_cursor;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = ExportNamedDeclaration {
+        _type: ASTType::ExportNamedDeclaration,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        declaration: Some(declaration_result.ast),
+        specifiers: Vec::new(),
+        source: None,
+        attributes: Vec::new(),
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::ExportNamedDeclaration(Some(Box::new(ast))),
+        next_cursor,
+    });
+}
+
+// export * from "a" 和 export * as ns from "a"
+fn export_all_declaration(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let mut next_cursor = cursor + 2;
+    let mut exported = None;
+    if is_contextual(tokens, next_cursor, "as") {
+        let exported_result = module_export_name(tokens, next_cursor + 1)?;
+        exported = Some(exported_result.ast);
+        next_cursor = exported_result.next_cursor;
+    }
+    let source_result = from_clause(tokens, next_cursor)?;
+    let attributes_result = with_clause(tokens, source_result.next_cursor)?;
+    next_cursor = consume_semicolon(tokens, attributes_result.next_cursor)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = ExportAllDeclaration {
+        _type: ASTType::ExportAllDeclaration,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        exported,
+        source: source_result.ast,
+        attributes: attributes_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::ExportAllDeclaration(ast),
+        next_cursor,
+    });
+}
+
+// export default 之后的函数和类是声明，可以省略名称，其余的是表达式
+fn export_default_declaration(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let declaration_cursor = cursor + 2;
+    let (declaration, next_cursor) = if is_label(tokens, declaration_cursor, "function")
+        || is_async_function(tokens, declaration_cursor)
+    {
+        let function_result = function_declaration_node(tokens, declaration_cursor, true, context)?;
+        (
+            ExportDefaultDeclarationKind::FunctionDeclaration(function_result.ast),
+            function_result.next_cursor,
+        )
+    } else if is_label(tokens, declaration_cursor, "class") {
+        let class_result = class_declaration_node(tokens, declaration_cursor, true, context)?;
+        (
+            ExportDefaultDeclarationKind::ClassDeclaration(class_result.ast),
+            class_result.next_cursor,
+        )
+    } else {
+        let expression_result = assignment_expression(tokens, declaration_cursor, false, context)?;
+        (
+            ExportDefaultDeclarationKind::Expression(expression_result.ast),
+            consume_semicolon(tokens, expression_result.next_cursor)?,
+        )
+    };
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = ExportDefaultDeclaration {
+        _type: ASTType::ExportDefaultDeclaration,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        declaration,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::ExportDefaultDeclaration(Some(Box::new(ast))),
+        next_cursor,
+    });
+}
+
+/*
+* <NamedExports> ::= '{' '}' | '{' <ExportSpecifier> (',' <ExportSpecifier>)* ','? '}'
+* <ExportSpecifier> ::= <ModuleExportName> ('as' <ModuleExportName>)?
+* 没有 from 时导出的是本模块的绑定，local 不能是字符串或保留字
+*/
+fn export_named_specifiers(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let mut specifiers = Vec::new();
+    // 每个 local 的起始位置，用于在没有 from 时检查 local
+    let mut local_cursors = Vec::new();
+    let mut next_cursor = cursor + 2;
+    while !is_label(tokens, next_cursor, "}") {
+        let local_result = module_export_name(tokens, next_cursor)?;
+        local_cursors.push(next_cursor);
+        let local = local_result.ast;
+        let (exported, specifier_next_cursor) =
+            if is_contextual(tokens, local_result.next_cursor, "as") {
+                let exported_result = module_export_name(tokens, local_result.next_cursor + 1)?;
+                (exported_result.ast, exported_result.next_cursor)
+            } else {
+                (local.clone(), local_result.next_cursor)
+            };
+        let start_token = &tokens[next_cursor];
+        let end_token = &tokens[specifier_next_cursor - 1];
+        specifiers.push(ExportSpecifier {
+            _type: ASTType::ExportSpecifier,
+            start: start_token.start,
+            end: end_token.end,
+            loc: SourceLocation {
+                start: start_token.loc.start,
+                end: end_token.loc.end,
+            },
+            local,
+            exported,
+        });
+        next_cursor = specifier_next_cursor;
+        match tokens.get(next_cursor) {
+            Some(token) if token._type.label == "," => next_cursor += 1,
+            Some(token) if token._type.label == "}" => {}
+            _ => return Err(ParseError::unexpected(tokens, next_cursor, "',' or '}'")),
+        }
+    }
+    next_cursor += 1;
+    let mut source = None;
+    let mut attributes = Vec::new();
+    if is_contextual(tokens, next_cursor, "from") {
+        let source_result = from_clause(tokens, next_cursor)?;
+        let attributes_result = with_clause(tokens, source_result.next_cursor)?;
+        source = Some(source_result.ast);
+        attributes = attributes_result.ast;
+        next_cursor = attributes_result.next_cursor;
+    } else {
+        for local_cursor in local_cursors {
+            let local_token = &tokens[local_cursor];
+            if local_token._type.label == "string" {
+                return Err(ParseError::syntax(
+                    "A string literal cannot be used as an exported binding without `from`",
+                    local_token.start,
+                    local_token.loc.start,
+                ));
+            } else if let MyOption(Some(keyword)) = &local_token._type.keyword {
+                return Err(ParseError::syntax(
+                    format!("Unexpected keyword '{}'", keyword),
+                    local_token.start,
+                    local_token.loc.start,
+                ));
+            }
+        }
+    }
+    next_cursor = consume_semicolon(tokens, next_cursor)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = ExportNamedDeclaration {
+        _type: ASTType::ExportNamedDeclaration,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        declaration: None,
+        specifiers,
+        source,
+        attributes,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::ExportNamedDeclaration(Some(Box::new(ast))),
+        next_cursor,
+    });
+}
+
+fn module_export_name_position(name: &ModuleExportName) -> (usize, Position) {
+    match name {
+        ModuleExportName::Identifier(identifier) => {
+            return (identifier.start, identifier.loc.start)
+        }
+        ModuleExportName::Literal(literal) => return (literal.start, literal.loc.start),
+    }
+}
+
+/// 同一个模块中每个名称只能导出一次，default 也算一个名称
+pub fn check_duplicate_exports(body: &[Body]) -> Result<(), ParseError> {
+    let mut exported_names = HashSet::new();
+    let mut check = |name: &str, start: usize, position: Position| {
+        if !exported_names.insert(name.to_string()) {
+            return Err(ParseError::syntax(
+                format!("Duplicate export '{}'", name),
+                start,
+                position,
+            ));
+        }
+        return Ok(());
+    };
+    for item in body {
+        match item {
+            Body::ExportNamedDeclaration(Some(ast)) => {
+                match &ast.declaration {
+                    Some(Body::VariableDeclaration(declaration)) => {
+                        let mut identifiers = Vec::new();
+                        for declarator in &declaration.declarations {
+                            bound_identifiers(&declarator.id, &mut identifiers);
+                        }
+                        for id in identifiers {
+                            check(&id.name, id.start, id.loc.start)?;
+                        }
+                    }
+                    Some(Body::FunctionDeclaration(declaration)) => {
+                        if let Some(id) = &declaration.id {
+                            check(&id.name, id.start, id.loc.start)?;
+                        }
+                    }
+                    Some(Body::ClassDeclaration(declaration)) => {
+                        if let Some(id) = &declaration.id {
+                            check(&id.name, id.start, id.loc.start)?;
+                        }
+                    }
+                    _ => {}
+                }
+                for specifier in &ast.specifiers {
+                    let (start, position) = module_export_name_position(&specifier.exported);
+                    check(
+                        module_export_name_string(&specifier.exported),
+                        start,
+                        position,
+                    )?;
+                }
+            }
+            Body::ExportDefaultDeclaration(Some(ast)) => {
+                check("default", ast.start, ast.loc.start)?;
+            }
+            Body::ExportAllDeclaration(ast) => {
+                if let Some(exported) = &ast.exported {
+                    let (start, position) = module_export_name_position(exported);
+                    check(module_export_name_string(exported), start, position)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, module, script};
+
+    #[test]
+    fn module_declarations() {
+        assert_json(
+            module,
+            &[
+                ("import a from 'a'", r#""type":"ImportDefaultSpecifier""#),
+                (
+                    "import * as a from 'a'",
+                    r#""type":"ImportNamespaceSpecifier""#,
+                ),
+                (
+                    "import { 'a' as b } from 'c'",
+                    r#""imported":{"type":"Literal""#,
+                ),
+                (
+                    "import a from 'a' with { type: 'json' }",
+                    r#""attributes":[{"type":"ImportAttribute""#,
+                ),
+                (
+                    "export * from 'a'; export * from 'b'",
+                    r#""type":"ExportAllDeclaration""#,
+                ),
+                (
+                    "export * as x from 'a'",
+                    r#""exported":{"type":"Identifier""#,
+                ),
+                (
+                    "export { a as 'b' } from 'c'",
+                    r#""exported":{"type":"Literal""#,
+                ),
+                (
+                    "export default function () {}",
+                    r#""type":"ExportDefaultDeclaration""#,
+                ),
+                ("import.meta", r#""type":"MetaProperty""#),
+                ("import('a')", r#""type":"ImportExpression""#),
+            ],
+        );
+    }
+
+    #[test]
+    fn module_early_errors() {
+        assert_errors(
+            module,
+            &[
+                ("export var a; export var a", "Duplicate export 'a'"),
+                ("export { a }; export { a }; var a", "Duplicate export 'a'"),
+                (
+                    "export default 1; export default 2",
+                    "Duplicate export 'default'",
+                ),
+                (
+                    "export { a as default }; export default 1; var a",
+                    "Duplicate export 'default'",
+                ),
+                (
+                    "export * as x from 'a'; export var x",
+                    "Duplicate export 'x'",
+                ),
+                ("export let [a, a] = b", "Duplicate export 'a'"),
+                (
+                    "function f() { import a from 'b' }",
+                    "'import' and 'export' may only appear at the top level",
+                ),
+                (
+                    "{ export var a }",
+                    "'import' and 'export' may only appear at the top level",
+                ),
+                (
+                    "export { 'a' }",
+                    "A string literal cannot be used as an exported binding without `from`",
+                ),
+                ("import { 'a' } from 'c'", "expected 'as', found '}'"),
+                (
+                    "import a from 'a' with { type: 'json', type: 'json' }",
+                    "Duplicate attribute key 'type'",
+                ),
+            ],
+        );
+        assert_errors(
+            script,
+            &[
+                (
+                    "export var a",
+                    "'import' and 'export' may appear only with 'sourceType: module'",
+                ),
+                (
+                    "import a from 'a'",
+                    "'import' and 'export' may appear only with 'sourceType: module'",
+                ),
+                ("import.meta", "Cannot use 'import.meta' outside a module"),
+            ],
+        );
+    }
+}
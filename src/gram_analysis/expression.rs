@@ -1,30 +1,88 @@
 use crate::{
+    error::ParseError,
     tokenizer::Token,
-    types::{Expression, GramAnalysisResult, LiteralType},
+    types::{ASTType, Context, Expression, GramAnalysisResult, SequenceExpression, SourceLocation},
 };
 
-use super::{array_expression::array_expression, literal_expression::literal_expression};
+use super::assignment_expression::assignment_expression;
 
-pub fn expression(tokens: &[Token], cursor: usize) -> Result<GramAnalysisResult<Expression>, &str> {
-    if let Some(token) = tokens.get(cursor) {
-        // literal: number, string, boolean, null, undefined, regexp
-        if token._type.label == "number" {
-            return literal_expression(tokens, cursor, LiteralType::NUMBER);
-        } else if token._type.label == "string" {
-            return literal_expression(tokens, cursor, LiteralType::STRING);
-        } else if token._type.label == "true" {
-            return literal_expression(tokens, cursor, LiteralType::BOOLEAN);
-        } else if token._type.label == "false" {
-            return literal_expression(tokens, cursor, LiteralType::BOOLEAN);
-        } else if token._type.label == "null" {
-            return literal_expression(tokens, cursor, LiteralType::NULL);
-        } else if token._type.label == "regexp" {
-            return literal_expression(tokens, cursor, LiteralType::REGEXP);
+/*
+* <Expression> ::= <AssignmentExpression> (',' <AssignmentExpression>)*
+* 参数、数组元素这些需要单个表达式的位置直接使用 assignment_expression
+*/
+pub fn expression(
+    tokens: &[Token],
+    cursor: usize,
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let first_result = assignment_expression(tokens, cursor, no_in, context)?;
+    return sequence_expression(tokens, cursor, first_result, no_in, context);
+}
+
+/// 已经解析出第一个表达式后，继续解析 ',' 之后的部分，没有 ',' 时原样返回第一个表达式
+pub fn sequence_expression(
+    tokens: &[Token],
+    cursor: usize,
+    first_result: GramAnalysisResult<Expression>,
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let mut next_cursor = first_result.next_cursor;
+    if !matches!(tokens.get(next_cursor), Some(token) if token._type.label == ",") {
+        return Ok(first_result);
+    }
+    let mut expressions = vec![first_result.ast];
+    while matches!(tokens.get(next_cursor), Some(token) if token._type.label == ",") {
+        let expression_result = assignment_expression(tokens, next_cursor + 1, no_in, context)?;
+        expressions.push(expression_result.ast);
+        next_cursor = expression_result.next_cursor;
+    }
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = SequenceExpression {
+        _type: ASTType::SequenceExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        expressions,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::SequenceExpression(Some(Box::new(ast))),
+        next_cursor,
+    });
+}
+
+/*
+* <ParenthesizedExpression> ::= '(' <Expression> ')'
+* if、while、switch 等语句头部的括号和 primary_expression 中的括号表达式，括号本身不属于表达式节点
+*/
+pub fn parenthesized_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    match tokens.get(cursor) {
+        Some(token) if token._type.label == "(" => {}
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'('")),
+    }
+    let expression_result = expression(tokens, cursor + 1, false, context)?;
+    match tokens.get(expression_result.next_cursor) {
+        Some(token) if token._type.label == ")" => {
+            return Ok(GramAnalysisResult {
+                ast: expression_result.ast,
+                next_cursor: expression_result.next_cursor + 1,
+            });
         }
-        // ArrayExpression
-        else if token._type.label == "[" {
-            return array_expression(tokens, cursor);
+        _ => {
+            return Err(ParseError::unexpected(
+                tokens,
+                expression_result.next_cursor,
+                "')'",
+            ))
         }
     }
-    return Err("");
 }
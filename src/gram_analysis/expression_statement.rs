@@ -0,0 +1,37 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, Context, ExpressionStatement, GramAnalysisResult, SourceLocation},
+};
+
+use super::{expression::expression, statement::consume_semicolon};
+
+/*
+* <ExpressionStatement> ::= [lookahead ∉ { '{', 'function', 'async function', 'class', 'let [' }] <Expression> ';'
+* 以这些 token 开头的语句分别是块语句和声明，由 statement 分派，不会走到这里
+*/
+pub fn expression_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let expression_result = expression(tokens, cursor, false, context)?;
+    let next_cursor = consume_semicolon(tokens, expression_result.next_cursor)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = ExpressionStatement {
+        _type: ASTType::ExpressionStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        expression: expression_result.ast,
+        directive: None,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::ExpressionStatement(ast),
+        next_cursor,
+    });
+}
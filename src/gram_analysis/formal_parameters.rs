@@ -0,0 +1,274 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        BlockStatement, Body, Context, ExpressionStatement, GramAnalysisResult, Identifier, Pattern,
+    },
+};
+
+use super::{
+    binding_pattern::{binding_element, bound_identifiers, rest_element},
+    identifier::{is_eval_or_arguments, is_strict_reserved_word},
+};
+
+// 函数体的指令序言中是否有 "use strict"
+fn has_use_strict(body: &BlockStatement) -> bool {
+    return body.body.iter().any(|statement| {
+        matches!(
+            statement,
+            Body::ExpressionStatement(ExpressionStatement {
+                directive: Some(directive),
+                ..
+            }) if directive == "use strict"
+        )
+    });
+}
+
+/// 函数解析完成后检查参数列表：函数体中的 "use strict" 同样作用于参数和函数名，
+/// 这时参数列表必须是简单的；严格模式、非简单参数列表以及 `unique` 为 true 时（箭头函数和方法）参数不能重名
+pub fn check_parameters(
+    tokens: &[Token],
+    cursor: usize,
+    id: Option<&Identifier>,
+    params: &[Pattern],
+    body: Option<&BlockStatement>,
+    unique: bool,
+    context: &Context,
+) -> Result<(), ParseError> {
+    let use_strict = body.is_some_and(has_use_strict);
+    let simple = params
+        .iter()
+        .all(|param| matches!(param, Pattern::Identifier(_)));
+    if use_strict && !simple {
+        let token = &tokens[cursor];
+        return Err(ParseError::syntax(
+            "Illegal 'use strict' directive in function with non-simple parameter list",
+            token.start,
+            token.loc.start,
+        ));
+    }
+    let strict = context.strict || use_strict;
+    let mut identifiers = Vec::new();
+    for param in params {
+        bound_identifiers(param, &mut identifiers);
+    }
+    // 外层已经是严格模式时，名称在解析时就检查过了
+    if use_strict && !context.strict {
+        for identifier in id.into_iter().chain(identifiers.iter().copied()) {
+            let message = if is_eval_or_arguments(&identifier.name) {
+                format!("Binding {} in strict mode", identifier.name)
+            } else if is_strict_reserved_word(&identifier.name) {
+                format!("The keyword '{}' is reserved", identifier.name)
+            } else {
+                continue;
+            };
+            return Err(ParseError::syntax(
+                message,
+                identifier.start,
+                identifier.loc.start,
+            ));
+        }
+    }
+    if strict || !simple || unique {
+        for (index, identifier) in identifiers.iter().enumerate() {
+            if identifiers[..index]
+                .iter()
+                .any(|other| other.name == identifier.name)
+            {
+                return Err(ParseError::syntax(
+                    "Argument name clash",
+                    identifier.start,
+                    identifier.loc.start,
+                ));
+            }
+        }
+    }
+    return Ok(());
+}
+
+/*
+* <FormalParameters> ::= '(' ')'
+*                      | '(' <BindingElement> (',' <BindingElement>)* ','? ')'
+*                      | '(' (<BindingElement> ',')* '...' <BindingTarget> ')'
+* `cursor` 指向 '('，返回的 next_cursor 指向 ')' 之后
+*/
+pub fn formal_parameters(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Vec<Pattern>>, ParseError> {
+    match tokens.get(cursor) {
+        Some(token) if token._type.label == "(" => {}
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'('")),
+    }
+    // 参数的默认值中不能使用 await 和 yield 表达式，如 async function f(a = await b) {}
+    let params_context = Context {
+        in_parameters: true,
+        ..context.clone()
+    };
+    let context = &params_context;
+    let mut next_cursor = cursor + 1;
+    let mut params = Vec::new();
+    loop {
+        match tokens.get(next_cursor) {
+            Some(token) if token._type.label == ")" => break,
+            Some(token) if token._type.label == "..." => {
+                let rest_result = rest_element(tokens, next_cursor, ")", context)?;
+                params.push(Pattern::RestElement(Some(Box::new(rest_result.ast))));
+                next_cursor = rest_result.next_cursor;
+                break;
+            }
+            _ => {}
+        }
+        let param_result = binding_element(tokens, next_cursor, context)?;
+        params.push(param_result.ast);
+        match tokens.get(param_result.next_cursor) {
+            Some(token) if token._type.label == "," => next_cursor = param_result.next_cursor + 1,
+            Some(token) if token._type.label == ")" => {
+                next_cursor = param_result.next_cursor;
+                break;
+            }
+            _ => {
+                return Err(ParseError::unexpected(
+                    tokens,
+                    param_result.next_cursor,
+                    "',' or ')'",
+                ))
+            }
+        }
+    }
+    return Ok(GramAnalysisResult {
+        ast: params,
+        next_cursor: next_cursor + 1,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn parameter_early_errors() {
+        assert_errors(
+            script,
+            &[
+                ("'use strict'; function f(a, a) {}", "Argument name clash"),
+                ("function f(a, a) { 'use strict' }", "Argument name clash"),
+                ("function f(a, [a]) {}", "Argument name clash"),
+                ("(a, a) => 1", "Argument name clash"),
+                ("o = { m(a, a) {} }", "Argument name clash"),
+                (
+                    "'use strict'; function f(eval) {}",
+                    "Binding eval in strict mode",
+                ),
+                (
+                    "function eval() { 'use strict' }",
+                    "Binding eval in strict mode",
+                ),
+                (
+                    "function f(arguments) { 'use strict' }",
+                    "Binding arguments in strict mode",
+                ),
+                (
+                    "function f(public) { 'use strict' }",
+                    "The keyword 'public' is reserved",
+                ),
+                (
+                    "function f(a = 1) { 'use strict' }",
+                    "Illegal 'use strict' directive in function with non-simple parameter list",
+                ),
+                (
+                    "function f({ a }) { 'use strict' }",
+                    "Illegal 'use strict' directive",
+                ),
+                (
+                    "(a = 1) => { 'use strict' }",
+                    "Illegal 'use strict' directive",
+                ),
+                (
+                    "o = { m(...a) { 'use strict' } }",
+                    "Illegal 'use strict' directive",
+                ),
+            ],
+        );
+        assert_json(
+            script,
+            &[
+                ("function f(a, a) {}", r#""type":"FunctionDeclaration""#),
+                ("function f(eval, arguments) {}", r#""name":"arguments""#),
+                (
+                    "function f(a, b) { 'use strict' }",
+                    r#""directive":"use strict""#,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn await_and_yield_in_parameters() {
+        assert_errors(
+            script,
+            &[
+                (
+                    "async function f(a = await 1) {}",
+                    "Await expression cannot be a default value",
+                ),
+                (
+                    "o = { async m([a] = [await 1]) {} }",
+                    "Await expression cannot be a default value",
+                ),
+                (
+                    "async (a = await 1) => 1",
+                    "Await expression cannot be a default value",
+                ),
+                (
+                    "async function f() { (a = await 1) => a }",
+                    "Await expression cannot be a default value",
+                ),
+                (
+                    "function* g(a = yield) {}",
+                    "Yield expression cannot be a default value",
+                ),
+                (
+                    "o = { *m(a = yield 1) {} }",
+                    "Yield expression cannot be a default value",
+                ),
+                (
+                    "async function f() { (await) => 1 }",
+                    "Cannot use 'await' as identifier inside an async function",
+                ),
+                (
+                    "async function f() { (...await) => 1 }",
+                    "Cannot use 'await' as identifier inside an async function",
+                ),
+                (
+                    "function* g() { (yield) => 1 }",
+                    "Cannot use 'yield' as identifier inside a generator",
+                ),
+                (
+                    "function* g() { ({ yield }) => 1 }",
+                    "Cannot use 'yield' as identifier inside a generator",
+                ),
+            ],
+        );
+        assert_json(
+            script,
+            &[
+                // 嵌套函数的参数和函数体不受外层参数列表的限制
+                (
+                    "async function f(a = async () => await 1) {}",
+                    r#""type":"AwaitExpression""#,
+                ),
+                (
+                    "function* g(a = function* () { yield 1 }) {}",
+                    r#""type":"YieldExpression""#,
+                ),
+                (
+                    "(await) => 1",
+                    r#""params":[{"type":"Identifier","start":1,"end":6,"name":"await"}]"#,
+                ),
+                ("function g(yield) {}", r#""name":"yield""#),
+            ],
+        );
+    }
+}
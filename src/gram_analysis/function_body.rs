@@ -0,0 +1,63 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, BlockStatement, Context, GramAnalysisResult, SourceLocation},
+};
+
+use super::{directive_prologue::directive_prologue, statement_list::statement_list};
+
+/*
+* <FunctionBody> ::= '{' <DirectivePrologue> <StatementList>? '}'
+* 函数体内不能 break/continue 到外层的标签和循环，严格模式由外层继承或由 "use strict" 开启，
+* 能否使用 super 由调用方决定（箭头函数继承外层），await 和 yield 是否是运算符也由调用方决定
+*/
+pub fn function_body(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<BlockStatement>, ParseError> {
+    let start_token = match tokens.get(cursor) {
+        Some(token) if token._type.label == "{" => token,
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'{'")),
+    };
+    let mut body_context = Context {
+        in_function: true,
+        strict: context.strict,
+        allow_super_call: context.allow_super_call,
+        allow_super_property: context.allow_super_property,
+        module: context.module,
+        allow_new_target: context.allow_new_target,
+        in_async: context.in_async,
+        in_generator: context.in_generator,
+        in_class_initializer: context.in_class_initializer,
+        private_names: context.private_names.clone(),
+        depth: context.depth,
+        ..Context::default()
+    };
+    let directive_result = directive_prologue(tokens, cursor + 1, &body_context)?;
+    body_context.strict = body_context.strict || directive_result.strict;
+    let mut body = directive_result.statements;
+    let mut statement_list_result =
+        statement_list(tokens, directive_result.next_cursor, &body_context)?;
+    body.append(&mut statement_list_result.statements);
+    let right_brace_cursor = statement_list_result.next_cursor;
+    match tokens.get(right_brace_cursor) {
+        Some(end_token) if end_token._type.label == "}" => {
+            let ast = BlockStatement {
+                _type: ASTType::BlockStatement,
+                start: start_token.start,
+                end: end_token.end,
+                loc: SourceLocation {
+                    start: start_token.loc.start,
+                    end: end_token.loc.end,
+                },
+                body,
+            };
+            return Ok(GramAnalysisResult {
+                ast,
+                next_cursor: right_brace_cursor + 1,
+            });
+        }
+        _ => return Err(ParseError::unexpected(tokens, right_brace_cursor, "'}'")),
+    }
+}
@@ -0,0 +1,57 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, Context, FunctionDeclaration, GramAnalysisResult},
+};
+
+use super::function_expression::function_expression;
+
+/*
+* <FunctionDeclaration> ::= ('async')? 'function' ('*')? <Identifier> '(' <FormalParameters> ')' '{' <FunctionBody> '}'
+*/
+pub fn function_declaration(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let declaration_result = function_declaration_node(tokens, cursor, false, context)?;
+    return Ok(GramAnalysisResult {
+        ast: Body::FunctionDeclaration(declaration_result.ast),
+        next_cursor: declaration_result.next_cursor,
+    });
+}
+
+/// 函数声明，`allow_anonymous` 为 true 时用于 export default，可以省略函数名
+pub fn function_declaration_node(
+    tokens: &[Token],
+    cursor: usize,
+    allow_anonymous: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<FunctionDeclaration>, ParseError> {
+    let function_result = function_expression(tokens, cursor, true, context)?;
+    let function_ast = function_result.ast;
+    if function_ast.id.is_none() && !allow_anonymous {
+        // 声明必须有函数名，报错位置在 '(' 上
+        let params_cursor = tokens[cursor..]
+            .iter()
+            .position(|token| token._type.label == "(")
+            .map_or(cursor, |offset| cursor + offset);
+        return Err(ParseError::unexpected(tokens, params_cursor, "identifier"));
+    }
+    let ast = FunctionDeclaration {
+        _type: ASTType::FunctionDeclaration,
+        start: function_ast.start,
+        end: function_ast.end,
+        loc: function_ast.loc,
+        id: function_ast.id,
+        expression: false,
+        generator: function_ast.generator,
+        _async: function_ast._async,
+        params: function_ast.params,
+        body: function_ast.body,
+    };
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: function_result.next_cursor,
+    });
+}
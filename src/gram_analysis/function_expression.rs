@@ -1,185 +1,212 @@
 use crate::{
+    error::ParseError,
     tokenizer::Token,
-    types::{
-        ASTType, BlockStatement, Body, Expression, FunctionExpression, GramAnalysisResult,
-        Identifier,
-    },
+    types::{ASTType, Context, FunctionExpression, GramAnalysisResult, SourceLocation},
 };
 
-use super::block_statement::block_statement;
+use super::{
+    formal_parameters::{check_parameters, formal_parameters},
+    function_body::function_body,
+    identifier::{binding_identifier, is_contextual},
+};
+
+/// `async function` 中两个 token 必须在同一行
+pub fn is_async_function(tokens: &[Token], cursor: usize) -> bool {
+    match tokens.get(cursor + 1) {
+        Some(next_token) => {
+            return is_contextual(tokens, cursor, "async")
+                && next_token._type.label == "function"
+                && !next_token.newline_before;
+        }
+        None => return false,
+    }
+}
 
+/*
+* <FunctionExpression> ::= ('async')? 'function' ('*')? <Identifier>? '(' <FormalParameters> ')' '{' <FunctionBody> '}'
+* 函数声明与函数表达式结构相同，声明时由调用方检查函数名；
+* 声明的函数名属于外层作用域，表达式的函数名按函数自身是否 async 和生成器检查
+*/
 pub fn function_expression(
     tokens: &[Token],
     cursor: usize,
-) -> Result<GramAnalysisResult<Expression>, &str> {
-    if let Some(token) = tokens.get(cursor) {
-        let mut ast = FunctionExpression {
-            _type: ASTType::FunctionExpression,
-            start: token.start,
-            end: token.end,
-            id: None,
-            _async: false,
-            expression: false,
-            generator: false,
-            params: Vec::new(),
-            body: BlockStatement {
-                _type: ASTType::BlockStatement,
-                start: token.start,
-                end: token.end,
-                body: Vec::new(),
-            },
+    declaration: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<FunctionExpression>, ParseError> {
+    let mut next_cursor = cursor;
+    let _async = is_async_function(tokens, cursor);
+    if _async {
+        next_cursor += 1;
+    }
+    match tokens.get(next_cursor) {
+        Some(token) if token._type.label == "function" => next_cursor += 1,
+        _ => return Err(ParseError::unexpected(tokens, next_cursor, "'function'")),
+    }
+    let generator = matches!(tokens.get(next_cursor), Some(token) if token._type.label == "*");
+    if generator {
+        next_cursor += 1;
+    }
+    let mut id = None;
+    if matches!(tokens.get(next_cursor), Some(token) if token._type.label == "name") {
+        let identifier_result = if declaration {
+            binding_identifier(tokens, next_cursor, context)?
+        } else {
+            let name_context = Context {
+                in_async: _async,
+                in_generator: generator,
+                ..context.clone()
+            };
+            binding_identifier(tokens, next_cursor, &name_context)?
         };
-        if token._type.label == "name" {
-            if let Some(next_token) = tokens.get(cursor + 1) {
-                if next_token._type.label == "(" {
-                    if let Some(next_next_token) = tokens.get(cursor + 2) {
-                        if next_next_token._type.label == ")" {
-                            if let Some(right_bracket_next_token) = tokens.get(cursor + 3) {
-                                if right_bracket_next_token._type.label == "{" {
-                                    if let Ok(block_statement_result) =
-                                        block_statement(tokens, cursor + 2)
-                                    {
-                                        if let Body::BlockStatement(block_statement) =
-                                            block_statement_result.ast
-                                        {
-                                            ast.body = block_statement;
-                                            ast.end = block_statement_result.next_cursor
-                                        }
-                                    }
-                                }
-                            }
-                        } else if next_next_token._type.label == "name" {
-                            // 有参数
-                            let mut current_cursor = cursor + 1;
-                            let mut current_token = next_next_token;
-                            loop {
-                                let identifier = Identifier {
-                                    _type: ASTType::Identifier,
-                                    start: current_token.start,
-                                    end: current_token.end,
-                                    name: current_token.value.to_string(),
-                                };
-                                ast.params.push(identifier);
-                                current_cursor += 1;
-                                if let Some(param_next_token) = tokens.get(current_cursor) {
-                                    if param_next_token._type.label == "," {
-                                        current_cursor += 1;
-                                        if let Some(comma_next_token) = tokens.get(current_cursor) {
-                                            if comma_next_token._type.label == "name" {
-                                                current_token = comma_next_token;
-                                            } else {
-                                                current_token = comma_next_token;
-                                                break;
-                                            }
-                                        } else {
-                                            return Err("");
-                                        }
-                                    } else {
-                                        current_token = param_next_token;
-                                        break;
-                                    }
-                                } else {
-                                    return Err("");
-                                }
-                            }
-                            if current_token._type.label == ")" {
-                                if let Some(right_bracket_next_token) =
-                                    tokens.get(current_cursor + 1)
-                                {
-                                    if right_bracket_next_token._type.label == "{" {
-                                        if let Ok(block_statement_result) =
-                                            block_statement(tokens, current_cursor + 1)
-                                        {
-                                            if let Body::BlockStatement(block_statement) =
-                                                block_statement_result.ast
-                                            {
-                                                ast.body = block_statement;
-                                                ast.end = block_statement_result.next_cursor
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        } else if let Some(token) = tokens.get(cursor) {
-            if token._type.label == "(" {
-                if let Some(next_token) = tokens.get(cursor + 1) {
-                    if next_token._type.label == ")" {
-                        if let Some(right_bracket_next_token) = tokens.get(cursor + 2) {
-                            if right_bracket_next_token._type.label == "{" {
-                                if let Ok(block_statement_result) =
-                                    block_statement(tokens, cursor + 2)
-                                {
-                                    if let Body::BlockStatement(block_statement) =
-                                        block_statement_result.ast
-                                    {
-                                        ast.body = block_statement;
-                                        ast.end = block_statement_result.next_cursor
-                                    }
-                                }
-                            }
-                        }
-                    } else if next_token._type.label == "name" {
-                        // 有参数
-                        let mut current_cursor = cursor + 1;
-                        let mut current_token = next_token;
-                        loop {
-                            let identifier = Identifier {
-                                _type: ASTType::Identifier,
-                                start: current_token.start,
-                                end: current_token.end,
-                                name: current_token.value.to_string(),
-                            };
-                            ast.params.push(identifier);
-                            current_cursor += 1;
-                            if let Some(param_next_token) = tokens.get(current_cursor) {
-                                if param_next_token._type.label == "," {
-                                    current_cursor += 1;
-                                    if let Some(comma_next_token) = tokens.get(current_cursor) {
-                                        if comma_next_token._type.label == "name" {
-                                            current_token = comma_next_token;
-                                        } else {
-                                            current_token = comma_next_token;
-                                            break;
-                                        }
-                                    } else {
-                                        return Err("");
-                                    }
-                                } else {
-                                    current_token = param_next_token;
-                                    break;
-                                }
-                            } else {
-                                return Err("");
-                            }
-                        }
-                        if current_token._type.label == ")" {
-                            if let Some(right_bracket_next_token) = tokens.get(current_cursor + 1) {
-                                if right_bracket_next_token._type.label == "{" {
-                                    if let Ok(block_statement_result) =
-                                        block_statement(tokens, current_cursor + 1)
-                                    {
-                                        if let Body::BlockStatement(block_statement) =
-                                            block_statement_result.ast
-                                        {
-                                            ast.body = block_statement;
-                                            ast.end = block_statement_result.next_cursor
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        } else if token._type.label == "async" {
-            ast._async = true;
-        } else if token._type.label == "*" {
-            ast.generator = true;
-        }
+        id = Some(identifier_result.ast);
+        next_cursor = identifier_result.next_cursor;
+    }
+    // 普通函数内不能使用 super
+    let function_context = Context {
+        allow_super_call: false,
+        allow_super_property: false,
+        allow_new_target: true,
+        in_async: _async,
+        in_generator: generator,
+        in_class_initializer: false,
+        in_static_block: false,
+        ..context.clone()
+    };
+    let params_result = formal_parameters(tokens, next_cursor, &function_context)?;
+    let body_result = function_body(tokens, params_result.next_cursor, &function_context)?;
+    check_parameters(
+        tokens,
+        cursor,
+        id.as_ref(),
+        &params_result.ast,
+        Some(&body_result.ast),
+        false,
+        context,
+    )?;
+    let start_token = &tokens[cursor];
+    let body = body_result.ast;
+    let ast = FunctionExpression {
+        _type: ASTType::FunctionExpression,
+        start: start_token.start,
+        end: body.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: body.loc.end,
+        },
+        id,
+        expression: false,
+        generator,
+        _async,
+        params: params_result.ast,
+        body,
+    };
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: body_result.next_cursor,
+    });
+}
+
+/// 对象和类中的方法：从 '(' 开始解析参数和函数体，与 acorn 一致节点从参数列表开始；
+/// 方法中可以使用 super.x，只有子类的构造函数可以调用 super()
+pub fn method_function(
+    tokens: &[Token],
+    cursor: usize,
+    _async: bool,
+    generator: bool,
+    allow_super_call: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<FunctionExpression>, ParseError> {
+    let function_context = Context {
+        allow_super_call,
+        allow_super_property: true,
+        allow_new_target: true,
+        in_async: _async,
+        in_generator: generator,
+        in_class_initializer: false,
+        in_static_block: false,
+        ..context.clone()
+    };
+    let params_result = formal_parameters(tokens, cursor, &function_context)?;
+    let body_result = function_body(tokens, params_result.next_cursor, &function_context)?;
+    check_parameters(
+        tokens,
+        cursor,
+        None,
+        &params_result.ast,
+        Some(&body_result.ast),
+        true,
+        context,
+    )?;
+    let body = body_result.ast;
+    let start_token = &tokens[cursor];
+    let ast = FunctionExpression {
+        _type: ASTType::FunctionExpression,
+        start: start_token.start,
+        end: body.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: body.loc.end,
+        },
+        id: None,
+        expression: false,
+        generator,
+        _async,
+        params: params_result.ast,
+        body,
+    };
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: body_result.next_cursor,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn function_names() {
+        assert_json(
+            script,
+            &[
+                // 声明的函数名属于外层作用域
+                ("async function await() {}", r#""name":"await""#),
+                ("function* yield() {}", r#""name":"yield""#),
+                // 表达式的函数名只受函数自身的限制
+                (
+                    "async function f() { x = function await() {} }",
+                    r#""id":{"type":"Identifier","start":34,"end":39,"name":"await"}"#,
+                ),
+                (
+                    "function* g() { x = function yield() {} }",
+                    r#""id":{"type":"Identifier","start":29,"end":34,"name":"yield"}"#,
+                ),
+            ],
+        );
+        assert_errors(
+            script,
+            &[
+                (
+                    "x = async function await() {}",
+                    "Cannot use 'await' as identifier inside an async function",
+                ),
+                (
+                    "x = function* yield() {}",
+                    "Cannot use 'yield' as identifier inside a generator",
+                ),
+                (
+                    "x = async function* yield() {}",
+                    "Cannot use 'yield' as identifier inside a generator",
+                ),
+                (
+                    "function* g() { function yield() {} }",
+                    "Cannot use 'yield' as identifier inside a generator",
+                ),
+                (
+                    "async function f() { function await() {} }",
+                    "Cannot use 'await' as identifier inside an async function",
+                ),
+            ],
+        );
     }
-    return Err("");
 }
@@ -1,14 +1,160 @@
-use crate::tokenizer::{Token, TokenValue};
-use crate::types::{ASTType, GramAnalysisResult, Identifier, AST};
+use crate::error::ParseError;
+use crate::tokenizer::{is_keyword, Token, TokenValue};
+use crate::types::{
+    ASTType, Context, GramAnalysisResult, Identifier, PrivateIdentifier, SourceLocation,
+};
 
-pub fn identifier(tokens: &[Token], cursor: usize) -> Result<GramAnalysisResult<Identifier>, &str> {
+/// `as`、`from` 这样只在特定位置有特殊含义的名称，词法上仍然是普通的标识符
+pub fn is_contextual(tokens: &[Token], cursor: usize, name: &str) -> bool {
+    match tokens.get(cursor) {
+        Some(token) if token.contextual_keyword => {
+            return matches!(&token.value, TokenValue::String(value) if value == name);
+        }
+        _ => return false,
+    }
+}
+
+// 严格模式中保留、不能作为标识符的名称
+const STRICT_RESERVED_WORDS: [&str; 9] = [
+    "implements",
+    "interface",
+    "let",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "static",
+    "yield",
+];
+
+pub fn is_strict_reserved_word(name: &str) -> bool {
+    return STRICT_RESERVED_WORDS.contains(&name);
+}
+
+// 严格模式中 eval 和 arguments 不能被绑定或赋值
+pub fn is_eval_or_arguments(name: &str) -> bool {
+    return name == "eval" || name == "arguments";
+}
+
+pub fn identifier(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Identifier>, ParseError> {
     if let Some(token) = tokens.get(cursor) {
         if let TokenValue::String(name) = &token.value {
             if token._type.label == "name" {
+                // 含有转义的关键字词法上是普通名称，但仍然不能用作标识符
+                if token.escaped && is_keyword(name) {
+                    return Err(ParseError::syntax(
+                        format!("Escape sequence in keyword {}", name),
+                        token.start,
+                        token.loc.start,
+                    ));
+                }
+                let ast = Identifier {
+                    _type: ASTType::Identifier,
+                    start: token.start,
+                    end: token.end,
+                    loc: SourceLocation {
+                        start: token.loc.start,
+                        end: token.loc.end,
+                    },
+                    name: String::from(name),
+                };
+                return Ok(GramAnalysisResult {
+                    ast,
+                    next_cursor: cursor + 1,
+                });
+            }
+        }
+    }
+    return Err(ParseError::unexpected(tokens, cursor, "identifier"));
+}
+
+/// 标识符引用和绑定：`await`、`yield`、`let` 词法上是普通名称，但 await 在模块和异步函数中保留，
+/// yield 在生成器中保留，yield、let、static、implements 等在严格模式中保留
+pub fn identifier_reference(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Identifier>, ParseError> {
+    let token = match tokens.get(cursor) {
+        Some(token) if token._type.label == "name" => token,
+        _ => return identifier(tokens, cursor),
+    };
+    // 含有转义的 aw\u0061it 同样受这些限制
+    let message = match &token.value {
+        TokenValue::String(name) if name == "await" => {
+            // 模块顶层的 in_async 也为 true，但那里的 await 不是在异步函数中
+            if context.in_async && (context.in_function || !context.module) {
+                Some(String::from(
+                    "Cannot use 'await' as identifier inside an async function",
+                ))
+            } else if context.in_static_block {
+                Some(String::from(
+                    "Cannot use 'await' as identifier inside a class static block",
+                ))
+            } else if context.module {
+                Some(String::from(
+                    "Cannot use keyword 'await' outside an async function",
+                ))
+            } else {
+                None
+            }
+        }
+        TokenValue::String(name) if name == "arguments" && context.in_class_initializer => {
+            Some(String::from(
+                "'arguments' is not allowed in class field initializer or static initialization block",
+            ))
+        }
+        TokenValue::String(name) if name == "yield" && context.in_generator => Some(String::from(
+            "Cannot use 'yield' as identifier inside a generator",
+        )),
+        TokenValue::String(name) if context.strict && is_strict_reserved_word(name) => {
+            Some(format!("The keyword '{}' is reserved", name))
+        }
+        _ => None,
+    };
+    if let Some(message) = message {
+        return Err(ParseError::syntax(message, token.start, token.loc.start));
+    }
+    return identifier(tokens, cursor);
+}
+
+/// 绑定名称：变量、参数、函数名、类名和导入的本地名称，严格模式中不能是 eval 和 arguments
+pub fn binding_identifier(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Identifier>, ParseError> {
+    let identifier_result = identifier_reference(tokens, cursor, context)?;
+    let identifier = &identifier_result.ast;
+    if context.strict && is_eval_or_arguments(&identifier.name) {
+        return Err(ParseError::syntax(
+            format!("Binding {} in strict mode", identifier.name),
+            identifier.start,
+            identifier.loc.start,
+        ));
+    }
+    return Ok(identifier_result);
+}
+
+/// IdentifierName：属性名等位置除了标识符，还可以是 `class`、`if` 这样的保留字
+pub fn identifier_name(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Identifier>, ParseError> {
+    if let Some(token) = tokens.get(cursor) {
+        if let TokenValue::String(name) = &token.value {
+            if token._type.label == "name" || token._type.keyword.0.is_some() {
                 let ast = Identifier {
                     _type: ASTType::Identifier,
                     start: token.start,
                     end: token.end,
+                    loc: SourceLocation {
+                        start: token.loc.start,
+                        end: token.loc.end,
+                    },
                     name: String::from(name),
                 };
                 return Ok(GramAnalysisResult {
@@ -18,5 +164,161 @@ pub fn identifier(tokens: &[Token], cursor: usize) -> Result<GramAnalysisResult<
             }
         }
     }
-    return Err("occur error when parsing identifier");
+    return Err(ParseError::unexpected(tokens, cursor, "identifier"));
+}
+
+/// 对私有名称的引用，如 this.#x 和 #x in o，名称必须在外层的某个类中声明
+pub fn private_name_reference(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<PrivateIdentifier>, ParseError> {
+    let private_result = private_identifier(tokens, cursor)?;
+    let private = &private_result.ast;
+    if !context.private_names.contains(&private.name) {
+        return Err(ParseError::syntax(
+            format!(
+                "Private field '#{}' must be declared in an enclosing class",
+                private.name
+            ),
+            private.start,
+            private.loc.start,
+        ));
+    }
+    return Ok(private_result);
+}
+
+/// PrivateIdentifier：类的私有名称 #x，name 不包括 '#'
+pub fn private_identifier(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<PrivateIdentifier>, ParseError> {
+    if let Some(token) = tokens.get(cursor) {
+        if let TokenValue::String(name) = &token.value {
+            if token._type.label == "privateId" {
+                let ast = PrivateIdentifier {
+                    _type: ASTType::PrivateIdentifier,
+                    start: token.start,
+                    end: token.end,
+                    loc: SourceLocation {
+                        start: token.loc.start,
+                        end: token.loc.end,
+                    },
+                    name: String::from(name),
+                };
+                return Ok(GramAnalysisResult {
+                    ast,
+                    next_cursor: cursor + 1,
+                });
+            }
+        }
+    }
+    return Err(ParseError::unexpected(tokens, cursor, "private name"));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, module, script};
+
+    #[test]
+    fn await_in_module() {
+        let message = "Cannot use keyword 'await' outside an async function";
+        assert_errors(
+            module,
+            &[
+                ("await: 1", message),
+                ("function f() { await: 1 }", message),
+                ("var await = 1", message),
+                ("function await() {}", message),
+                ("class await {}", message),
+                ("function f(await) {}", message),
+                ("try {} catch (await) {}", message),
+                ("await => 1", message),
+                ("import await from 'a'", message),
+                ("import * as await from 'a'", message),
+                ("import { await } from 'a'", message),
+                ("import { a as await } from 'a'", message),
+            ],
+        );
+        assert_json(
+            script,
+            &[
+                (
+                    "await: 1",
+                    r#""label":{"type":"Identifier","start":0,"end":5,"name":"await"}"#,
+                ),
+                ("var await = 1", r#""name":"await""#),
+                ("function await() {}", r#""name":"await""#),
+            ],
+        );
+    }
+
+    #[test]
+    fn escaped_keywords() {
+        assert_errors(
+            script,
+            &[
+                ("v\\u0061r x = 1", "Escape sequence in keyword var"),
+                ("var v\\u0061r = 1", "Escape sequence in keyword var"),
+                ("\\u{74}his", "Escape sequence in keyword this"),
+                // 含有转义的上下文关键字只是普通名称
+                ("l\\u0065t x = 1", "expected ';', found 'x'"),
+                (
+                    "async function f() { aw\\u0061it x }",
+                    "Cannot use 'await' as identifier",
+                ),
+            ],
+        );
+        assert_json(
+            script,
+            &[
+                (
+                    "a.\\u0069f",
+                    r#""property":{"type":"Identifier","start":2,"end":9,"name":"if"}"#,
+                ),
+                ("({ \\u0069f: 1 })", r#""name":"if""#),
+                ("\\u0061bc", r#""name":"abc""#),
+            ],
+        );
+    }
+
+    #[test]
+    fn reserved_binding_names() {
+        assert_errors(
+            script,
+            &[
+                (
+                    "function* g() { yield: 1 }",
+                    "Cannot use 'yield' as identifier inside a generator",
+                ),
+                ("class yield {}", "The keyword 'yield' is reserved"),
+                ("class let {}", "The keyword 'let' is reserved"),
+                (
+                    "'use strict'; implements = 1",
+                    "The keyword 'implements' is reserved",
+                ),
+                (
+                    "'use strict'; var interface",
+                    "The keyword 'interface' is reserved",
+                ),
+                (
+                    "'use strict'; package: 1",
+                    "The keyword 'package' is reserved",
+                ),
+                (
+                    "function f() { 'use strict'; private = 1 }",
+                    "The keyword 'private' is reserved",
+                ),
+                (
+                    "function protected() { 'use strict' }",
+                    "The keyword 'protected' is reserved",
+                ),
+                (
+                    "'use strict'; static = 1",
+                    "The keyword 'static' is reserved",
+                ),
+            ],
+        );
+        assert_json(script, &[("implements = public", r#""name":"public""#)]);
+    }
 }
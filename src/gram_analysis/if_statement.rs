@@ -0,0 +1,54 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, Context, GramAnalysisResult, IfStatement, SourceLocation},
+};
+
+use super::{
+    expression::parenthesized_expression,
+    statement::{substatement, SubstatementPosition},
+};
+
+/*
+* <IfStatement> ::= 'if' '(' <Expression> ')' <Statement> ('else' <Statement>)?
+* else 与最近的 if 匹配
+*/
+pub fn if_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let test_result = parenthesized_expression(tokens, cursor + 1, context)?;
+    let consequent_result = substatement(
+        tokens,
+        test_result.next_cursor,
+        SubstatementPosition::If,
+        context,
+    )?;
+    let mut next_cursor = consequent_result.next_cursor;
+    let mut alternate = None;
+    if matches!(tokens.get(next_cursor), Some(token) if token._type.label == "else") {
+        let alternate_result =
+            substatement(tokens, next_cursor + 1, SubstatementPosition::If, context)?;
+        alternate = Some(alternate_result.ast);
+        next_cursor = alternate_result.next_cursor;
+    }
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = IfStatement {
+        _type: ASTType::IfStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        test: test_result.ast,
+        consequent: consequent_result.ast,
+        alternate,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::IfStatement(Some(Box::new(ast))),
+        next_cursor,
+    });
+}
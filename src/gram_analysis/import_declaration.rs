@@ -0,0 +1,348 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, Body, Context, Expression, GramAnalysisResult, Identifier, ImportAttribute,
+        ImportDeclaration, ImportDeclarationSpecifier, ImportDefaultSpecifier,
+        ImportNamespaceSpecifier, ImportSpecifier, Literal, LiteralType, LiteralValue,
+        ModuleExportName, SourceLocation,
+    },
+};
+
+use super::{
+    identifier::{binding_identifier, identifier_name, is_contextual},
+    literal_expression::literal_expression,
+    statement::consume_semicolon,
+};
+
+fn is_label(tokens: &[Token], cursor: usize, label: &str) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == label);
+}
+
+/// `import(` 和 `import.` 开头的是表达式，其余的 import 是导入声明
+pub fn is_import_declaration(tokens: &[Token], cursor: usize) -> bool {
+    return is_label(tokens, cursor, "import")
+        && !is_label(tokens, cursor + 1, "(")
+        && !is_label(tokens, cursor + 1, ".");
+}
+
+/// ModuleExportName 对应的名称，字符串取转义后的值
+pub fn module_export_name_string(name: &ModuleExportName) -> &str {
+    match name {
+        ModuleExportName::Identifier(identifier) => return &identifier.name,
+        ModuleExportName::Literal(literal) => match &literal.value {
+            LiteralValue::String(value) => return value,
+            _ => return "",
+        },
+    }
+}
+
+fn string_literal(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Literal>, ParseError> {
+    if is_label(tokens, cursor, "string") {
+        // import 和 export 只出现在模块中，模块代码总是严格模式
+        let context = Context {
+            strict: true,
+            ..Context::default()
+        };
+        let literal_result = literal_expression(tokens, cursor, LiteralType::STRING, &context)?;
+        if let Expression::Literal(Some(literal)) = literal_result.ast {
+            return Ok(GramAnalysisResult {
+                ast: *literal,
+                next_cursor: literal_result.next_cursor,
+            });
+        }
+    }
+    return Err(ParseError::unexpected(tokens, cursor, "string"));
+}
+
+/*
+* <FromClause> ::= 'from' <ModuleSpecifier>
+* <ModuleSpecifier> ::= <StringLiteral>
+*/
+pub fn from_clause(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Literal>, ParseError> {
+    if !is_contextual(tokens, cursor, "from") {
+        return Err(ParseError::unexpected(tokens, cursor, "'from'"));
+    }
+    return string_literal(tokens, cursor + 1);
+}
+
+/*
+* <ModuleExportName> ::= <IdentifierName> | <StringLiteral>
+*/
+pub fn module_export_name(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<ModuleExportName>, ParseError> {
+    if is_label(tokens, cursor, "string") {
+        let literal_result = string_literal(tokens, cursor)?;
+        return Ok(GramAnalysisResult {
+            ast: ModuleExportName::Literal(literal_result.ast),
+            next_cursor: literal_result.next_cursor,
+        });
+    }
+    let identifier_result = identifier_name(tokens, cursor)?;
+    return Ok(GramAnalysisResult {
+        ast: ModuleExportName::Identifier(identifier_result.ast),
+        next_cursor: identifier_result.next_cursor,
+    });
+}
+
+/*
+* <WithClause> ::= 'with' '{' '}' | 'with' '{' <ImportAttribute> (',' <ImportAttribute>)* ','? '}'
+* <ImportAttribute> ::= (<IdentifierName> | <StringLiteral>) ':' <StringLiteral>
+* 没有 with 时返回空列表
+*/
+pub fn with_clause(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Vec<ImportAttribute>>, ParseError> {
+    let mut attributes: Vec<ImportAttribute> = Vec::new();
+    if !is_label(tokens, cursor, "with") {
+        return Ok(GramAnalysisResult {
+            ast: attributes,
+            next_cursor: cursor,
+        });
+    }
+    if !is_label(tokens, cursor + 1, "{") {
+        return Err(ParseError::unexpected(tokens, cursor + 1, "'{'"));
+    }
+    let mut next_cursor = cursor + 2;
+    while !is_label(tokens, next_cursor, "}") {
+        let key_result = module_export_name(tokens, next_cursor)?;
+        let key = key_result.ast;
+        let key_name = module_export_name_string(&key);
+        if attributes
+            .iter()
+            .any(|attribute| module_export_name_string(&attribute.key) == key_name)
+        {
+            let key_token = &tokens[next_cursor];
+            return Err(ParseError::syntax(
+                format!("Duplicate attribute key '{}'", key_name),
+                key_token.start,
+                key_token.loc.start,
+            ));
+        }
+        if !is_label(tokens, key_result.next_cursor, ":") {
+            return Err(ParseError::unexpected(
+                tokens,
+                key_result.next_cursor,
+                "':'",
+            ));
+        }
+        let value_result = string_literal(tokens, key_result.next_cursor + 1)?;
+        let start_token = &tokens[next_cursor];
+        let value = value_result.ast;
+        attributes.push(ImportAttribute {
+            _type: ASTType::ImportAttribute,
+            start: start_token.start,
+            end: value.end,
+            loc: SourceLocation {
+                start: start_token.loc.start,
+                end: value.loc.end,
+            },
+            key,
+            value,
+        });
+        next_cursor = value_result.next_cursor;
+        match tokens.get(next_cursor) {
+            Some(token) if token._type.label == "," => next_cursor += 1,
+            Some(token) if token._type.label == "}" => {}
+            _ => return Err(ParseError::unexpected(tokens, next_cursor, "',' or '}'")),
+        }
+    }
+    return Ok(GramAnalysisResult {
+        ast: attributes,
+        next_cursor: next_cursor + 1,
+    });
+}
+
+/*
+* <ImportDeclaration> ::= 'import' <ImportClause> <FromClause> <WithClause>? ';'
+*                       | 'import' <ModuleSpecifier> <WithClause>? ';'
+* <ImportClause> ::= <ImportedDefaultBinding>
+*                  | <NameSpaceImport>
+*                  | <NamedImports>
+*                  | <ImportedDefaultBinding> ',' (<NameSpaceImport> | <NamedImports>)
+* <NameSpaceImport> ::= '*' 'as' <Identifier>
+* <NamedImports> ::= '{' '}' | '{' <ImportSpecifier> (',' <ImportSpecifier>)* ','? '}'
+*/
+pub fn import_declaration(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    if !is_label(tokens, cursor, "import") {
+        return Err(ParseError::unexpected(tokens, cursor, "'import'"));
+    }
+    let mut next_cursor = cursor + 1;
+    let mut specifiers = Vec::new();
+    // import "a" 只执行模块，不导入任何名称
+    let source_result = if is_label(tokens, next_cursor, "string") {
+        string_literal(tokens, next_cursor)?
+    } else {
+        let mut need_more = true;
+        if is_label(tokens, next_cursor, "name") {
+            let local_result = import_binding(tokens, next_cursor)?;
+            let local = local_result.ast;
+            specifiers.push(ImportDeclarationSpecifier::ImportDefaultSpecifier(
+                ImportDefaultSpecifier {
+                    _type: ASTType::ImportDefaultSpecifier,
+                    start: local.start,
+                    end: local.end,
+                    loc: local.loc,
+                    local,
+                },
+            ));
+            next_cursor = local_result.next_cursor;
+            need_more = is_label(tokens, next_cursor, ",");
+            if need_more {
+                next_cursor += 1;
+            }
+        }
+        if need_more {
+            if is_label(tokens, next_cursor, "*") {
+                let namespace_result = namespace_import(tokens, next_cursor)?;
+                specifiers.push(namespace_result.ast);
+                next_cursor = namespace_result.next_cursor;
+            } else if is_label(tokens, next_cursor, "{") {
+                let named_result = named_imports(tokens, next_cursor)?;
+                specifiers.extend(named_result.ast);
+                next_cursor = named_result.next_cursor;
+            } else {
+                return Err(ParseError::unexpected(tokens, next_cursor, "'*' or '{'"));
+            }
+        }
+        from_clause(tokens, next_cursor)?
+    };
+    let attributes_result = with_clause(tokens, source_result.next_cursor)?;
+    next_cursor = consume_semicolon(tokens, attributes_result.next_cursor)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = ImportDeclaration {
+        _type: ASTType::ImportDeclaration,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        specifiers,
+        source: source_result.ast,
+        attributes: attributes_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::ImportDeclaration(ast),
+        next_cursor,
+    });
+}
+
+fn namespace_import(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<ImportDeclarationSpecifier>, ParseError> {
+    if !is_contextual(tokens, cursor + 1, "as") {
+        return Err(ParseError::unexpected(tokens, cursor + 1, "'as'"));
+    }
+    let local_result = import_binding(tokens, cursor + 2)?;
+    let start_token = &tokens[cursor];
+    let local = local_result.ast;
+    let ast = ImportNamespaceSpecifier {
+        _type: ASTType::ImportNamespaceSpecifier,
+        start: start_token.start,
+        end: local.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: local.loc.end,
+        },
+        local,
+    };
+    return Ok(GramAnalysisResult {
+        ast: ImportDeclarationSpecifier::ImportNamespaceSpecifier(ast),
+        next_cursor: local_result.next_cursor,
+    });
+}
+
+fn named_imports(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Vec<ImportDeclarationSpecifier>>, ParseError> {
+    let mut specifiers = Vec::new();
+    let mut next_cursor = cursor + 1;
+    while !is_label(tokens, next_cursor, "}") {
+        let specifier_result = import_specifier(tokens, next_cursor)?;
+        specifiers.push(ImportDeclarationSpecifier::ImportSpecifier(
+            specifier_result.ast,
+        ));
+        next_cursor = specifier_result.next_cursor;
+        match tokens.get(next_cursor) {
+            Some(token) if token._type.label == "," => next_cursor += 1,
+            Some(token) if token._type.label == "}" => {}
+            _ => return Err(ParseError::unexpected(tokens, next_cursor, "',' or '}'")),
+        }
+    }
+    return Ok(GramAnalysisResult {
+        ast: specifiers,
+        next_cursor: next_cursor + 1,
+    });
+}
+
+// 导入的本地绑定，模块中 await 等保留字不能作为绑定名称
+fn import_binding(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Identifier>, ParseError> {
+    let context = Context {
+        strict: true,
+        module: true,
+        ..Context::default()
+    };
+    return binding_identifier(tokens, cursor, &context);
+}
+
+/*
+* <ImportSpecifier> ::= <Identifier> | <ModuleExportName> 'as' <Identifier>
+* 省略 as 时导入的名称同时是本地绑定，不能是字符串或保留字
+*/
+fn import_specifier(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<ImportSpecifier>, ParseError> {
+    let imported_result = module_export_name(tokens, cursor)?;
+    let imported = imported_result.ast;
+    let (local, next_cursor) = if is_contextual(tokens, imported_result.next_cursor, "as") {
+        let local_result = import_binding(tokens, imported_result.next_cursor + 1)?;
+        (local_result.ast, local_result.next_cursor)
+    } else {
+        match &imported {
+            ModuleExportName::Identifier(_) if is_label(tokens, cursor, "name") => {
+                let local_result = import_binding(tokens, cursor)?;
+                (local_result.ast, local_result.next_cursor)
+            }
+            _ => {
+                return Err(ParseError::unexpected(
+                    tokens,
+                    imported_result.next_cursor,
+                    "'as'",
+                ))
+            }
+        }
+    };
+    let start_token = &tokens[cursor];
+    let ast = ImportSpecifier {
+        _type: ASTType::ImportSpecifier,
+        start: start_token.start,
+        end: local.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: local.loc.end,
+        },
+        imported,
+        local,
+    };
+    return Ok(GramAnalysisResult { ast, next_cursor });
+}
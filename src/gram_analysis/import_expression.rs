@@ -0,0 +1,112 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, Context, Expression, GramAnalysisResult, ImportExpression, MetaProperty,
+        SourceLocation,
+    },
+};
+
+use super::{
+    assignment_expression::assignment_expression,
+    identifier::{identifier_name, is_contextual},
+};
+
+fn is_label(tokens: &[Token], cursor: usize, label: &str) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == label);
+}
+
+/*
+* <ImportCall> ::= 'import' '(' <AssignmentExpression> (',' <AssignmentExpression>)? ','? ')'
+* <ImportMeta> ::= 'import' '.' 'meta'
+*/
+pub fn import_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    if is_label(tokens, cursor + 1, ".") {
+        return import_meta(tokens, cursor, context);
+    }
+    if !is_label(tokens, cursor + 1, "(") {
+        return Err(ParseError::unexpected(tokens, cursor + 1, "'(' or '.'"));
+    }
+    let source_result = assignment_expression(tokens, cursor + 2, false, context)?;
+    let mut next_cursor = source_result.next_cursor;
+    let mut options = None;
+    if is_label(tokens, next_cursor, ",") {
+        next_cursor += 1;
+        // 第二个参数之后，或者只有一个参数时，都允许末尾的逗号
+        if !is_label(tokens, next_cursor, ")") {
+            let options_result = assignment_expression(tokens, next_cursor, false, context)?;
+            options = Some(options_result.ast);
+            next_cursor = options_result.next_cursor;
+            if is_label(tokens, next_cursor, ",") {
+                next_cursor += 1;
+            }
+        }
+    }
+    if !is_label(tokens, next_cursor, ")") {
+        return Err(ParseError::unexpected(tokens, next_cursor, "')'"));
+    }
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor];
+    let ast = ImportExpression {
+        _type: ASTType::ImportExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        source: source_result.ast,
+        options,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::ImportExpression(Some(Box::new(ast))),
+        next_cursor: next_cursor + 1,
+    });
+}
+
+// import.meta 只能出现在模块中
+fn import_meta(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let start_token = &tokens[cursor];
+    let property_cursor = cursor + 2;
+    if !is_contextual(tokens, property_cursor, "meta") {
+        let token = tokens.get(property_cursor).unwrap_or(start_token);
+        return Err(ParseError::syntax(
+            "The only valid meta property for import is 'import.meta'",
+            token.start,
+            token.loc.start,
+        ));
+    }
+    if !context.module {
+        return Err(ParseError::syntax(
+            "Cannot use 'import.meta' outside a module",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+    let meta_result = identifier_name(tokens, cursor)?;
+    let property_result = identifier_name(tokens, property_cursor)?;
+    let property = property_result.ast;
+    let ast = MetaProperty {
+        _type: ASTType::MetaProperty,
+        start: start_token.start,
+        end: property.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: property.loc.end,
+        },
+        meta: meta_result.ast,
+        property,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::MetaProperty(Some(Box::new(ast))),
+        next_cursor: property_result.next_cursor,
+    });
+}
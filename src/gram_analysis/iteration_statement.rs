@@ -0,0 +1,396 @@
+use crate::{
+    error::ParseError,
+    tokenizer::{MyOption, Token},
+    types::{
+        ASTType, Body, Context, DoWhileStatement, Expression, ForInOfLeft, ForInStatement,
+        ForOfStatement, ForStatement, ForStatementInit, GramAnalysisResult, SourceLocation,
+        WhileStatement,
+    },
+};
+
+use super::{
+    assignment_expression::{
+        assignment_expression, assignment_target, check_cover_initialized_name,
+        check_strict_assignment, cover_assignment_expression,
+    },
+    expression::{expression, parenthesized_expression, sequence_expression},
+    identifier::is_contextual,
+    statement::{substatement, SubstatementPosition},
+    variable_statement::{
+        check_declaration_initializers, is_let_declaration, variable_declarations,
+    },
+};
+
+fn is_label(tokens: &[Token], cursor: usize, label: &str) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == label);
+}
+
+// 循环体中可以使用不带标签的 break 和 continue
+fn loop_body(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let mut body_context = context.clone();
+    body_context.in_iteration = true;
+    return substatement(tokens, cursor, SubstatementPosition::Body, &body_context);
+}
+
+// 以 `;` 或 `)` 结尾的可选表达式，用于 for 语句头部的 test 和 update
+fn optional_expression(
+    tokens: &[Token],
+    cursor: usize,
+    end_label: &str,
+    context: &Context,
+) -> Result<GramAnalysisResult<Option<Expression>>, ParseError> {
+    let mut next_cursor = cursor;
+    let mut ast = None;
+    if !is_label(tokens, cursor, end_label) {
+        let expression_result = expression(tokens, cursor, false, context)?;
+        ast = Some(expression_result.ast);
+        next_cursor = expression_result.next_cursor;
+    }
+    if !is_label(tokens, next_cursor, end_label) {
+        return Err(ParseError::unexpected(
+            tokens,
+            next_cursor,
+            &format!("'{}'", end_label),
+        ));
+    }
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: next_cursor + 1,
+    });
+}
+
+/*
+* <IterationStatement> ::= 'do' <Statement> 'while' '(' <Expression> ')' ';'
+*                        | 'while' '(' <Expression> ')' <Statement>
+*                        | 'for' '(' <ForInit>? ';' <Expression>? ';' <Expression>? ')' <Statement>
+*                        | 'for' '(' <ForInOfLeft> 'in' <Expression> ')' <Statement>
+*                        | 'for' 'await'? '(' <ForInOfLeft> 'of' <AssignmentExpression> ')' <Statement>
+*/
+pub fn iteration_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let start_token = &tokens[cursor];
+    match &start_token._type.keyword {
+        MyOption(Some(keyword)) if keyword == "while" => {
+            return while_statement(tokens, cursor, context)
+        }
+        MyOption(Some(keyword)) if keyword == "do" => {
+            return do_while_statement(tokens, cursor, context)
+        }
+        MyOption(Some(keyword)) if keyword == "for" => {
+            return for_statement(tokens, cursor, context)
+        }
+        _ => {
+            return Err(ParseError::unexpected(
+                tokens,
+                cursor,
+                "'for', 'while' or 'do'",
+            ))
+        }
+    }
+}
+
+fn while_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let test_result = parenthesized_expression(tokens, cursor + 1, context)?;
+    let body_result = loop_body(tokens, test_result.next_cursor, context)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[body_result.next_cursor - 1];
+    let ast = WhileStatement {
+        _type: ASTType::WhileStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        test: test_result.ast,
+        body: body_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::WhileStatement(Some(Box::new(ast))),
+        next_cursor: body_result.next_cursor,
+    });
+}
+
+fn do_while_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let body_result = loop_body(tokens, cursor + 1, context)?;
+    if !is_label(tokens, body_result.next_cursor, "while") {
+        return Err(ParseError::unexpected(
+            tokens,
+            body_result.next_cursor,
+            "'while'",
+        ));
+    }
+    let test_result = parenthesized_expression(tokens, body_result.next_cursor + 1, context)?;
+    // do-while 之后的分号即使在同一行也可以省略
+    let next_cursor = if is_label(tokens, test_result.next_cursor, ";") {
+        test_result.next_cursor + 1
+    } else {
+        test_result.next_cursor
+    };
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = DoWhileStatement {
+        _type: ASTType::DoWhileStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        body: body_result.ast,
+        test: test_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::DoWhileStatement(Some(Box::new(ast))),
+        next_cursor,
+    });
+}
+
+fn for_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let start_token = &tokens[cursor];
+    let mut next_cursor = cursor + 1;
+    // for await 只能出现在异步函数和模块的顶层
+    let _await = context.in_async && is_contextual(tokens, next_cursor, "await");
+    if _await {
+        next_cursor += 1;
+    }
+    if !is_label(tokens, next_cursor, "(") {
+        return Err(ParseError::unexpected(tokens, next_cursor, "'('"));
+    }
+    next_cursor += 1;
+    let init_cursor = next_cursor;
+
+    // 头部第一部分中的 `in` 不能当作运算符，否则无法区分 for-in
+    let mut init = None;
+    if !is_label(tokens, next_cursor, ";") {
+        let is_declaration = is_let_declaration(tokens, next_cursor)
+            || matches!(
+                &tokens.get(next_cursor).map(|token| &token._type.keyword),
+                Some(MyOption(Some(keyword))) if keyword == "var" || keyword == "const"
+            );
+        if is_declaration {
+            let declaration_result = variable_declarations(tokens, next_cursor, true, context)?;
+            init = Some(ForStatementInit::VariableDeclaration(
+                declaration_result.ast,
+            ));
+            next_cursor = declaration_result.next_cursor;
+        } else {
+            // for ({ a = 1 } of b) 中的左侧在确定是 for-of 之后才转换为解构模式
+            let mut expression_result =
+                cover_assignment_expression(tokens, next_cursor, true, context)?;
+            // for (i = 0, l = a.length; ...) 中的逗号表达式不可能是 for-in/of 的左侧
+            if is_label(tokens, expression_result.next_cursor, ",") {
+                check_cover_initialized_name(&expression_result.ast)?;
+                expression_result =
+                    sequence_expression(tokens, next_cursor, expression_result, true, context)?;
+            }
+            init = Some(ForStatementInit::Expression(expression_result.ast));
+            next_cursor = expression_result.next_cursor;
+        }
+    }
+
+    let is_for_in = is_label(tokens, next_cursor, "in");
+    let is_for_of = is_contextual(tokens, next_cursor, "of");
+    if is_for_in || is_for_of {
+        let left = match init {
+            Some(ForStatementInit::VariableDeclaration(declaration)) => {
+                let head = if is_for_in { "for-in" } else { "for-of" };
+                if declaration.declarations.len() != 1 {
+                    return Err(ParseError::syntax(
+                        format!("Must have a single binding in {} loop", head),
+                        declaration.start,
+                        declaration.loc.start,
+                    ));
+                }
+                if declaration.declarations[0].init.is_some() {
+                    return Err(ParseError::syntax(
+                        format!(
+                            "{} loop variable declaration may not have an initializer",
+                            head
+                        ),
+                        declaration.start,
+                        declaration.loc.start,
+                    ));
+                }
+                ForInOfLeft::VariableDeclaration(declaration)
+            }
+            Some(ForStatementInit::Expression(expression)) => {
+                // for (async of x) 与 for (async of => {};;) 有歧义，与 let 一样被排除，for await 中则可以
+                if is_for_of
+                    && !_await
+                    && next_cursor == init_cursor + 1
+                    && is_contextual(tokens, init_cursor, "async")
+                {
+                    let token = &tokens[init_cursor];
+                    return Err(ParseError::syntax(
+                        "The left-hand side of a for-of loop may not be 'async'",
+                        token.start,
+                        token.loc.start,
+                    ));
+                }
+                let pattern = assignment_target(tokens, init_cursor, expression, true)?;
+                check_strict_assignment(&pattern, context)?;
+                ForInOfLeft::Pattern(pattern)
+            }
+            None => return Err(ParseError::unexpected(tokens, init_cursor, "expression")),
+        };
+        if _await && is_for_in {
+            return Err(ParseError::unexpected(tokens, next_cursor, "'of'"));
+        }
+        // for-of 的右侧是 AssignmentExpression，不能是逗号表达式
+        let right_result = if is_for_of {
+            assignment_expression(tokens, next_cursor + 1, false, context)?
+        } else {
+            expression(tokens, next_cursor + 1, false, context)?
+        };
+        if !is_label(tokens, right_result.next_cursor, ")") {
+            return Err(ParseError::unexpected(
+                tokens,
+                right_result.next_cursor,
+                "')'",
+            ));
+        }
+        let body_result = loop_body(tokens, right_result.next_cursor + 1, context)?;
+        let end_token = &tokens[body_result.next_cursor - 1];
+        let loc = SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        };
+        let ast = if is_for_of {
+            Body::ForOfStatement(Some(Box::new(ForOfStatement {
+                _type: ASTType::ForOfStatement,
+                start: start_token.start,
+                end: end_token.end,
+                loc,
+                _await,
+                left,
+                right: right_result.ast,
+                body: body_result.ast,
+            })))
+        } else {
+            Body::ForInStatement(Some(Box::new(ForInStatement {
+                _type: ASTType::ForInStatement,
+                start: start_token.start,
+                end: end_token.end,
+                loc,
+                left,
+                right: right_result.ast,
+                body: body_result.ast,
+            })))
+        };
+        return Ok(GramAnalysisResult {
+            ast,
+            next_cursor: body_result.next_cursor,
+        });
+    }
+
+    if _await {
+        return Err(ParseError::unexpected(tokens, next_cursor, "'of'"));
+    }
+    match &init {
+        Some(ForStatementInit::VariableDeclaration(declaration)) => {
+            check_declaration_initializers(declaration)?
+        }
+        Some(ForStatementInit::Expression(expression)) => check_cover_initialized_name(expression)?,
+        None => {}
+    }
+    if !is_label(tokens, next_cursor, ";") {
+        return Err(ParseError::unexpected(tokens, next_cursor, "';'"));
+    }
+    let test_result = optional_expression(tokens, next_cursor + 1, ";", context)?;
+    let update_result = optional_expression(tokens, test_result.next_cursor, ")", context)?;
+    let body_result = loop_body(tokens, update_result.next_cursor, context)?;
+    let end_token = &tokens[body_result.next_cursor - 1];
+    let ast = ForStatement {
+        _type: ASTType::ForStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        init,
+        test: test_result.ast,
+        update: update_result.ast,
+        body: body_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::ForStatement(Some(Box::new(ast))),
+        next_cursor: body_result.next_cursor,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn for_statement_heads() {
+        assert_json(
+            script,
+            &[
+                (
+                    "for (i = 0, l = a.length; i < l; i++);",
+                    r#""init":{"type":"SequenceExpression""#,
+                ),
+                (
+                    "for (;; a++, b++);",
+                    r#""update":{"type":"SequenceExpression""#,
+                ),
+                ("for (; a, b;);", r#""test":{"type":"SequenceExpression""#),
+                (
+                    "for (var i = 0, j = 1;;);",
+                    r#""init":{"type":"VariableDeclaration""#,
+                ),
+                (
+                    "for (x in a, b);",
+                    r#""right":{"type":"SequenceExpression""#,
+                ),
+                ("for (x of a);", r#""type":"ForOfStatement""#),
+                ("for (async of => {};;);", r#""type":"ForStatement""#),
+                ("for (\\u0061sync of x);", r#""name":"async""#),
+                ("for ((async) of x);", r#""name":"async""#),
+                (
+                    "async function f() { for await (async of x); }",
+                    r#""await":true"#,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn for_statement_head_errors() {
+        assert_errors(
+            script,
+            &[
+                // for-of 的右侧是 AssignmentExpression，不允许逗号
+                ("for (x of a, b);", "expected ')', found ','"),
+                ("for (a, b in c);", "Assigning to rvalue"),
+                ("for (var a, b of c);", "Must have a single binding"),
+                (
+                    "for (async of x);",
+                    "The left-hand side of a for-of loop may not be 'async'",
+                ),
+            ],
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, Context, GramAnalysisResult, Label, LabeledStatement, SourceLocation},
+};
+
+use super::{
+    identifier::identifier_reference,
+    statement::{substatement, SubstatementPosition},
+};
+
+// 跳过连续的标签，如 a: b: for (...)，判断最终标记的是否为循环
+fn labels_loop(tokens: &[Token], cursor: usize) -> bool {
+    let mut next_cursor = cursor;
+    while let (Some(token), Some(next_token)) =
+        (tokens.get(next_cursor), tokens.get(next_cursor + 1))
+    {
+        if token._type.label == "name" && next_token._type.label == ":" {
+            next_cursor += 2;
+        } else {
+            break;
+        }
+    }
+    return matches!(
+        tokens.get(next_cursor),
+        Some(token) if token._type.label == "for" || token._type.label == "while" || token._type.label == "do"
+    );
+}
+
+/*
+* <LabelledStatement> ::= <Identifier> ':' <Statement>
+*/
+pub fn labelled_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let identifier_result = identifier_reference(tokens, cursor, context)?;
+    let label = identifier_result.ast;
+    if context.labels.iter().any(|item| item.name == label.name) {
+        return Err(ParseError::syntax(
+            format!("Label '{}' is already declared", label.name),
+            label.start,
+            label.loc.start,
+        ));
+    }
+    let body_cursor = identifier_result.next_cursor + 1;
+    let mut body_context = context.clone();
+    body_context.labels.push(Label {
+        name: label.name.clone(),
+        is_loop: labels_loop(tokens, body_cursor),
+    });
+    let body_result = substatement(
+        tokens,
+        body_cursor,
+        SubstatementPosition::Label,
+        &body_context,
+    )?;
+    let end_token = &tokens[body_result.next_cursor - 1];
+    let ast = LabeledStatement {
+        _type: ASTType::LabeledStatement,
+        start: label.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: label.loc.start,
+            end: end_token.loc.end,
+        },
+        label,
+        body: body_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::LabeledStatement(Some(Box::new(ast))),
+        next_cursor: body_result.next_cursor,
+    });
+}
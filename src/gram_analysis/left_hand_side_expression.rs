@@ -0,0 +1,403 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, CallExpression, ChainExpression, Context, Expression, GramAnalysisResult,
+        MemberExpression, MetaProperty, NewExpression, SourceLocation, TaggedTemplateExpression,
+    },
+};
+
+use super::{
+    arguments::arguments,
+    assignment_expression::check_cover_initialized_name,
+    expression::expression,
+    identifier::{identifier_name, is_contextual, private_name_reference},
+    primary_expression::primary_expression,
+    statement::nested_context,
+    template_literal::template_literal,
+};
+
+fn is_label(tokens: &[Token], cursor: usize, label: &str) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == label);
+}
+
+/*
+* <LeftHandSideExpression> ::= <NewExpression> | <CallExpression> | <OptionalExpression>
+* <MemberExpression> ::= <PrimaryExpression>
+*                      | <MemberExpression> '[' <Expression> ']'
+*                      | <MemberExpression> '.' (<IdentifierName> | <PrivateIdentifier>)
+*                      | 'new' <MemberExpression> <Arguments>
+*                      | 'new' '.' 'target'
+* <NewExpression> ::= <MemberExpression> | 'new' <NewExpression>
+* <CallExpression> ::= <MemberExpression> <Arguments> | <CallExpression> (<Arguments> | '[' <Expression> ']' | '.' <IdentifierName>)
+* <OptionalChain> ::= '?.' (<Arguments> | '[' <Expression> ']' | <IdentifierName>) ...
+* <MemberExpression> 和 <CallExpression> 之后都可以跟 <TemplateLiteral>，即标签模板
+*/
+pub fn left_hand_side_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let base_result = if is_label(tokens, cursor, "new") {
+        new_expression(tokens, cursor, context)?
+    } else {
+        primary_expression(tokens, cursor, context)?
+    };
+    return subscripts(tokens, cursor, base_result, false, context);
+}
+
+// `new` 的 callee 中不能出现调用，`new a.b()` 的参数属于 new 本身
+fn new_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    if is_label(tokens, cursor + 1, ".") {
+        return new_target(tokens, cursor, context);
+    }
+    if is_label(tokens, cursor + 1, "import") && is_label(tokens, cursor + 2, "(") {
+        let token = &tokens[cursor + 1];
+        return Err(ParseError::syntax(
+            "Cannot use new with import()",
+            token.start,
+            token.loc.start,
+        ));
+    }
+    let callee_base_result = if is_label(tokens, cursor + 1, "new") {
+        let context = &nested_context(tokens, cursor + 1, context)?;
+        new_expression(tokens, cursor + 1, context)?
+    } else {
+        primary_expression(tokens, cursor + 1, context)?
+    };
+    let callee_result = subscripts(tokens, cursor + 1, callee_base_result, true, context)?;
+    check_cover_initialized_name(&callee_result.ast)?;
+    let (argument_list, next_cursor) = if is_label(tokens, callee_result.next_cursor, "(") {
+        let arguments_result = arguments(tokens, callee_result.next_cursor, context)?;
+        (arguments_result.ast, arguments_result.next_cursor)
+    } else {
+        (Vec::new(), callee_result.next_cursor)
+    };
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = NewExpression {
+        _type: ASTType::NewExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        callee: callee_result.ast,
+        arguments: argument_list,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::NewExpression(Some(Box::new(ast))),
+        next_cursor,
+    });
+}
+
+// new.target 只能出现在非箭头函数、类字段初始值和静态块中，箭头函数继承外层
+fn new_target(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let start_token = &tokens[cursor];
+    let property_cursor = cursor + 2;
+    if !is_contextual(tokens, property_cursor, "target") {
+        let token = tokens.get(property_cursor).unwrap_or(start_token);
+        return Err(ParseError::syntax(
+            "The only valid meta property for new is 'new.target'",
+            token.start,
+            token.loc.start,
+        ));
+    }
+    if !context.allow_new_target {
+        return Err(ParseError::syntax(
+            "'new.target' can only be used in functions and class static block",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+    let meta_result = identifier_name(tokens, cursor)?;
+    let property_result = identifier_name(tokens, property_cursor)?;
+    let property = property_result.ast;
+    let ast = MetaProperty {
+        _type: ASTType::MetaProperty,
+        start: start_token.start,
+        end: property.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: property.loc.end,
+        },
+        meta: meta_result.ast,
+        property,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::MetaProperty(Some(Box::new(ast))),
+        next_cursor: property_result.next_cursor,
+    });
+}
+
+// 解析 base 之后的 `.`、`?.`、`[]` 和调用，`no_call` 为 true 时用于 new 的 callee
+fn subscripts(
+    tokens: &[Token],
+    cursor: usize,
+    base_result: GramAnalysisResult<Expression>,
+    no_call: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let mut result = base_result;
+    if matches!(tokens.get(result.next_cursor), Some(token) if matches!(token._type.label.as_str(), "." | "?." | "[" | "(" | "`"))
+    {
+        check_cover_initialized_name(&result.ast)?;
+    }
+    let mut under_optional_chain = false;
+    while let Some(token) = tokens.get(result.next_cursor) {
+        // 标签模板，new 的 callee 中也可以出现
+        if token._type.label == "`" {
+            if under_optional_chain {
+                return Err(ParseError::syntax(
+                    "Optional chaining cannot appear in the tag of tagged template expressions",
+                    token.start,
+                    token.loc.start,
+                ));
+            }
+            result = tagged_template(tokens, cursor, result, context)?;
+            continue;
+        }
+        let optional = token._type.label == "?.";
+        if optional {
+            if no_call {
+                return Err(ParseError::syntax(
+                    "Optional chaining cannot appear in the callee of new expressions",
+                    token.start,
+                    token.loc.start,
+                ));
+            }
+            under_optional_chain = true;
+        }
+        // `?.[` 和 `?.(` 中真正的起始符号在 `?.` 之后
+        let bracket_cursor = if optional {
+            result.next_cursor + 1
+        } else {
+            result.next_cursor
+        };
+        if token._type.label == "."
+            || (optional
+                && !is_label(tokens, bracket_cursor, "[")
+                && !is_label(tokens, bracket_cursor, "("))
+        {
+            let property_cursor = result.next_cursor + 1;
+            result = member_expression(
+                tokens,
+                cursor,
+                result,
+                property_cursor,
+                false,
+                optional,
+                context,
+            )?;
+        } else if is_label(tokens, bracket_cursor, "[") {
+            result = member_expression(
+                tokens,
+                cursor,
+                result,
+                bracket_cursor + 1,
+                true,
+                optional,
+                context,
+            )?;
+        } else if !no_call && is_label(tokens, bracket_cursor, "(") {
+            result = call_expression(tokens, cursor, result, bracket_cursor, optional, context)?;
+        } else {
+            break;
+        }
+    }
+    if under_optional_chain {
+        let start_token = &tokens[cursor];
+        let end_token = &tokens[result.next_cursor - 1];
+        result.ast = Expression::ChainExpression(Some(Box::new(ChainExpression {
+            _type: ASTType::ChainExpression,
+            start: start_token.start,
+            end: end_token.end,
+            loc: SourceLocation {
+                start: start_token.loc.start,
+                end: end_token.loc.end,
+            },
+            expression: result.ast,
+        })));
+    }
+    return Ok(result);
+}
+
+// 以下几个函数在已经解析出的 object、callee 或 tag 之后解析一个后缀，节点都从 cursor 开始
+
+// `.x`、`.#x` 或 `[x]`，property_cursor 指向属性名或 `[` 之后的表达式
+fn member_expression(
+    tokens: &[Token],
+    cursor: usize,
+    object_result: GramAnalysisResult<Expression>,
+    property_cursor: usize,
+    computed: bool,
+    optional: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let (property, next_cursor) = if computed {
+        let property_context = nested_context(tokens, property_cursor, context)?;
+        let property_result = expression(tokens, property_cursor, false, &property_context)?;
+        if !is_label(tokens, property_result.next_cursor, "]") {
+            return Err(ParseError::unexpected(
+                tokens,
+                property_result.next_cursor,
+                "']'",
+            ));
+        }
+        (property_result.ast, property_result.next_cursor + 1)
+    } else if is_label(tokens, property_cursor, "privateId") {
+        let property_result = private_name_reference(tokens, property_cursor, context)?;
+        (
+            Expression::PrivateIdentifier(property_result.ast),
+            property_result.next_cursor,
+        )
+    } else {
+        let property_result = identifier_name(tokens, property_cursor)?;
+        (
+            Expression::Identifier(property_result.ast),
+            property_result.next_cursor,
+        )
+    };
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = MemberExpression {
+        _type: ASTType::MemberExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        object: object_result.ast,
+        property,
+        computed,
+        optional,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::MemberExpression(Some(Box::new(ast))),
+        next_cursor,
+    });
+}
+
+// 调用，arguments_cursor 指向 `(`
+fn call_expression(
+    tokens: &[Token],
+    cursor: usize,
+    callee_result: GramAnalysisResult<Expression>,
+    arguments_cursor: usize,
+    optional: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let arguments_result = arguments(tokens, arguments_cursor, context)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[arguments_result.next_cursor - 1];
+    let ast = CallExpression {
+        _type: ASTType::CallExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        callee: callee_result.ast,
+        arguments: arguments_result.ast,
+        optional,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::CallExpression(Some(Box::new(ast))),
+        next_cursor: arguments_result.next_cursor,
+    });
+}
+
+// 标签模板，模板从 tag 之后的 '`' 开始
+fn tagged_template(
+    tokens: &[Token],
+    cursor: usize,
+    tag_result: GramAnalysisResult<Expression>,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let quasi_result = template_literal(tokens, tag_result.next_cursor, true, context)?;
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[quasi_result.next_cursor - 1];
+    let ast = TaggedTemplateExpression {
+        _type: ASTType::TaggedTemplateExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        tag: tag_result.ast,
+        quasi: quasi_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::TaggedTemplateExpression(Some(Box::new(ast))),
+        next_cursor: quasi_result.next_cursor,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn new_target() {
+        assert_json(
+            script,
+            &[
+                (
+                    "function f() { return new.target }",
+                    r#""argument":{"type":"MetaProperty","start":22,"end":32,"meta":{"type":"Identifier","start":22,"end":25,"name":"new"},"property":{"type":"Identifier","start":26,"end":32,"name":"target"}}"#,
+                ),
+                (
+                    "function f() { new new.target() }",
+                    r#""type":"NewExpression","start":15,"end":31,"callee":{"type":"MetaProperty""#,
+                ),
+                (
+                    "function f() { return () => new.target.name }",
+                    r#""object":{"type":"MetaProperty""#,
+                ),
+                (
+                    "o = { m() { return new.target } }",
+                    r#""type":"MetaProperty""#,
+                ),
+                (
+                    "class A { a = new.target }",
+                    r#""value":{"type":"MetaProperty""#,
+                ),
+                (
+                    "class A { static { new.target } }",
+                    r#""type":"MetaProperty""#,
+                ),
+                ("var target = 1; target", r#""name":"target""#),
+            ],
+        );
+    }
+
+    #[test]
+    fn new_target_errors() {
+        assert_errors(
+            script,
+            &[
+                ("new.target", "'new.target' can only be used in functions"),
+                (
+                    "() => new.target",
+                    "'new.target' can only be used in functions",
+                ),
+                (
+                    "function f() { new.foo }",
+                    "The only valid meta property for new is 'new.target'",
+                ),
+            ],
+        );
+    }
+}
@@ -1,45 +1,199 @@
 use crate::{
+    error::ParseError,
     tokenizer::{Token, TokenValue},
-    types::{ASTType, Expression, GramAnalysisResult, Literal, LiteralType, Regex},
+    types::{
+        ASTType, Context, Expression, GramAnalysisResult, Literal, LiteralType, LiteralValue,
+        Regex, SourceLocation,
+    },
+    utils::escape::cook_string,
 };
 
+// 以 0 开头的多位整数，只含 0-7 时是旧式八进制，如 010 为 8，08 仍是十进制
+fn legacy_octal_radix(digits: &str) -> Option<u32> {
+    if digits.len() > 1
+        && digits.starts_with('0')
+        && digits.bytes().all(|byte| byte.is_ascii_digit())
+    {
+        if digits.bytes().all(|byte| matches!(byte, b'0'..=b'7')) {
+            return Some(8);
+        }
+        return Some(10);
+    }
+    return None;
+}
+
+// 数字字面量的值，词法分析时已经检查过语法
+fn numeric_value(raw: &str) -> f64 {
+    let digits = raw.replace('_', "");
+    let (radix, digits) = match digits.get(..2) {
+        Some("0x" | "0X") => (16, &digits[2..]),
+        Some("0o" | "0O") => (8, &digits[2..]),
+        Some("0b" | "0B") => (2, &digits[2..]),
+        _ => match legacy_octal_radix(&digits) {
+            Some(8) => (8, &digits[1..]),
+            _ => return digits.parse::<f64>().unwrap_or(f64::NAN),
+        },
+    };
+    if let Ok(value) = u128::from_str_radix(digits, radix) {
+        return value as f64;
+    }
+    // 超出 u128 的整数按位累加
+    return digits.chars().fold(0.0, |value, char| {
+        value * radix as f64 + char.to_digit(radix).unwrap_or(0) as f64
+    });
+}
+
 pub fn literal_expression(
     tokens: &[Token],
     cursor: usize,
     literal_type: LiteralType,
-) -> Result<GramAnalysisResult<Expression>, &str> {
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
     let token = tokens.get(cursor).unwrap();
     if let LiteralType::REGEXP = literal_type {
         if let TokenValue::RegexpValue(token_value) = &token.value {
-            let ast = Expression::Literal(Literal {
+            let ast = Expression::Literal(Some(Box::new(Literal {
                 _type: ASTType::Literal,
                 start: token.start,
                 end: token.end,
-                value: literal_type,
-                raw: &token_value.value.as_str(),
+                loc: SourceLocation {
+                    start: token.loc.start,
+                    end: token.loc.end,
+                },
+                value: LiteralValue::RegExp,
+                raw: token_value.value.clone(),
                 regex: Some(Regex {
-                    pattern: &token_value.pattern.as_str(),
-                    flags: &token_value.flags.as_str(),
+                    pattern: token_value.pattern.clone(),
+                    flags: token_value.flags.clone(),
                 }),
-            });
+                bigint: None,
+            })));
             return Ok(GramAnalysisResult {
                 ast,
                 next_cursor: cursor + 1,
             });
         }
     } else if let TokenValue::String(token_value) = &token.value {
-        let ast = Expression::Literal(Literal {
+        let mut bigint = None;
+        let value = match literal_type {
+            LiteralType::STRING => {
+                let cooked = cook_string(&token_value[1..token_value.len() - 1]).map_err(
+                    |(message, _)| ParseError::syntax(message, token.start, token.loc.start),
+                )?;
+                // 严格模式下不能使用 \01 这样的八进制转义以及 \8、\9
+                if let Some((_, message)) = cooked.octal {
+                    if context.strict {
+                        return Err(ParseError::syntax(message, token.start, token.loc.start));
+                    }
+                }
+                LiteralValue::String(cooked.value)
+            }
+            LiteralType::NUMBER => {
+                // 严格模式下不能使用 010 和 08 这样以 0 开头的整数
+                match legacy_octal_radix(token_value.split(['.', 'e', 'E']).next().unwrap()) {
+                    Some(radix) if context.strict => {
+                        let message = if radix == 8 {
+                            "Octal literals are not allowed in strict mode"
+                        } else {
+                            "Decimals with leading zeros are not allowed in strict mode"
+                        };
+                        return Err(ParseError::syntax(message, token.start, token.loc.start));
+                    }
+                    _ => {}
+                }
+                if let Some(digits) = token_value.strip_suffix('n') {
+                    bigint = Some(digits.replace('_', ""));
+                    LiteralValue::BigInt
+                } else {
+                    LiteralValue::Number(numeric_value(token_value))
+                }
+            }
+            LiteralType::BOOLEAN => LiteralValue::Boolean(token_value == "true"),
+            LiteralType::NULL => LiteralValue::Null,
+            LiteralType::REGEXP => LiteralValue::RegExp,
+        };
+        let ast = Expression::Literal(Some(Box::new(Literal {
             _type: ASTType::Literal,
             start: token.start,
             end: token.end,
-            value: literal_type,
-            raw: &token_value.as_str(),
+            loc: SourceLocation {
+                start: token.loc.start,
+                end: token.loc.end,
+            },
+            value,
+            raw: token_value.clone(),
             regex: None,
-        });
+            bigint,
+        })));
         return Ok(GramAnalysisResult {
             ast,
             next_cursor: cursor + 1,
         });
     }
-    return Err("");
+    return Err(ParseError::unexpected(tokens, cursor, "literal"));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn numeric_literals() {
+        assert_json(
+            script,
+            &[
+                ("0xFF", r#""value":255,"raw":"0xFF""#),
+                ("0o17", r#""value":15,"raw":"0o17""#),
+                ("0b1010", r#""value":10,"raw":"0b1010""#),
+                ("1e10", r#""value":10000000000,"raw":"1e10""#),
+                ("1.5e-3", r#""value":0.0015,"raw":"1.5e-3""#),
+                (".5", r#""value":0.5,"raw":".5""#),
+                ("5.", r#""value":5,"raw":"5.""#),
+                ("1_000_000", r#""value":1000000,"raw":"1_000_000""#),
+                ("0x1_F", r#""value":31,"raw":"0x1_F""#),
+                ("10n", r#""value":null,"raw":"10n","bigint":"10""#),
+                ("0x1Fn", r#""value":null,"raw":"0x1Fn","bigint":"0x1F""#),
+                ("1_0n", r#""bigint":"10""#),
+                ("1e400", r#""value":null,"raw":"1e400""#),
+                // 非严格模式中的旧式八进制，以及含有 8、9 时按十进制处理
+                ("017", r#""value":15,"raw":"017""#),
+                ("08", r#""value":8,"raw":"08""#),
+                ("019.5", r#""value":19.5,"raw":"019.5""#),
+                ("1..toString()", r#""value":1,"raw":"1.""#),
+                ("1n.toString()", r#""bigint":"1""#),
+            ],
+        );
+    }
+
+    #[test]
+    fn numeric_literal_errors() {
+        assert_errors(
+            script,
+            &[
+                (
+                    "1__0",
+                    "Only one underscore is allowed as numeric separator",
+                ),
+                (
+                    "1_",
+                    "Numeric separators are not allowed at the end of numeric literals",
+                ),
+                ("0_1", "Numeric separator can not be used after leading 0"),
+                ("1e", "Invalid number"),
+                ("0x", "Expected number in radix 16"),
+                ("0b2", "Expected number in radix 2"),
+                ("3in x", "Identifier directly after number"),
+                ("1.5n", "Identifier directly after number"),
+                ("017n", "Identifier directly after number"),
+                (
+                    "'use strict'; 017",
+                    "Octal literals are not allowed in strict mode",
+                ),
+                (
+                    "'use strict'; 08",
+                    "Decimals with leading zeros are not allowed in strict mode",
+                ),
+            ],
+        );
+    }
 }
@@ -1,3 +0,0 @@
-use crate::tokenizer::Token;
-
-pub fn logical_and_expression(tokens: &[Token], cursor: usize) {}
\ No newline at end of file
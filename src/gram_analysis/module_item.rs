@@ -0,0 +1,30 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{Body, Context, GramAnalysisResult},
+};
+
+use super::{
+    export_declaration::export_declaration,
+    import_declaration::{import_declaration, is_import_declaration},
+    statement::statement,
+};
+
+/*
+* <ModuleItem> ::= <ImportDeclaration> | <ExportDeclaration> | <StatementListItem>
+* import 和 export 声明只能出现在模块的顶层，脚本和其他位置由 statement 报错
+*/
+pub fn module_item(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    if context.module {
+        if is_import_declaration(tokens, cursor) {
+            return import_declaration(tokens, cursor);
+        } else if matches!(tokens.get(cursor), Some(token) if token._type.label == "export") {
+            return export_declaration(tokens, cursor, context);
+        }
+    }
+    return statement(tokens, cursor, context);
+}
@@ -1,18 +1,163 @@
 use crate::{
+    error::ParseError,
     tokenizer::Token,
-    types::{ASTType, ArrayExpression, Expression, GramAnalysisResult},
+    types::{
+        ASTType, Context, Expression, GramAnalysisResult, Kind, LiteralValue, ObjectExpression,
+        ObjectExpressionProperty, Property, PropertyKey, SourceLocation,
+    },
 };
 
-use super::object_property::object_property;
+use super::{object_property::object_property, statement::nested_context};
 
+// 普通的 `__proto__: value` 属性，简写、方法和计算属性名不算
+fn is_proto_property(property: &Property) -> bool {
+    if property.computed
+        || property.shorthand
+        || property.method
+        || !matches!(property.kind, Kind::Init)
+    {
+        return false;
+    }
+    match &property.key {
+        PropertyKey::Identifier(identifier) => return identifier.name == "__proto__",
+        PropertyKey::Literal(literal) => {
+            return matches!(&literal.value, LiteralValue::String(value) if value == "__proto__")
+        }
+        PropertyKey::ComputedPropertyKey(_) | PropertyKey::PrivateIdentifier(_) => return false,
+    }
+}
+
+/*
+* <ObjectExpression> ::= '{' '}' | '{' <PropertyDefinition> (',' <PropertyDefinition>)* ','? '}'
+*/
 pub fn object_expression(
     tokens: &[Token],
     cursor: usize,
-) -> Result<GramAnalysisResult<Expression>, &str> {
-    if let Some(token) = tokens.get(cursor) {
-        if token._type.label == "{" {
-            object_property(tokens, cursor + 1);
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let start_token = match tokens.get(cursor) {
+        Some(token) if token._type.label == "{" => token,
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'{'")),
+    };
+    let context = &nested_context(tokens, cursor, context)?;
+    let mut next_cursor = cursor + 1;
+    let mut properties = Vec::new();
+    let mut has_proto = false;
+    loop {
+        // 允许末尾的逗号，如 { a: 1, }
+        if let Some(token) = tokens.get(next_cursor) {
+            if token._type.label == "}" {
+                break;
+            }
+        }
+        let property_result = object_property(tokens, next_cursor, context)?;
+        if let ObjectExpressionProperty::Property(property) = &property_result.ast {
+            if is_proto_property(property) {
+                if has_proto {
+                    return Err(ParseError::syntax(
+                        "Redefinition of __proto__ property",
+                        property.start,
+                        property.loc.start,
+                    ));
+                }
+                has_proto = true;
+            }
+        }
+        properties.push(property_result.ast);
+        match tokens.get(property_result.next_cursor) {
+            Some(token) if token._type.label == "," => {
+                next_cursor = property_result.next_cursor + 1
+            }
+            Some(token) if token._type.label == "}" => {
+                next_cursor = property_result.next_cursor;
+                break;
+            }
+            _ => {
+                return Err(ParseError::unexpected(
+                    tokens,
+                    property_result.next_cursor,
+                    "',' or '}'",
+                ))
+            }
         }
     }
-    return Err("");
+    let end_token = &tokens[next_cursor];
+    let ast = ObjectExpression {
+        _type: ASTType::ObjectExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        properties,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::ObjectExpression(Some(Box::new(ast))),
+        next_cursor: next_cursor + 1,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn object_literals() {
+        assert_json(
+            script,
+            &[
+                (
+                    "({ a })",
+                    r#""method":false,"shorthand":true,"computed":false"#,
+                ),
+                ("({ [a]: 1 })", r#""computed":true"#),
+                ("({ a() {} })", r#""method":true"#),
+                ("({ get a() {}, set a(v) {} })", r#""kind":"set""#),
+                (
+                    "({ async *a() { yield; await 1 } })",
+                    r#""generator":true,"async":true"#,
+                ),
+                (
+                    "({ 1: a, 'b': c })",
+                    r#""key":{"type":"Literal","start":9,"end":12,"value":"b""#,
+                ),
+                ("({ ...a, b })", r#""type":"SpreadElement""#),
+                ("({ get: 1, set: 2, async: 3 })", r#""name":"async""#),
+                // 计算属性名、简写和方法不算重复的 __proto__
+                ("({ __proto__: 1, ['__proto__']: 2 })", r#""computed":true"#),
+                ("({ __proto__: 1, __proto__ })", r#""shorthand":true"#),
+                ("({ __proto__: 1, __proto__() {} })", r#""method":true"#),
+                ("({ a = 1 } = b)", r#""type":"AssignmentPattern""#),
+            ],
+        );
+    }
+
+    #[test]
+    fn object_literal_early_errors() {
+        assert_errors(
+            script,
+            &[
+                (
+                    "({ __proto__: 1, __proto__: 2 })",
+                    "Redefinition of __proto__ property",
+                ),
+                (
+                    "({ __proto__: 1, '__proto__': 2 })",
+                    "Redefinition of __proto__ property",
+                ),
+                (
+                    "({ a = 1 })",
+                    "Shorthand property assignments are valid only in destructuring patterns",
+                ),
+                ("({ get a(x) {} })", "getter should have no params"),
+                ("({ set a() {} })", "setter should have exactly one param"),
+                ("({ set a(...b) {} })", "Setter cannot use rest params"),
+                (
+                    "({ a() { super() } })",
+                    "super() call outside constructor of a subclass",
+                ),
+            ],
+        );
+    }
 }
@@ -1,123 +1,248 @@
 use crate::{
+    error::ParseError,
     tokenizer::Token,
-    types::{ASTType, GramAnalysisResult, Identifier, Kind, Property, PropertyKey},
+    types::{
+        ASTType, AssignmentExpression, Context, Expression, FunctionExpression, GramAnalysisResult,
+        Kind, LiteralType, ObjectExpressionProperty, Pattern, Property, PropertyKey,
+        SourceLocation, SpreadElement,
+    },
 };
 
-use super::expression::expression;
+use super::{
+    assignment_expression::{assignment_expression, cover_assignment_expression},
+    function_expression::method_function,
+    identifier::{identifier_name, identifier_reference, is_contextual},
+    literal_expression::literal_expression,
+};
 
-pub fn object_property<'a>(
+// get/set/async/* 后面紧跟这些符号时，它们本身就是属性名，如 { get: 1 }、{ async() {} }
+fn is_property_name_end(token: Option<&Token>) -> bool {
+    match token {
+        Some(token) => matches!(token._type.label.as_str(), "," | "}" | ":" | "(" | "="),
+        None => true,
+    }
+}
+
+/*
+* <PropertyName> ::= <IdentifierName> | <string> | <number> | '[' <AssignmentExpression> ']'
+*/
+pub fn property_key(
     tokens: &[Token],
     cursor: usize,
-) -> Result<GramAnalysisResult<Property<'a>>, &'a str> {
-    if let Some(token) = tokens.get(cursor) {
-        let mut colon_cursor_index: usize = cursor;
-        let mut key: PropertyKey;
-        let mut kind: Kind = Kind::Init;
-        let mut method = false;
-        let mut function_start_cursor = cursor;
-        if token._type.label == "name" {
-            if let Some(next_token) = tokens.get(cursor + 1) {
-                if next_token._type.label == "(" {
-                    method = true;
-                    function_start_cursor = cursor;
-                } else {
-                    colon_cursor_index = cursor + 1;
-                }
-                key = PropertyKey::Identifier(Identifier {
-                    _type: ASTType::Identifier,
-                    start: token.start,
-                    end: token.end,
-                    name: token.value.to_string(),
-                });
-            } else {
-                return Err("");
-            }
-        } else if token._type.label == "string" {
-            key = PropertyKey::Identifier(Identifier {
-                _type: ASTType::Identifier,
-                start: token.start,
-                end: token.end,
-                name: token.value.to_string(),
-            });
-            colon_cursor_index = cursor + 1;
-        } else if token._type.label == "[" {
-            if let Ok(expression_result) = expression(tokens, cursor + 1) {
-                if let Some(next_token) = tokens.get(expression_result.next_cursor) {
-                    if next_token._type.label != "]" {
-                        return Err("");
-                    } else {
-                        key = PropertyKey::ComputedPropertyKey(expression_result.ast);
-                        colon_cursor_index = expression_result.next_cursor + 1;
-                        if let Some(right_bracket_next_token) =
-                            tokens.get(expression_result.next_cursor + 1)
-                        {
-                            if right_bracket_next_token._type.label == "(" {
-                                method = true;
-                                function_start_cursor = expression_result.next_cursor + 1;
-                            }
-                        }
-                    }
-                } else {
-                    return Err("");
-                }
-            } else {
-                return Err("");
-            }
-        } else if token._type.label == "get" || token._type.label == "set" {
-            if token._type.label == "get" {
-                kind = Kind::Get;
-            } else {
-                kind = Kind::Set;
-            }
-            if let Some(next_token) = tokens.get(cursor + 1) {
-                if next_token._type.label == "name" {
-                    key = PropertyKey::Identifier(Identifier {
-                        _type: ASTType::Identifier,
-                        start: next_token.start,
-                        end: next_token.end,
-                        name: next_token.value.to_string(),
+    context: &Context,
+) -> Result<GramAnalysisResult<PropertyKey>, ParseError> {
+    let literal_type = match tokens.get(cursor) {
+        Some(token) if token._type.label == "[" => {
+            let key_result = assignment_expression(tokens, cursor + 1, false, context)?;
+            match tokens.get(key_result.next_cursor) {
+                Some(token) if token._type.label == "]" => {
+                    return Ok(GramAnalysisResult {
+                        ast: PropertyKey::ComputedPropertyKey(key_result.ast),
+                        next_cursor: key_result.next_cursor + 1,
                     });
-                    if let Some(identifier_next_token) = tokens.get(cursor + 2) {
-                        if identifier_next_token._type.label == "(" {
-                            function_start_cursor = cursor + 1;
-                        } else {
-                            return Err("");
-                        }
-                    }
-                } else {
-                    return Err("");
                 }
-            } else {
-                return Err("");
-            }
-        } else {
-            return Err("");
-        }
-        if let Kind::Init = kind {
-            if !method {
-                if let Some(token) = tokens.get(colon_cursor_index) {
-                    if token._type.label == ":" {
-                        if let Ok(expression_result) = expression(tokens, colon_cursor_index + 1) {
-                            let property_ast = Property {
-                                _type: ASTType::Property,
-                                start: token.start,
-                                end: token.end,
-                                method,
-                                shorthand: false,
-                                computed: false,
-                                key,
-                                value: expression_result.ast,
-                                kind,
-                            };
-                        }
-                    }
+                _ => {
+                    return Err(ParseError::unexpected(
+                        tokens,
+                        key_result.next_cursor,
+                        "']'",
+                    ))
                 }
-            } else {
-                // 是方法函数
             }
-        } else {
-            // 是 get/set 属性
         }
+        Some(token) if token._type.label == "string" => LiteralType::STRING,
+        Some(token) if token._type.label == "number" => LiteralType::NUMBER,
+        _ => {
+            let key_result = identifier_name(tokens, cursor)
+                .map_err(|_| ParseError::unexpected(tokens, cursor, "property name"))?;
+            return Ok(GramAnalysisResult {
+                ast: PropertyKey::Identifier(key_result.ast),
+                next_cursor: key_result.next_cursor,
+            });
+        }
+    };
+    let key_result = literal_expression(tokens, cursor, literal_type, context)?;
+    match key_result.ast {
+        Expression::Literal(Some(literal)) => {
+            return Ok(GramAnalysisResult {
+                ast: PropertyKey::Literal(*literal),
+                next_cursor: key_result.next_cursor,
+            });
+        }
+        _ => return Err(ParseError::unexpected(tokens, cursor, "property name")),
+    }
+}
+
+/// getter 不能有参数，setter 只能有一个非 rest 参数，对象和类共用
+pub fn check_accessor_params(
+    function_ast: &FunctionExpression,
+    kind: &str,
+) -> Result<(), ParseError> {
+    let params_count = function_ast.params.len();
+    if kind == "get" && params_count != 0 {
+        return Err(ParseError::syntax(
+            "getter should have no params",
+            function_ast.start,
+            function_ast.loc.start,
+        ));
+    }
+    if kind == "set" && params_count != 1 {
+        return Err(ParseError::syntax(
+            "setter should have exactly one param",
+            function_ast.start,
+            function_ast.loc.start,
+        ));
+    }
+    if kind == "set" && matches!(function_ast.params.first(), Some(Pattern::RestElement(_))) {
+        return Err(ParseError::syntax(
+            "Setter cannot use rest params",
+            function_ast.start,
+            function_ast.loc.start,
+        ));
+    }
+    return Ok(());
+}
+
+/*
+* <PropertyDefinition> ::= <IdentifierReference>
+*                        | <PropertyName> ':' <AssignmentExpression>
+*                        | <MethodDefinition>
+*                        | '...' <AssignmentExpression>
+* <MethodDefinition> ::= ('async')? ('*')? <PropertyName> '(' <FormalParameters> ')' '{' <FunctionBody> '}'
+*                      | ('get' | 'set') <PropertyName> '(' <FormalParameters> ')' '{' <FunctionBody> '}'
+*/
+pub fn object_property(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<ObjectExpressionProperty>, ParseError> {
+    let start_token = match tokens.get(cursor) {
+        Some(token) => token,
+        None => return Err(ParseError::unexpected(tokens, cursor, "property")),
+    };
+    if start_token._type.label == "..." {
+        let argument_result = assignment_expression(tokens, cursor + 1, false, context)?;
+        let end_token = &tokens[argument_result.next_cursor - 1];
+        let ast = SpreadElement {
+            _type: ASTType::SpreadElement,
+            start: start_token.start,
+            end: end_token.end,
+            loc: SourceLocation {
+                start: start_token.loc.start,
+                end: end_token.loc.end,
+            },
+            argument: argument_result.ast,
+        };
+        return Ok(GramAnalysisResult {
+            ast: ObjectExpressionProperty::SpreadElement(ast),
+            next_cursor: argument_result.next_cursor,
+        });
     }
-    return Err("");
+
+    let mut next_cursor = cursor;
+    let mut _async = false;
+    let mut generator = false;
+    let mut kind = Kind::Init;
+    // `async` 和属性名之间不能换行
+    if is_contextual(tokens, cursor, "async")
+        && !is_property_name_end(tokens.get(cursor + 1))
+        && !tokens[cursor + 1].newline_before
+    {
+        _async = true;
+        next_cursor += 1;
+    } else if (is_contextual(tokens, cursor, "get") || is_contextual(tokens, cursor, "set"))
+        && !is_property_name_end(tokens.get(cursor + 1))
+    {
+        kind = if is_contextual(tokens, cursor, "get") {
+            Kind::Get
+        } else {
+            Kind::Set
+        };
+        next_cursor += 1;
+    }
+    if matches!(kind, Kind::Init)
+        && matches!(tokens.get(next_cursor), Some(token) if token._type.label == "*")
+    {
+        generator = true;
+        next_cursor += 1;
+    }
+
+    let key_cursor = next_cursor;
+    let key_result = property_key(tokens, key_cursor, context)?;
+    let key = key_result.ast;
+    next_cursor = key_result.next_cursor;
+    let computed = matches!(key, PropertyKey::ComputedPropertyKey(_));
+    let mut method = false;
+    let mut shorthand = false;
+    let value = match tokens.get(next_cursor) {
+        Some(token) if token._type.label == "(" => {
+            let function_result =
+                method_function(tokens, next_cursor, _async, generator, false, context)?;
+            let function_ast = function_result.ast;
+            check_accessor_params(&function_ast, kind.as_str())?;
+            method = matches!(kind, Kind::Init);
+            next_cursor = function_result.next_cursor;
+            Expression::FunctionExpression(Some(Box::new(function_ast)))
+        }
+        _ if _async || generator || !matches!(kind, Kind::Init) => {
+            return Err(ParseError::unexpected(tokens, next_cursor, "'('"));
+        }
+        Some(token) if token._type.label == ":" => {
+            // 值可能在之后被转换为解构模式，如 ({ a: { b = 1 } } = c)
+            let value_result =
+                cover_assignment_expression(tokens, next_cursor + 1, false, context)?;
+            next_cursor = value_result.next_cursor;
+            value_result.ast
+        }
+        // { a = 1 } 只能出现在解构中，先解析为赋值表达式，由 assignment_target 转换或由
+        // check_cover_initialized_name 报错
+        Some(token) if token._type.label == "=" && tokens[key_cursor]._type.label == "name" => {
+            shorthand = true;
+            let identifier = identifier_reference(tokens, key_cursor, context)?.ast;
+            let right_result = assignment_expression(tokens, next_cursor + 1, false, context)?;
+            next_cursor = right_result.next_cursor;
+            let end_token = &tokens[next_cursor - 1];
+            Expression::AssignmentExpression(Some(Box::new(AssignmentExpression {
+                _type: ASTType::AssignmentExpression,
+                start: identifier.start,
+                end: end_token.end,
+                loc: SourceLocation {
+                    start: identifier.loc.start,
+                    end: end_token.loc.end,
+                },
+                operator: "=",
+                left: Pattern::Identifier(identifier),
+                right: right_result.ast,
+            })))
+        }
+        // 简写属性 { a }，属性名只能是标识符，不能是 { if } 这样的保留字
+        Some(token)
+            if (token._type.label == "," || token._type.label == "}")
+                && tokens[key_cursor]._type.label == "name" =>
+        {
+            shorthand = true;
+            Expression::Identifier(identifier_reference(tokens, key_cursor, context)?.ast)
+        }
+        _ => return Err(ParseError::unexpected(tokens, next_cursor, "':'")),
+    };
+    let end_token = &tokens[next_cursor - 1];
+    let ast = Property {
+        _type: ASTType::Property,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        method,
+        shorthand,
+        computed,
+        key,
+        value,
+        kind,
+    };
+    return Ok(GramAnalysisResult {
+        ast: ObjectExpressionProperty::Property(ast),
+        next_cursor,
+    });
 }
@@ -0,0 +1,186 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, Context, Expression, GramAnalysisResult, LiteralType, SourceLocation, Super,
+        ThisExpression,
+    },
+};
+
+use super::{
+    array_expression::array_expression,
+    class_expression::class_expression,
+    expression::parenthesized_expression,
+    function_expression::{function_expression, is_async_function},
+    identifier::{identifier_reference, private_name_reference},
+    import_expression::import_expression,
+    literal_expression::literal_expression,
+    object_expression::object_expression,
+    template_literal::template_literal_expression,
+};
+
+pub fn primary_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    if let Some(token) = tokens.get(cursor) {
+        // literal: number, string, boolean, null, undefined, regexp
+        if token._type.label == "number" {
+            return literal_expression(tokens, cursor, LiteralType::NUMBER, context);
+        } else if token._type.label == "string" {
+            return literal_expression(tokens, cursor, LiteralType::STRING, context);
+        } else if token._type.label == "true" || token._type.label == "false" {
+            return literal_expression(tokens, cursor, LiteralType::BOOLEAN, context);
+        } else if token._type.label == "null" {
+            return literal_expression(tokens, cursor, LiteralType::NULL, context);
+        } else if token._type.label == "regexp" {
+            return literal_expression(tokens, cursor, LiteralType::REGEXP, context);
+        }
+        // ArrayExpression
+        else if token._type.label == "[" {
+            return array_expression(tokens, cursor, context);
+        }
+        // ObjectExpression
+        else if token._type.label == "{" {
+            return object_expression(tokens, cursor, context);
+        }
+        // TemplateLiteral
+        else if token._type.label == "`" {
+            return template_literal_expression(tokens, cursor, context);
+        } else if token._type.label == "this" {
+            return this_expression(tokens, cursor);
+        } else if token._type.label == "function"
+            || token._type.label == "class"
+            || is_async_function(tokens, cursor)
+        {
+            return function_or_class_expression(tokens, cursor, context);
+        } else if token._type.label == "super" {
+            return super_expression(tokens, cursor, context);
+        } else if token._type.label == "import" {
+            return import_expression(tokens, cursor, context);
+        }
+        // #x in obj，私有名称只能出现在 in 的左侧
+        else if token._type.label == "name"
+            || (token._type.label == "privateId"
+                && matches!(tokens.get(cursor + 1), Some(next_token) if next_token._type.label == "in"))
+        {
+            return identifier_expression(tokens, cursor, context);
+        }
+        // 括号表达式，与 acorn 一致直接返回括号内的表达式
+        else if token._type.label == "(" {
+            return parenthesized_expression(tokens, cursor, context);
+        }
+    }
+    return Err(ParseError::unexpected(tokens, cursor, "expression"));
+}
+
+// 以下几个函数把子节点包装为 Expression，与 primary_expression 分开以减小递归路径上的栈帧
+
+fn this_expression(
+    tokens: &[Token],
+    cursor: usize,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let token = &tokens[cursor];
+    let ast = ThisExpression {
+        _type: ASTType::ThisExpression,
+        start: token.start,
+        end: token.end,
+        loc: SourceLocation {
+            start: token.loc.start,
+            end: token.loc.end,
+        },
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::ThisExpression(ast),
+        next_cursor: cursor + 1,
+    });
+}
+
+fn function_or_class_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    if tokens[cursor]._type.label == "class" {
+        let class_result = class_expression(tokens, cursor, context)?;
+        return Ok(GramAnalysisResult {
+            ast: Expression::ClassExpression(Some(Box::new(class_result.ast))),
+            next_cursor: class_result.next_cursor,
+        });
+    }
+    let function_result = function_expression(tokens, cursor, false, context)?;
+    return Ok(GramAnalysisResult {
+        ast: Expression::FunctionExpression(Some(Box::new(function_result.ast))),
+        next_cursor: function_result.next_cursor,
+    });
+}
+
+// 标识符引用，或者 #x in obj 中的私有名称
+fn identifier_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    if tokens[cursor]._type.label == "privateId" {
+        let private_result = private_name_reference(tokens, cursor, context)?;
+        return Ok(GramAnalysisResult {
+            ast: Expression::PrivateIdentifier(private_result.ast),
+            next_cursor: private_result.next_cursor,
+        });
+    }
+    let identifier_result = identifier_reference(tokens, cursor, context)?;
+    return Ok(GramAnalysisResult {
+        ast: Expression::Identifier(identifier_result.ast),
+        next_cursor: identifier_result.next_cursor,
+    });
+}
+
+// super 只能用于 super(...)、super.x 和 super[x]
+fn super_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let token = &tokens[cursor];
+    match tokens.get(cursor + 1) {
+        Some(next_token) if next_token._type.label == "(" => {
+            if !context.allow_super_call {
+                return Err(ParseError::syntax(
+                    "super() call outside constructor of a subclass",
+                    token.start,
+                    token.loc.start,
+                ));
+            }
+        }
+        Some(next_token) if next_token._type.label == "." || next_token._type.label == "[" => {
+            if !context.allow_super_property {
+                return Err(ParseError::syntax(
+                    "'super' keyword outside a method",
+                    token.start,
+                    token.loc.start,
+                ));
+            }
+        }
+        _ => {
+            return Err(ParseError::unexpected(
+                tokens,
+                cursor + 1,
+                "'(', '.' or '['",
+            ))
+        }
+    }
+    let ast = Super {
+        _type: ASTType::Super,
+        start: token.start,
+        end: token.end,
+        loc: SourceLocation {
+            start: token.loc.start,
+            end: token.loc.end,
+        },
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::Super(ast),
+        next_cursor: cursor + 1,
+    });
+}
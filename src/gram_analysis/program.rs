@@ -1,16 +1,112 @@
-use crate::{gram_analysis::statement_list::statement_list, tokenizer::Token, types::{Program, ASTType, SourceType}};
+use crate::{
+    error::ParseError,
+    gram_analysis::{
+        directive_prologue::directive_prologue, export_declaration::check_duplicate_exports,
+        module_item::module_item,
+    },
+    tokenizer::Token,
+    types::{ASTType, Context, Position, Program, SourceLocation, SourceType},
+};
 
-pub fn program(tokens: &[Token]) -> Result<Program, &str> {
+pub fn program(tokens: &[Token], source_type: SourceType) -> Result<Program, ParseError> {
+    // 模块代码始终是严格模式
+    let module = matches!(source_type, SourceType::module);
+    let mut context = Context {
+        strict: module,
+        module,
+        // 模块的顶层可以使用 await
+        in_async: module,
+        ..Context::default()
+    };
     let mut ast = Program {
         _type: ASTType::Program,
+        start: 0,
+        end: tokens.last().map_or(0, |token| token.end),
+        loc: SourceLocation {
+            start: Position { line: 1, column: 0 },
+            end: tokens
+                .last()
+                .map_or(Position { line: 1, column: 0 }, |token| token.loc.end),
+        },
         body: Vec::new(),
-        sourceType: SourceType::module
+        sourceType: source_type,
     };
-    if let Ok(mut result) = statement_list(tokens, 0) {
-        ast.body.append(&mut result.statements);
-        println!("{:#?}", ast);
-        return Ok(ast);
-    } else {
-        return Err("");
+    let directive_result = directive_prologue(tokens, 0, &context)?;
+    context.strict = context.strict || directive_result.strict;
+    ast.body = directive_result.statements;
+    let mut next_cursor = directive_result.next_cursor;
+    while next_cursor < tokens.len() {
+        // 顶层不允许出现多余的 '}'
+        if tokens[next_cursor]._type.label == "}" {
+            return Err(ParseError::unexpected(tokens, next_cursor, "statement"));
+        }
+        let result = module_item(tokens, next_cursor, &context)?;
+        ast.body.push(result.ast);
+        next_cursor = result.next_cursor;
+    }
+    if module {
+        check_duplicate_exports(&ast.body)?;
+    }
+    return Ok(ast);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_json, script_with_locations};
+    use crate::{parse, ParseOptions, SourceType};
+
+    #[test]
+    fn node_locations() {
+        assert_json(
+            script_with_locations,
+            &[
+                (
+                    "a;\r\n\u{1D400} + b",
+                    r#""type":"BinaryExpression","start":4,"end":10,"loc":{"start":{"line":2,"column":0},"end":{"line":2,"column":6}}"#,
+                ),
+                (
+                    "a\rb",
+                    r#""type":"Identifier","start":2,"end":3,"loc":{"start":{"line":2,"column":0},"end":{"line":2,"column":1}}"#,
+                ),
+                (
+                    "if (a)\u{2028}{\u{2029}}",
+                    r#""type":"BlockStatement","start":7,"end":10,"loc":{"start":{"line":2,"column":0},"end":{"line":3,"column":1}}"#,
+                ),
+                (
+                    "'\u{1D400}'.x",
+                    r#""type":"MemberExpression","start":0,"end":6,"loc":{"start":{"line":1,"column":0},"end":{"line":1,"column":6}}"#,
+                ),
+                // Program 覆盖末尾的空白和注释
+                (
+                    "a\r\n// \u{1D400}",
+                    r#"{"type":"Program","start":0,"end":8,"loc":{"start":{"line":1,"column":0},"end":{"line":2,"column":5}}"#,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn error_positions() {
+        let cases = [
+            ("x\r\n  = )", 2, 4),
+            ("x\u{2028}  )", 2, 2),
+            ("\u{1D400} \u{1D400}", 1, 3),
+            ("a\rb\r(", 3, 1),
+        ];
+        for (source, line, column) in cases {
+            let options = ParseOptions {
+                source_type: SourceType::script,
+            };
+            match parse(source, options) {
+                Ok(_) => panic!("{:?}: expected error", source),
+                Err(error) => assert_eq!(
+                    (error.line, error.column),
+                    (line, column),
+                    "{:?}: {}",
+                    source,
+                    error
+                ),
+            }
+        }
     }
 }
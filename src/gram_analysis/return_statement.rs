@@ -0,0 +1,54 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, Context, GramAnalysisResult, ReturnStatement, SourceLocation},
+};
+
+use super::{expression::expression, statement::consume_semicolon};
+
+/*
+* <ReturnStatement> ::= 'return' ';' | 'return' <Expression> ';'
+* 返回值必须与 return 在同一行，否则视为省略了分号
+*/
+pub fn return_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let start_token = &tokens[cursor];
+    if !context.in_function {
+        return Err(ParseError::syntax(
+            "'return' outside of function",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+    let mut next_cursor = cursor + 1;
+    let mut argument = None;
+    match tokens.get(next_cursor) {
+        Some(token)
+            if token._type.label != ";" && token._type.label != "}" && !token.newline_before =>
+        {
+            let expression_result = expression(tokens, next_cursor, false, context)?;
+            argument = Some(expression_result.ast);
+            next_cursor = expression_result.next_cursor;
+        }
+        _ => {}
+    }
+    next_cursor = consume_semicolon(tokens, next_cursor)?;
+    let end_token = &tokens[next_cursor - 1];
+    let ast = ReturnStatement {
+        _type: ASTType::ReturnStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        argument,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::ReturnStatement(ast),
+        next_cursor,
+    });
+}
@@ -0,0 +1,43 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Context, GramAnalysisResult, SourceLocation, SpreadElement},
+};
+
+use super::assignment_expression::{assignment_expression, cover_assignment_expression};
+
+/*
+* <SpreadElement> ::= '...' <AssignmentExpression>
+* `cover` 为 true 时用于数组元素，参数之后可能被转换为 rest 元素，由调用方检查
+*/
+pub fn spread_element(
+    tokens: &[Token],
+    cursor: usize,
+    cover: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<SpreadElement>, ParseError> {
+    let start_token = match tokens.get(cursor) {
+        Some(token) if token._type.label == "..." => token,
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'...'")),
+    };
+    let argument_result = if cover {
+        cover_assignment_expression(tokens, cursor + 1, false, context)?
+    } else {
+        assignment_expression(tokens, cursor + 1, false, context)?
+    };
+    let end_token = &tokens[argument_result.next_cursor - 1];
+    let ast = SpreadElement {
+        _type: ASTType::SpreadElement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        argument: argument_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: argument_result.next_cursor,
+    });
+}
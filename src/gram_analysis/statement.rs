@@ -1,24 +1,399 @@
 use crate::{
+    error::ParseError,
     tokenizer::{MyOption, Token},
-    types::{Body, GramAnalysisResult},
+    types::{Body, Context, GramAnalysisResult},
 };
 
-use super::{block_statement::block_statement, variable_statement::variable_statement};
+use super::{
+    block_statement::block_statement,
+    break_statement::break_statement,
+    class_declaration::class_declaration,
+    continue_statement::continue_statement,
+    debugger_statement::debugger_statement,
+    empty_statement::empty_statement,
+    expression_statement::expression_statement,
+    function_declaration::function_declaration,
+    function_expression::is_async_function,
+    if_statement::if_statement,
+    import_declaration::is_import_declaration,
+    iteration_statement::iteration_statement,
+    labelled_statement::labelled_statement,
+    return_statement::return_statement,
+    switch_statement::switch_statement,
+    throw_statement::throw_statement,
+    try_statement::try_statement,
+    variable_statement::{is_let_declaration, variable_statement},
+    with_statement::with_statement,
+};
+
+/// 语句末尾的 ';'，存在时返回它之后的位置；省略时只有在下一个 token 之前换行、
+/// 下一个 token 是 '}' 或者已经到达结尾时才会自动插入分号
+pub fn consume_semicolon(tokens: &[Token], cursor: usize) -> Result<usize, ParseError> {
+    match tokens.get(cursor) {
+        Some(token) if token._type.label == ";" => return Ok(cursor + 1),
+        Some(token) if token._type.label == "}" || token.newline_before => return Ok(cursor),
+        Some(_) => return Err(ParseError::unexpected(tokens, cursor, "';'")),
+        None => return Ok(cursor),
+    }
+}
+
+// 嵌套层数的上限，递归下降的每一层都占用调用栈，过深的输入报错而不是栈溢出
+const MAX_NESTING_DEPTH: usize = 128;
+
+/// 进入从 cursor 开始的下一层语句、表达式或绑定模式，超过 MAX_NESTING_DEPTH 时报错
+pub fn nested_context(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<Context, ParseError> {
+    if context.depth >= MAX_NESTING_DEPTH {
+        // 源码提前结束时报告在最后一个 token 上
+        if let Some(token) = tokens.get(cursor).or(tokens.last()) {
+            return Err(ParseError::syntax(
+                "Maximum nesting depth exceeded",
+                token.start,
+                token.loc.start,
+            ));
+        }
+    }
+    return Ok(Context {
+        depth: context.depth + 1,
+        ..context.clone()
+    });
+}
+
+pub fn statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    if let Some(token) = tokens.get(cursor) {
+        let context = &nested_context(tokens, cursor, context)?;
+        if is_let_declaration(tokens, cursor) {
+            return variable_statement(tokens, cursor, context);
+        } else if is_async_function(tokens, cursor) {
+            return function_declaration(tokens, cursor, context);
+        } else if let MyOption(Some(keyword)) = &token._type.keyword {
+            if keyword == "const" || keyword == "var" {
+                return variable_statement(tokens, cursor, context);
+            } else if keyword == "function" {
+                return function_declaration(tokens, cursor, context);
+            } else if keyword == "class" {
+                return class_declaration(tokens, cursor, context);
+            } else if keyword == "if" {
+                return if_statement(tokens, cursor, context);
+            } else if keyword == "for" || keyword == "while" || keyword == "do" {
+                return iteration_statement(tokens, cursor, context);
+            } else if keyword == "switch" {
+                return switch_statement(tokens, cursor, context);
+            } else if keyword == "break" {
+                return break_statement(tokens, cursor, context);
+            } else if keyword == "continue" {
+                return continue_statement(tokens, cursor, context);
+            } else if keyword == "return" {
+                return return_statement(tokens, cursor, context);
+            } else if keyword == "throw" {
+                return throw_statement(tokens, cursor, context);
+            } else if keyword == "try" {
+                return try_statement(tokens, cursor, context);
+            } else if keyword == "with" {
+                return with_statement(tokens, cursor, context);
+            } else if keyword == "debugger" {
+                return debugger_statement(tokens, cursor);
+            } else if keyword == "export" || is_import_declaration(tokens, cursor) {
+                // 模块顶层的 import 和 export 由 module_item 处理，不会走到这里
+                let message = if context.module {
+                    "'import' and 'export' may only appear at the top level"
+                } else {
+                    "'import' and 'export' may appear only with 'sourceType: module'"
+                };
+                return Err(ParseError::syntax(message, token.start, token.loc.start));
+            }
+        } else if token._type.label == "{" {
+            return block_statement(tokens, cursor, context);
+        } else if token._type.label == ";" {
+            return empty_statement(tokens, cursor);
+        } else if token._type.label == "name"
+            && matches!(tokens.get(cursor + 1), Some(next_token) if next_token._type.label == ":")
+        {
+            return labelled_statement(tokens, cursor, context);
+        }
+        return expression_statement(tokens, cursor, context);
+    }
+    return Err(ParseError::unexpected(tokens, cursor, "statement"));
+}
+
+// 单条语句所在的位置，决定其中能否出现函数声明
+pub enum SubstatementPosition {
+    // if 和 else 的主体，非严格模式下允许普通函数声明
+    If,
+    // 循环和 with 的主体
+    Body,
+    // 标签的主体，非严格模式下允许普通函数声明
+    Label,
+}
+
+fn declaration_error(message: &str, token: &Token) -> ParseError {
+    return ParseError::syntax(message, token.start, token.loc.start);
+}
 
-pub fn statement(tokens: &[Token], cursor: usize) -> Result<GramAnalysisResult<Body>, &str> {
+/*
+* <Substatement> ::= <Statement>
+* if、循环、with 和标签的主体只能是语句而不是声明，
+* 只有非严格模式下 if 和标签的主体可以是普通的函数声明
+*/
+pub fn substatement(
+    tokens: &[Token],
+    cursor: usize,
+    position: SubstatementPosition,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
     if let Some(token) = tokens.get(cursor) {
-        if let MyOption(keywordOption) = &token._type.keyword {
-            println!("{}", token);
-            if let Some(keyword) = keywordOption {
-                if keyword == "const" || keyword == "let" || keyword == "var" {
-                    return variable_statement(tokens, cursor);
-                }
-            } else {
-                if token._type.label == "{" {
-                    return block_statement(tokens, cursor);
-                }
+        if is_let_declaration(tokens, cursor) {
+            // let 后换行时按标识符处理，但 `let [` 始终视为声明
+            let next_token = &tokens[cursor + 1];
+            if next_token._type.label == "[" || !next_token.newline_before {
+                return Err(declaration_error(
+                    "Lexical declaration cannot appear in a single-statement context",
+                    token,
+                ));
+            }
+            return expression_statement(tokens, cursor, context);
+        } else if is_async_function(tokens, cursor) {
+            return Err(declaration_error(
+                "Async functions can only be declared at the top level or inside a block",
+                token,
+            ));
+        } else if token._type.label == "const" {
+            return Err(declaration_error(
+                "Lexical declaration cannot appear in a single-statement context",
+                token,
+            ));
+        } else if token._type.label == "class" {
+            return Err(declaration_error(
+                "Class declaration cannot appear in a single-statement context",
+                token,
+            ));
+        } else if token._type.label == "function" {
+            if matches!(tokens.get(cursor + 1), Some(next_token) if next_token._type.label == "*") {
+                return Err(declaration_error(
+                    "Generators can only be declared at the top level or inside a block",
+                    token,
+                ));
+            } else if context.strict {
+                return Err(declaration_error(
+                    "In strict mode code, functions can only be declared at top level or inside a block",
+                    token,
+                ));
+            } else if matches!(position, SubstatementPosition::Body) {
+                return Err(declaration_error(
+                    "In non-strict mode code, functions can only be declared at top level, inside a block, or as the body of an if statement",
+                    token,
+                ));
+            }
+        } else if !matches!(position, SubstatementPosition::Label) {
+            // if、循环和 with 的主体不能是带标签的函数声明，如 if (a) l: function f() {}
+            let mut next_cursor = cursor;
+            while matches!(
+                (tokens.get(next_cursor), tokens.get(next_cursor + 1)),
+                (Some(name), Some(colon)) if name._type.label == "name" && colon._type.label == ":"
+            ) {
+                next_cursor += 2;
+            }
+            if next_cursor != cursor
+                && matches!(tokens.get(next_cursor), Some(target) if target._type.label == "function")
+            {
+                return Err(declaration_error(
+                    "Labelled function declaration cannot appear in a single-statement context",
+                    &tokens[next_cursor],
+                ));
             }
         }
     }
-    return Err("");
+    return statement(tokens, cursor, context);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn automatic_semicolon_insertion() {
+        assert_json(
+            script,
+            &[
+                (
+                    "let a = 1\nlet b = 2",
+                    r#""kind":"let"},{"type":"VariableDeclaration","start":10"#,
+                ),
+                (
+                    "{ a } b",
+                    r#""type":"ExpressionStatement","start":6,"end":7"#,
+                ),
+                (
+                    "var a = 1;;",
+                    r#"{"type":"EmptyStatement","start":10,"end":11}"#,
+                ),
+                (
+                    "do a\nwhile (b) c",
+                    r#""type":"DoWhileStatement","start":0,"end":14"#,
+                ),
+                (
+                    "if (a) b\nelse c",
+                    r#""alternate":{"type":"ExpressionStatement""#,
+                ),
+                // 下一行以 ( [ ` 开头时不插入分号
+                ("a\n(b)", r#""type":"CallExpression""#),
+                ("a = b\n[c]", r#""right":{"type":"MemberExpression""#),
+                ("a\n`t`", r#""type":"TaggedTemplateExpression""#),
+            ],
+        );
+    }
+
+    #[test]
+    fn restricted_productions() {
+        assert_json(
+            script,
+            &[
+                (
+                    "function f() { return\na }",
+                    r#""type":"ReturnStatement","start":15,"end":21,"argument":null"#,
+                ),
+                ("a\n++b", r#""operator":"++","prefix":true"#),
+                ("a++\nb", r#""operator":"++","prefix":false"#),
+                (
+                    "x: while (1) { break\nx }",
+                    r#""type":"BreakStatement","start":15,"end":20,"label":null"#,
+                ),
+                (
+                    "x: while (1) { continue\nx }",
+                    r#""type":"ContinueStatement","start":15,"end":23,"label":null"#,
+                ),
+            ],
+        );
+        assert_errors(
+            script,
+            &[
+                ("let a = 1 let b = 2", "expected ';', found 'let'"),
+                ("for (a\nb;;);", "expected ';', found 'b'"),
+                ("throw\na", "Illegal newline after throw"),
+                ("a\n=> 1", "expected expression, found '=>'"),
+            ],
+        );
+    }
+
+    #[test]
+    fn substatement_declarations() {
+        assert_json(
+            script,
+            &[
+                (
+                    "if (a) function f() {} else function g() {}",
+                    r#""alternate":{"type":"FunctionDeclaration""#,
+                ),
+                (
+                    "l: m: function f() {}",
+                    r#""body":{"type":"FunctionDeclaration""#,
+                ),
+                ("while (1) { const a = 1 }", r#""kind":"const""#),
+                // let 后换行时是标识符
+                (
+                    "if (a) let\nx = 1",
+                    r#""consequent":{"type":"ExpressionStatement","start":7,"end":10"#,
+                ),
+                (
+                    "if (a) async\nfunction f() {}",
+                    r#""consequent":{"type":"ExpressionStatement""#,
+                ),
+            ],
+        );
+        assert_errors(
+            script,
+            &[
+                (
+                    "while (1) const x = 1;",
+                    "Lexical declaration cannot appear in a single-statement context",
+                ),
+                (
+                    "if (a) let x = 1",
+                    "Lexical declaration cannot appear in a single-statement context",
+                ),
+                (
+                    "if (a) let\n[x] = 1",
+                    "Lexical declaration cannot appear in a single-statement context",
+                ),
+                (
+                    "l: const a = 1",
+                    "Lexical declaration cannot appear in a single-statement context",
+                ),
+                (
+                    "if (a) class A {}",
+                    "Class declaration cannot appear in a single-statement context",
+                ),
+                (
+                    "for (;;) async function f() {}",
+                    "Async functions can only be declared at the top level or inside a block",
+                ),
+                (
+                    "a: function* g() {}",
+                    "Generators can only be declared at the top level or inside a block",
+                ),
+                (
+                    "do function f() {} while (0)",
+                    "functions can only be declared at top level, inside a block, or as the body of an if statement",
+                ),
+                (
+                    "with (a) function f() {}",
+                    "functions can only be declared at top level, inside a block, or as the body of an if statement",
+                ),
+                (
+                    "if (a) l: function f() {}",
+                    "Labelled function declaration cannot appear in a single-statement context",
+                ),
+                (
+                    "while (1) l: m: function f() {}",
+                    "Labelled function declaration cannot appear in a single-statement context",
+                ),
+                (
+                    "\"use strict\"; if (a) function f() {}",
+                    "In strict mode code, functions can only be declared at top level or inside a block",
+                ),
+                (
+                    "\"use strict\"; a: function f() {}",
+                    "In strict mode code, functions can only be declared at top level or inside a block",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn nesting_depth() {
+        // 真实代码中常见的回调嵌套可以正常解析
+        let callbacks = format!(
+            "{}x = [b, {{ c: (d ? e : h) }}];{}",
+            "f(function () { return g(() => { if (a) { ".repeat(6),
+            "} }); });".repeat(6)
+        );
+        assert_json(script, &[(&callbacks, r#""type":"Program""#)]);
+        // 过深的输入报错而不是栈溢出
+        let n = 10000;
+        let deep = [
+            format!("{}a{}", "(".repeat(n), ")".repeat(n)),
+            format!("{}{}", "[".repeat(n), "]".repeat(n)),
+            format!("{}{}", "{".repeat(n), "}".repeat(n)),
+            format!("{}a{}", "f(".repeat(n), ")".repeat(n)),
+            format!("{}a", "!".repeat(n)),
+            format!("{}a", "a = ".repeat(n)),
+            format!("{}a", "a ? a : ".repeat(n)),
+            format!("{};", "if (a) ".repeat(n)),
+            format!("var {}a{} = b", "[".repeat(n), "]".repeat(n)),
+            format!("{}{}", "(function () {".repeat(n), "})".repeat(n)),
+            format!("{}{}", "(class { m() {".repeat(n), "}})".repeat(n)),
+        ];
+        let cases: Vec<(&str, &str)> = deep
+            .iter()
+            .map(|source| (source.as_str(), "Maximum nesting depth exceeded"))
+            .collect();
+        assert_errors(script, &cases);
+    }
 }
@@ -1,28 +1,28 @@
+use crate::error::ParseError;
 use crate::gram_analysis::statement::statement;
 use crate::tokenizer::Token;
-use crate::types::Body;
+use crate::types::{Body, Context};
 
-pub struct StatementListResult<'a> {
-    pub statements: Vec<Body<'a>>,
+pub struct StatementListResult {
+    pub statements: Vec<Body>,
     pub next_cursor: usize,
 }
 
-pub fn statement_list(tokens: &[Token], cursor: usize) -> Result<StatementListResult, &str> {
+pub fn statement_list(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<StatementListResult, ParseError> {
     let mut statements = Vec::new();
     let mut next_cursor = cursor;
-    loop {
-        match tokens.get(next_cursor) {
-            Some(_) => {
-                println!("11");
-                if let Ok(result) = statement(tokens, next_cursor) {
-                    statements.push(result.ast);
-                    next_cursor = result.next_cursor;
-                } else {
-                    return Err("");
-                }
-            }
-            None => break,
+    while let Some(token) = tokens.get(next_cursor) {
+        // 块语句的结束
+        if token._type.label == "}" {
+            break;
         }
+        let result = statement(tokens, next_cursor, context)?;
+        statements.push(result.ast);
+        next_cursor = result.next_cursor;
     }
     return Ok(StatementListResult {
         statements,
@@ -0,0 +1,107 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, Body, Context, GramAnalysisResult, SourceLocation, SwitchCase, SwitchStatement,
+    },
+};
+
+use super::{
+    expression::{expression, parenthesized_expression},
+    statement::statement,
+};
+
+/*
+* <SwitchStatement> ::= 'switch' '(' <Expression> ')' '{' <CaseClause>* '}'
+* <CaseClause> ::= 'case' <Expression> ':' <StatementList>? | 'default' ':' <StatementList>?
+*/
+pub fn switch_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let discriminant_result = parenthesized_expression(tokens, cursor + 1, context)?;
+    let mut next_cursor = discriminant_result.next_cursor;
+    match tokens.get(next_cursor) {
+        Some(token) if token._type.label == "{" => next_cursor += 1,
+        _ => return Err(ParseError::unexpected(tokens, next_cursor, "'{'")),
+    }
+    let mut case_context = context.clone();
+    case_context.in_switch = true;
+    let mut cases = Vec::new();
+    let mut has_default = false;
+    loop {
+        let case_token = match tokens.get(next_cursor) {
+            Some(token) if token._type.label == "}" => break,
+            Some(token) if token._type.label == "case" || token._type.label == "default" => token,
+            _ => {
+                return Err(ParseError::unexpected(
+                    tokens,
+                    next_cursor,
+                    "'case' or 'default'",
+                ))
+            }
+        };
+        let mut test = None;
+        if case_token._type.label == "case" {
+            let test_result = expression(tokens, next_cursor + 1, false, context)?;
+            test = Some(test_result.ast);
+            next_cursor = test_result.next_cursor;
+        } else {
+            if has_default {
+                return Err(ParseError::syntax(
+                    "Multiple default clauses",
+                    case_token.start,
+                    case_token.loc.start,
+                ));
+            }
+            has_default = true;
+            next_cursor += 1;
+        }
+        match tokens.get(next_cursor) {
+            Some(token) if token._type.label == ":" => next_cursor += 1,
+            _ => return Err(ParseError::unexpected(tokens, next_cursor, "':'")),
+        }
+        let mut consequent = Vec::new();
+        while let Some(token) = tokens.get(next_cursor) {
+            if token._type.label == "case"
+                || token._type.label == "default"
+                || token._type.label == "}"
+            {
+                break;
+            }
+            let statement_result = statement(tokens, next_cursor, &case_context)?;
+            consequent.push(statement_result.ast);
+            next_cursor = statement_result.next_cursor;
+        }
+        let end_token = &tokens[next_cursor - 1];
+        cases.push(SwitchCase {
+            _type: ASTType::SwitchCase,
+            start: case_token.start,
+            end: end_token.end,
+            loc: SourceLocation {
+                start: case_token.loc.start,
+                end: end_token.loc.end,
+            },
+            test,
+            consequent,
+        });
+    }
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor];
+    let ast = SwitchStatement {
+        _type: ASTType::SwitchStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        discriminant: discriminant_result.ast,
+        cases,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::SwitchStatement(ast),
+        next_cursor: next_cursor + 1,
+    });
+}
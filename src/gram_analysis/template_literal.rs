@@ -0,0 +1,234 @@
+use crate::{
+    error::ParseError,
+    tokenizer::{Token, TokenValue},
+    types::{
+        ASTType, Context, Expression, GramAnalysisResult, SourceLocation, TemplateElement,
+        TemplateElementValue, TemplateLiteral,
+    },
+};
+
+use crate::utils::escape::{
+    combine_surrogate_pair, push_code_point, read_hex, read_unicode_escape,
+};
+
+use super::expression::expression;
+
+fn is_label(tokens: &[Token], cursor: usize, label: &str) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == label);
+}
+
+/*
+* <TemplateLiteral> ::= '`' <TemplateCharacters>? '`'
+*                     | '`' <TemplateCharacters>? '${' <Expression> '}' (<TemplateCharacters>? '${' <Expression> '}')* <TemplateCharacters>? '`'
+* `tagged` 为 true 时是标签模板，非法转义不报错，cooked 为 null
+*/
+pub fn template_literal(
+    tokens: &[Token],
+    cursor: usize,
+    tagged: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<TemplateLiteral>, ParseError> {
+    if !is_label(tokens, cursor, "`") {
+        return Err(ParseError::unexpected(tokens, cursor, "'`'"));
+    }
+    let mut quasis = Vec::new();
+    let mut expressions = Vec::new();
+    let mut next_cursor = cursor + 1;
+    loop {
+        let element_token = match tokens.get(next_cursor) {
+            Some(token) if token._type.label == "template" => token,
+            _ => return Err(ParseError::unexpected(tokens, next_cursor, "template")),
+        };
+        let tail = is_label(tokens, next_cursor + 1, "`");
+        quasis.push(template_element(element_token, tail, tagged)?);
+        if tail {
+            next_cursor += 2;
+            break;
+        }
+        if !is_label(tokens, next_cursor + 1, "${") {
+            return Err(ParseError::unexpected(
+                tokens,
+                next_cursor + 1,
+                "'${' or '`'",
+            ));
+        }
+        let expression_result = expression(tokens, next_cursor + 2, false, context)?;
+        if !is_label(tokens, expression_result.next_cursor, "}") {
+            return Err(ParseError::unexpected(
+                tokens,
+                expression_result.next_cursor,
+                "'}'",
+            ));
+        }
+        expressions.push(expression_result.ast);
+        next_cursor = expression_result.next_cursor + 1;
+    }
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let ast = TemplateLiteral {
+        _type: ASTType::TemplateLiteral,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        quasis,
+        expressions,
+    };
+    return Ok(GramAnalysisResult { ast, next_cursor });
+}
+
+pub fn template_literal_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let template_result = template_literal(tokens, cursor, false, context)?;
+    return Ok(GramAnalysisResult {
+        ast: Expression::TemplateLiteral(Some(Box::new(template_result.ast))),
+        next_cursor: template_result.next_cursor,
+    });
+}
+
+fn template_element(
+    token: &Token,
+    tail: bool,
+    tagged: bool,
+) -> Result<TemplateElement, ParseError> {
+    let source = match &token.value {
+        TokenValue::String(value) => value.as_str(),
+        _ => "",
+    };
+    // raw 和 cooked 中的 \r\n 和 \r 都视为 \n
+    let raw = source.replace("\r\n", "\n").replace('\r', "\n");
+    let cooked = match cook_template(&raw) {
+        Ok(cooked) => Some(cooked),
+        Err(message) if !tagged => {
+            return Err(ParseError::syntax(message, token.start, token.loc.start))
+        }
+        Err(_) => None,
+    };
+    return Ok(TemplateElement {
+        _type: ASTType::TemplateElement,
+        start: token.start,
+        end: token.end,
+        loc: SourceLocation {
+            start: token.loc.start,
+            end: token.loc.end,
+        },
+        value: TemplateElementValue { raw, cooked },
+        tail,
+    });
+}
+
+// 处理模板字符串中的转义，非法转义返回错误信息
+fn cook_template(raw: &str) -> Result<String, String> {
+    let invalid_escape = || String::from("Bad escape sequence in untagged template literal");
+    let chars: Vec<char> = raw.chars().collect();
+    let mut cooked = String::new();
+    let mut index = 0;
+    while index < chars.len() {
+        let char = chars[index];
+        index += 1;
+        if char != '\\' {
+            cooked.push(char);
+            continue;
+        }
+        let escaped = match chars.get(index) {
+            Some(escaped) => *escaped,
+            None => return Err(invalid_escape()),
+        };
+        index += 1;
+        match escaped {
+            'n' => cooked.push('\n'),
+            't' => cooked.push('\t'),
+            'r' => cooked.push('\r'),
+            'b' => cooked.push('\u{8}'),
+            'f' => cooked.push('\u{c}'),
+            'v' => cooked.push('\u{b}'),
+            // 续行，反斜杠和换行都不出现在结果中
+            '\n' | '\u{2028}' | '\u{2029}' => {}
+            '0' if !matches!(chars.get(index), Some(next) if next.is_ascii_digit()) => {
+                cooked.push('\0')
+            }
+            // 模板字符串中不允许八进制转义
+            '0'..='9' => return Err(invalid_escape()),
+            'x' => {
+                let code_point = read_hex(&chars, index, 2).ok_or_else(invalid_escape)?;
+                push_code_point(&mut cooked, code_point);
+                index += 2;
+            }
+            'u' => {
+                let (code_point, length) =
+                    read_unicode_escape(&chars, index).map_err(|_| invalid_escape())?;
+                index += length;
+                let (code_point, length) = combine_surrogate_pair(&chars, index, code_point);
+                index += length;
+                push_code_point(&mut cooked, code_point);
+            }
+            escaped => cooked.push(escaped),
+        }
+    }
+    return Ok(cooked);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn template_literals() {
+        assert_json(
+            script,
+            &[
+                // 标签模板中的非法转义使 cooked 为 null，raw 保持原样
+                (
+                    "tag`\\unicode and \\u{55}`",
+                    r#""value":{"raw":"\\unicode and \\u{55}","cooked":null}"#,
+                ),
+                ("tag`\\01`", r#""value":{"raw":"\\01","cooked":null}"#),
+                (
+                    "tag`a${b}\\xg`",
+                    r#""value":{"raw":"a","cooked":"a"},"tail":false},{"type":"TemplateElement","start":9,"end":12,"value":{"raw":"\\xg","cooked":null},"tail":true}"#,
+                ),
+                // raw 和 cooked 中的 \r\n 和 \r 都规范化为 \n
+                (
+                    "`a\r\nb\rc`",
+                    r#""value":{"raw":"a\nb\nc","cooked":"a\nb\nc"}"#,
+                ),
+                ("`\\\r\n`", r#""value":{"raw":"\\\n","cooked":""}"#),
+                // 嵌套的模板
+                (
+                    "`a${ `b${c}` }d`",
+                    r#""expressions":[{"type":"TemplateLiteral","start":5,"end":12,"expressions":[{"type":"Identifier","start":9,"end":10,"name":"c"}]"#,
+                ),
+                ("`${ `${ `${a}` }` }`", r#""name":"a""#),
+                ("a.b`c`", r#""tag":{"type":"MemberExpression""#),
+            ],
+        );
+        assert_errors(
+            script,
+            &[
+                (
+                    "`\\unicode`",
+                    "Bad escape sequence in untagged template literal",
+                ),
+                (
+                    "`a${b}\\01`",
+                    "Bad escape sequence in untagged template literal",
+                ),
+                (
+                    "a?.b`c`",
+                    "Optional chaining cannot appear in the tag of tagged template expressions",
+                ),
+                (
+                    "a?.b.c`d`",
+                    "Optional chaining cannot appear in the tag of tagged template expressions",
+                ),
+                ("`a${b`", "Unterminated template"),
+                ("`${ `${}` }`", "expected expression, found '}'"),
+            ],
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, Context, GramAnalysisResult, SourceLocation, ThrowStatement},
+};
+
+use super::{expression::expression, statement::consume_semicolon};
+
+/*
+* <ThrowStatement> ::= 'throw' <Expression> ';'
+*/
+pub fn throw_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let start_token = &tokens[cursor];
+    // throw 之后不能换行，否则会被自动插入分号
+    if let Some(token) = tokens.get(cursor + 1) {
+        if token.newline_before {
+            return Err(ParseError::syntax(
+                "Illegal newline after throw",
+                start_token.end,
+                start_token.loc.end,
+            ));
+        }
+    }
+    let expression_result = expression(tokens, cursor + 1, false, context)?;
+    let next_cursor = consume_semicolon(tokens, expression_result.next_cursor)?;
+    let end_token = &tokens[next_cursor - 1];
+    let ast = ThrowStatement {
+        _type: ASTType::ThrowStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        argument: expression_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::ThrowStatement(ast),
+        next_cursor,
+    });
+}
@@ -0,0 +1,155 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, Body, CatchClause, Context, GramAnalysisResult, SourceLocation, TryStatement,
+    },
+};
+
+use super::{binding_pattern::binding_target, block_statement::block};
+
+fn is_label(tokens: &[Token], cursor: usize, label: &str) -> bool {
+    return matches!(tokens.get(cursor), Some(token) if token._type.label == label);
+}
+
+/*
+* <TryStatement> ::= 'try' <Block> <Catch> | 'try' <Block> <Finally> | 'try' <Block> <Catch> <Finally>
+* <Catch> ::= 'catch' '(' <BindingTarget> ')' <Block> | 'catch' <Block>
+* <Finally> ::= 'finally' <Block>
+*/
+pub fn try_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let block_result = block(tokens, cursor + 1, context)?;
+    let mut next_cursor = block_result.next_cursor;
+    let mut handler = None;
+    if is_label(tokens, next_cursor, "catch") {
+        let catch_token = &tokens[next_cursor];
+        next_cursor += 1;
+        let mut param = None;
+        if is_label(tokens, next_cursor, "(") {
+            let param_result = binding_target(tokens, next_cursor + 1, context)?;
+            if !is_label(tokens, param_result.next_cursor, ")") {
+                return Err(ParseError::unexpected(
+                    tokens,
+                    param_result.next_cursor,
+                    "')'",
+                ));
+            }
+            param = Some(param_result.ast);
+            next_cursor = param_result.next_cursor + 1;
+        }
+        let body_result = block(tokens, next_cursor, context)?;
+        let body = body_result.ast;
+        next_cursor = body_result.next_cursor;
+        handler = Some(CatchClause {
+            _type: ASTType::CatchClause,
+            start: catch_token.start,
+            end: body.end,
+            loc: SourceLocation {
+                start: catch_token.loc.start,
+                end: body.loc.end,
+            },
+            param,
+            body,
+        });
+    }
+    let mut finalizer = None;
+    if is_label(tokens, next_cursor, "finally") {
+        let finalizer_result = block(tokens, next_cursor + 1, context)?;
+        finalizer = Some(finalizer_result.ast);
+        next_cursor = finalizer_result.next_cursor;
+    }
+    let start_token = &tokens[cursor];
+    if handler.is_none() && finalizer.is_none() {
+        return Err(ParseError::syntax(
+            "Missing catch or finally clause",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+    let end_token = &tokens[next_cursor - 1];
+    let ast = TryStatement {
+        _type: ASTType::TryStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        block: block_result.ast,
+        handler,
+        finalizer,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::TryStatement(ast),
+        next_cursor,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn try_statements() {
+        assert_json(
+            script,
+            &[
+                (
+                    "try { a } catch (e) { b }",
+                    r#""handler":{"type":"CatchClause","start":10,"end":25,"param":{"type":"Identifier","start":17,"end":18,"name":"e"}"#,
+                ),
+                ("try { a } catch (e) { b }", r#""finalizer":null"#),
+                (
+                    "try {} finally {}",
+                    r#""handler":null,"finalizer":{"type":"BlockStatement","start":15,"end":17,"body":[]}"#,
+                ),
+                // 省略 catch 的参数
+                (
+                    "try {} catch {} finally {}",
+                    r#""handler":{"type":"CatchClause","start":7,"end":15,"param":null,"#,
+                ),
+                (
+                    "try { a } catch ([b]) { c } finally { d }",
+                    r#""param":{"type":"ArrayPattern","start":17,"end":20,"#,
+                ),
+                (
+                    "try { a } catch ([b]) { c } finally { d }",
+                    r#""finalizer":{"type":"BlockStatement","start":36,"end":41,"#,
+                ),
+                ("try {}\ncatch (e) {}", r#""type":"CatchClause""#),
+                // 语句末尾的 '}' 之后是正则
+                (
+                    "try {} catch (e) {} /x/",
+                    r#""regex":{"pattern":"x","flags":""}"#,
+                ),
+            ],
+        );
+        assert_errors(
+            script,
+            &[
+                ("try {}", "Missing catch or finally clause"),
+                ("try a; catch (e) {}", "expected '{', found 'a'"),
+                ("try {} catch (e) a", "expected '{', found 'a'"),
+                ("try {} catch () {}", "expected identifier, found ')'"),
+                ("try {} catch (e = 1) {}", "expected ')', found '='"),
+                (
+                    "try {} catch (e) {} catch (f) {}",
+                    "expected expression, found 'catch'",
+                ),
+                ("try {} finally", "expected '{', found end of input"),
+                (
+                    "'use strict'; try {} catch (eval) {}",
+                    "Binding eval in strict mode",
+                ),
+                (
+                    "function* g() { try {} catch (yield) {} }",
+                    "Cannot use 'yield' as identifier inside a generator",
+                ),
+            ],
+        );
+    }
+}
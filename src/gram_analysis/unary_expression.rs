@@ -0,0 +1,181 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{
+        ASTType, Context, Expression, GramAnalysisResult, SourceLocation, UnaryExpression,
+        UpdateExpression,
+    },
+};
+
+use super::{
+    assignment_expression::check_cover_initialized_name,
+    await_expression::await_expression,
+    identifier::is_contextual,
+    statement::nested_context,
+    update_expression::{check_update_argument, update_expression},
+};
+
+fn unary_operator(label: &str) -> Option<&'static str> {
+    match label {
+        "-" => Some("-"),
+        "+" => Some("+"),
+        "!" => Some("!"),
+        "~" => Some("~"),
+        "typeof" => Some("typeof"),
+        "void" => Some("void"),
+        "delete" => Some("delete"),
+        _ => None,
+    }
+}
+
+// a.#x 或以其结尾的可选链
+fn is_private_member(expression: &Expression) -> bool {
+    match expression {
+        Expression::MemberExpression(Some(member)) => {
+            return matches!(member.property, Expression::PrivateIdentifier(_))
+        }
+        Expression::ChainExpression(Some(chain)) => return is_private_member(&chain.expression),
+        _ => return false,
+    }
+}
+
+/*
+* <UnaryExpression> ::= <UpdateExpression>
+*                     | ('delete' | 'void' | 'typeof' | '+' | '-' | '~' | '!') <UnaryExpression>
+*                     | ('++' | '--') <UnaryExpression>
+*                     | <AwaitExpression>
+*/
+pub fn unary_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let label = match tokens.get(cursor) {
+        Some(token) => token._type.label.as_str(),
+        None => return update_expression(tokens, cursor, context),
+    };
+    let is_await = is_contextual(tokens, cursor, "await") && context.in_async;
+    let operator = unary_operator(label);
+    if !is_await && operator.is_none() && label != "++" && label != "--" {
+        return update_expression(tokens, cursor, context);
+    }
+    // 前缀运算符可以任意嵌套，如 !!a、- -a、await await a
+    let context = &nested_context(tokens, cursor, context)?;
+    if is_await {
+        return await_expression(tokens, cursor, context);
+    } else if let Some(operator) = operator {
+        return unary_operation(tokens, cursor, operator, context);
+    }
+    return prefix_update(tokens, cursor, context);
+}
+
+fn unary_operation(
+    tokens: &[Token],
+    cursor: usize,
+    operator: &'static str,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let token = &tokens[cursor];
+    let argument_result = unary_expression(tokens, cursor + 1, context)?;
+    check_cover_initialized_name(&argument_result.ast)?;
+    // 私有成员始终不能删除，delete this?.#a 同样如此
+    if operator == "delete" && is_private_member(&argument_result.ast) {
+        return Err(ParseError::syntax(
+            "Private fields can not be deleted",
+            token.start,
+            token.loc.start,
+        ));
+    }
+    // 严格模式中不能删除变量，delete (a) 同样如此
+    if operator == "delete" && context.strict {
+        if let Expression::Identifier(_) = argument_result.ast {
+            return Err(ParseError::syntax(
+                "Deleting local variable in strict mode",
+                token.start,
+                token.loc.start,
+            ));
+        }
+    }
+    let end_token = &tokens[argument_result.next_cursor - 1];
+    let ast = UnaryExpression {
+        _type: ASTType::UnaryExpression,
+        start: token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: token.loc.start,
+            end: end_token.loc.end,
+        },
+        operator,
+        prefix: true,
+        argument: argument_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::UnaryExpression(Some(Box::new(ast))),
+        next_cursor: argument_result.next_cursor,
+    });
+}
+
+// 前缀的 ++a 和 --a
+fn prefix_update(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let token = &tokens[cursor];
+    let argument_result = unary_expression(tokens, cursor + 1, context)?;
+    check_update_argument(tokens, cursor + 1, &argument_result.ast, context)?;
+    let end_token = &tokens[argument_result.next_cursor - 1];
+    let ast = UpdateExpression {
+        _type: ASTType::UpdateExpression,
+        start: token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: token.loc.start,
+            end: end_token.loc.end,
+        },
+        operator: if token._type.label == "++" {
+            "++"
+        } else {
+            "--"
+        },
+        prefix: true,
+        argument: argument_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::UpdateExpression(Some(Box::new(ast))),
+        next_cursor: argument_result.next_cursor,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn strict_mode_delete() {
+        assert_errors(
+            script,
+            &[
+                (
+                    "'use strict'; delete a",
+                    "Deleting local variable in strict mode",
+                ),
+                (
+                    "'use strict'; delete (a)",
+                    "Deleting local variable in strict mode",
+                ),
+                (
+                    "function f() { 'use strict'; delete a }",
+                    "Deleting local variable",
+                ),
+            ],
+        );
+        assert_json(
+            script,
+            &[
+                ("delete a", r#""operator":"delete""#),
+                ("'use strict'; delete a.b", r#""operator":"delete""#),
+            ],
+        );
+    }
+}
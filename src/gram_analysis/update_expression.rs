@@ -0,0 +1,91 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Context, Expression, GramAnalysisResult, SourceLocation, UpdateExpression},
+};
+
+use super::{
+    identifier::is_eval_or_arguments, left_hand_side_expression::left_hand_side_expression,
+};
+
+/// `++`/`--` 的操作数只能是标识符或成员表达式，`argument_cursor` 是操作数的第一个 token
+pub fn check_update_argument(
+    tokens: &[Token],
+    argument_cursor: usize,
+    argument: &Expression,
+    context: &Context,
+) -> Result<(), ParseError> {
+    match argument {
+        Expression::Identifier(identifier)
+            if context.strict && is_eval_or_arguments(&identifier.name) =>
+        {
+            return Err(ParseError::syntax(
+                format!("Assigning to {} in strict mode", identifier.name),
+                identifier.start,
+                identifier.loc.start,
+            ));
+        }
+        Expression::Identifier(_) | Expression::MemberExpression(_) => return Ok(()),
+        _ => {
+            let token = &tokens[argument_cursor];
+            return Err(ParseError::syntax(
+                "Assigning to rvalue",
+                token.start,
+                token.loc.start,
+            ));
+        }
+    }
+}
+
+/*
+* <UpdateExpression> ::= <LeftHandSideExpression> | <LeftHandSideExpression> ('++' | '--')
+*/
+pub fn update_expression(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let argument_result = left_hand_side_expression(tokens, cursor, context)?;
+    match tokens.get(argument_result.next_cursor) {
+        // 操作数和后缀运算符之间不能换行，`a\n++b` 中的 `++` 属于下一条语句
+        Some(token)
+            if (token._type.label == "++" || token._type.label == "--")
+                && !token.newline_before =>
+        {
+            return postfix_update(tokens, cursor, argument_result, context)
+        }
+        _ => return Ok(argument_result),
+    }
+}
+
+// 已经解析出操作数，下一个 token 是后缀的 ++ 或 --
+fn postfix_update(
+    tokens: &[Token],
+    cursor: usize,
+    argument_result: GramAnalysisResult<Expression>,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    check_update_argument(tokens, cursor, &argument_result.ast, context)?;
+    let start_token = &tokens[cursor];
+    let token = &tokens[argument_result.next_cursor];
+    let ast = UpdateExpression {
+        _type: ASTType::UpdateExpression,
+        start: start_token.start,
+        end: token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: token.loc.end,
+        },
+        operator: if token._type.label == "++" {
+            "++"
+        } else {
+            "--"
+        },
+        prefix: false,
+        argument: argument_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::UpdateExpression(Some(Box::new(ast))),
+        next_cursor: argument_result.next_cursor + 1,
+    });
+}
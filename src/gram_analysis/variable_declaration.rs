@@ -1,38 +1,46 @@
 use crate::{
+    error::ParseError,
     tokenizer::Token,
-    types::{ASTType, GramAnalysisResult, VariableDeclarator},
+    types::{ASTType, Context, GramAnalysisResult, SourceLocation, VariableDeclarator},
 };
 
-use super::{expression::expression, identifier::identifier};
+use super::{assignment_expression::assignment_expression, binding_pattern::binding_target};
 
+/*
+* <VariableDeclaration> ::= <BindingTarget> ('=' <AssignmentExpression>)?
+* 解构声明缺少初始值时由 check_declaration_initializers 报错，for-in / for-of 的头部除外
+*/
 pub fn variable_declaration(
     tokens: &[Token],
     cursor: usize,
-) -> Result<GramAnalysisResult<VariableDeclarator>, &str> {
-    if let Some(start_token) = tokens.get(cursor) {
-        if let Ok(identifier_result) = identifier(tokens, cursor) {
-            if let Some(lookaheadSymbol) = tokens.get(identifier_result.next_cursor) {
-                if lookaheadSymbol._type.label == "=" {
-                    if let Ok(expression_result) =
-                        expression(tokens, identifier_result.next_cursor + 1)
-                    {
-                        if let Some(end_token) = tokens.get(expression_result.next_cursor - 1) {
-                            let variable_declarator = VariableDeclarator {
-                                _type: ASTType::VariableDeclarator,
-                                start: start_token.start,
-                                end: end_token.end,
-                                id: identifier_result.ast,
-                                init: expression_result.ast,
-                            };
-                            return Ok(GramAnalysisResult {
-                                ast: variable_declarator,
-                                next_cursor: expression_result.next_cursor,
-                            });
-                        }
-                    }
-                }
-            }
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<VariableDeclarator>, ParseError> {
+    let id_result = binding_target(tokens, cursor, context)?;
+    let mut init = None;
+    let mut next_cursor = id_result.next_cursor;
+    if let Some(lookahead_symbol) = tokens.get(next_cursor) {
+        if lookahead_symbol._type.label == "=" {
+            let expression_result = assignment_expression(tokens, next_cursor + 1, no_in, context)?;
+            init = Some(expression_result.ast);
+            next_cursor = expression_result.next_cursor;
         }
     }
-    return Err("");
+    let start_token = &tokens[cursor];
+    let end_token = &tokens[next_cursor - 1];
+    let variable_declarator = VariableDeclarator {
+        _type: ASTType::VariableDeclarator,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        id: id_result.ast,
+        init,
+    };
+    return Ok(GramAnalysisResult {
+        ast: variable_declarator,
+        next_cursor,
+    });
 }
@@ -1,33 +1,31 @@
-use crate::{tokenizer::Token, types::VariableDeclarator};
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{Context, VariableDeclarator},
+};
 
 use super::variable_declaration::variable_declaration;
 
-pub struct VariableDeclarationListResult<'a> {
-    pub list: Vec<VariableDeclarator<'a>>,
+pub struct VariableDeclarationListResult {
+    pub list: Vec<VariableDeclarator>,
     pub next_cursor: usize,
 }
 
 pub fn variable_declaration_list(
     tokens: &[Token],
     cursor: usize,
-) -> Result<VariableDeclarationListResult, &str> {
+    no_in: bool,
+    context: &Context,
+) -> Result<VariableDeclarationListResult, ParseError> {
     let mut next_cursor = cursor;
     let mut list = Vec::new();
     loop {
-        if let Ok(result) = variable_declaration(tokens, next_cursor) {
-            next_cursor = result.next_cursor;
-            list.push(result.ast);
-            if let Some(token) = tokens.get(next_cursor) {
-                if token._type.label == "," {
-                    next_cursor += 1;
-                } else {
-                    break;
-                }
-            } else {
-                break;
-            }
-        } else {
-            return Err("");
+        let result = variable_declaration(tokens, next_cursor, no_in, context)?;
+        next_cursor = result.next_cursor;
+        list.push(result.ast);
+        match tokens.get(next_cursor) {
+            Some(token) if token._type.label == "," => next_cursor += 1,
+            _ => break,
         }
     }
     return Ok(VariableDeclarationListResult { list, next_cursor });
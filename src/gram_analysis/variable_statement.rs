@@ -1,71 +1,174 @@
 use crate::{
+    error::ParseError,
     tokenizer::{MyOption, Token},
-    types::{ASTType, Body, DeclarationKind, GramAnalysisResult, VariableDeclaration},
+    types::{
+        ASTType, Body, Context, DeclarationKind, GramAnalysisResult, Pattern, SourceLocation,
+        VariableDeclaration,
+    },
 };
 
 use super::{
-    variable_declaration::variable_declaration,
+    binding_pattern::bound_identifiers, identifier::is_contextual, statement::consume_semicolon,
     variable_declaration_list::variable_declaration_list,
 };
 
+/// `let` 不是保留字，之后是绑定名或解构模式时才是声明，否则是标识符，如 let = 1、let.x
+pub fn is_let_declaration(tokens: &[Token], cursor: usize) -> bool {
+    if !is_contextual(tokens, cursor, "let") {
+        return false;
+    }
+    match tokens.get(cursor + 1) {
+        // let in x、let instanceof x 中 let 是标识符
+        Some(token) => match &token._type.keyword {
+            MyOption(Some(keyword)) => return keyword != "in" && keyword != "instanceof",
+            MyOption(None) => return matches!(token._type.label.as_str(), "name" | "[" | "{"),
+        },
+        None => return false,
+    }
+}
+
+// let 和 const 声明的名称在任何模式下都不能是 let，如 let let = 1、const [let] = a
+fn check_lexical_names(ast: &VariableDeclaration) -> Result<(), ParseError> {
+    if matches!(ast.kind, DeclarationKind::Var) {
+        return Ok(());
+    }
+    let mut identifiers = Vec::new();
+    for declarator in &ast.declarations {
+        bound_identifiers(&declarator.id, &mut identifiers);
+    }
+    for identifier in identifiers {
+        if identifier.name == "let" {
+            return Err(ParseError::syntax(
+                "let is disallowed as a lexically bound name",
+                identifier.start,
+                identifier.loc.start,
+            ));
+        }
+    }
+    return Ok(());
+}
+
+/*
+* <VariableStatement> ::= ('var' | 'let' | 'const') <VariableDeclarationList> ';'
+*/
 pub fn variable_statement(
     tokens: &[Token],
     cursor: usize,
-) -> Result<GramAnalysisResult<Body>, &str> {
-    if let Some(token) = tokens.get(cursor) {
-        println!("variable_statement");
-        let mut ast = VariableDeclaration {
-            _type: ASTType::VariableDeclaration,
-            start: token.start,
-            end: token.end,
-            declarations: Vec::new(),
-            kind: DeclarationKind::Let,
-        };
-        if let MyOption(keywordOption) = &token._type.keyword {
-            if let Some(keyword) = keywordOption {
-                if keyword == "let" || keyword == "var" {
-                    if keyword == "let" {
-                        ast.kind = DeclarationKind::Let;
-                    } else {
-                        ast.kind = DeclarationKind::Var;
-                    }
-                    if let Ok(mut result) = variable_declaration_list(tokens, cursor + 1) {
-                        ast.declarations.append(&mut result.list);
-                        ast.end = result.next_cursor - 1;
-                        if let Some(next_token) = tokens.get(result.next_cursor) {
-                            if next_token._type.label == ";" {
-                                return Ok(GramAnalysisResult {
-                                    ast: Body::VariableDeclaration(ast),
-                                    next_cursor: result.next_cursor + 1,
-                                });
-                            }
-                        }
-                        return Ok(GramAnalysisResult {
-                            ast: Body::VariableDeclaration(ast),
-                            next_cursor: result.next_cursor,
-                        });
-                    }
-                } else if keyword == "const" {
-                    ast.kind = DeclarationKind::Const;
-                    if let Ok(result) = variable_declaration(tokens, cursor + 1) {
-                        ast.declarations.push(result.ast);
-                        ast.end = result.next_cursor - 1;
-                        if let Some(next_token) = tokens.get(result.next_cursor) {
-                            if next_token._type.label == ";" {
-                                return Ok(GramAnalysisResult {
-                                    ast: Body::VariableDeclaration(ast),
-                                    next_cursor: result.next_cursor + 1,
-                                });
-                            }
-                        }
-                        return Ok(GramAnalysisResult {
-                            ast: Body::VariableDeclaration(ast),
-                            next_cursor: result.next_cursor,
-                        });
-                    }
-                }
-            }
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let result = variable_declarations(tokens, cursor, false, context)?;
+    let mut ast = result.ast;
+    check_declaration_initializers(&ast)?;
+    let next_cursor = consume_semicolon(tokens, result.next_cursor)?;
+    ast.end = tokens[next_cursor - 1].end;
+    ast.loc.end = tokens[next_cursor - 1].loc.end;
+    return Ok(GramAnalysisResult {
+        ast: Body::VariableDeclaration(ast),
+        next_cursor,
+    });
+}
+
+/// 只解析声明本身，不包括末尾的 ';'，for 语句的头部也使用它
+pub fn variable_declarations(
+    tokens: &[Token],
+    cursor: usize,
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<VariableDeclaration>, ParseError> {
+    let token = match tokens.get(cursor) {
+        Some(token) => token,
+        None => {
+            return Err(ParseError::unexpected(
+                tokens,
+                cursor,
+                "'var', 'let' or 'const'",
+            ))
+        }
+    };
+    let kind = match &token._type.keyword {
+        _ if is_contextual(tokens, cursor, "let") => DeclarationKind::Let,
+        MyOption(Some(keyword)) if keyword == "var" => DeclarationKind::Var,
+        MyOption(Some(keyword)) if keyword == "const" => DeclarationKind::Const,
+        _ => {
+            return Err(ParseError::unexpected(
+                tokens,
+                cursor,
+                "'var', 'let' or 'const'",
+            ))
+        }
+    };
+    let result = variable_declaration_list(tokens, cursor + 1, no_in, context)?;
+    let end_token = &tokens[result.next_cursor - 1];
+    let ast = VariableDeclaration {
+        _type: ASTType::VariableDeclaration,
+        start: token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: token.loc.start,
+            end: end_token.loc.end,
+        },
+        declarations: result.list,
+        kind,
+    };
+    check_lexical_names(&ast)?;
+    return Ok(GramAnalysisResult {
+        ast,
+        next_cursor: result.next_cursor,
+    });
+}
+
+/// for-in / for-of 以外，const 声明和解构声明必须有初始值
+pub fn check_declaration_initializers(ast: &VariableDeclaration) -> Result<(), ParseError> {
+    for declarator in &ast.declarations {
+        if declarator.init.is_some() {
+            continue;
         }
+        if matches!(ast.kind, DeclarationKind::Const) {
+            return Err(ParseError::syntax(
+                "Missing initializer in const declaration",
+                declarator.end,
+                declarator.loc.end,
+            ));
+        } else if !matches!(declarator.id, Pattern::Identifier(_)) {
+            return Err(ParseError::syntax(
+                "Complex binding patterns require an initialization value",
+                declarator.end,
+                declarator.loc.end,
+            ));
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::test_support::{assert_errors, assert_json, script};
+
+    #[test]
+    fn let_as_lexically_bound_name() {
+        let message = "let is disallowed as a lexically bound name";
+        assert_errors(
+            script,
+            &[
+                ("let let = 1", message),
+                ("const let = 1", message),
+                ("let [a, let] = b", message),
+                ("const { a: let } = b", message),
+                ("for (let let of a);", message),
+                ("for (const let in a);", message),
+                ("'use strict'; var let", "The keyword 'let' is reserved"),
+            ],
+        );
+        assert_json(
+            script,
+            &[
+                ("var let = 1", r#""name":"let""#),
+                (
+                    "let = 1",
+                    r#""left":{"type":"Identifier","start":0,"end":3,"name":"let"}"#,
+                ),
+                ("for (var let of a);", r#""type":"ForOfStatement""#),
+            ],
+        );
     }
-    return Err("");
 }
@@ -0,0 +1,52 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Body, Context, GramAnalysisResult, SourceLocation, WithStatement},
+};
+
+use super::{
+    expression::parenthesized_expression,
+    statement::{substatement, SubstatementPosition},
+};
+
+/*
+* <WithStatement> ::= 'with' '(' <Expression> ')' <Statement>
+* 严格模式（包括模块）中不允许使用
+*/
+pub fn with_statement(
+    tokens: &[Token],
+    cursor: usize,
+    context: &Context,
+) -> Result<GramAnalysisResult<Body>, ParseError> {
+    let start_token = &tokens[cursor];
+    if context.strict {
+        return Err(ParseError::syntax(
+            "'with' in strict mode",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+    let object_result = parenthesized_expression(tokens, cursor + 1, context)?;
+    let body_result = substatement(
+        tokens,
+        object_result.next_cursor,
+        SubstatementPosition::Body,
+        context,
+    )?;
+    let end_token = &tokens[body_result.next_cursor - 1];
+    let ast = WithStatement {
+        _type: ASTType::WithStatement,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        object: object_result.ast,
+        body: body_result.ast,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Body::WithStatement(Some(Box::new(ast))),
+        next_cursor: body_result.next_cursor,
+    });
+}
@@ -0,0 +1,74 @@
+use crate::{
+    error::ParseError,
+    tokenizer::Token,
+    types::{ASTType, Context, Expression, GramAnalysisResult, SourceLocation, YieldExpression},
+};
+
+use super::{assignment_expression::assignment_expression, identifier::is_contextual};
+
+// yield 之后换行或者紧跟这些符号时没有操作数，如 [yield]、f(yield, 1)
+fn has_argument(tokens: &[Token], cursor: usize) -> bool {
+    match tokens.get(cursor + 1) {
+        Some(next_token) => {
+            return !next_token.newline_before
+                && !matches!(
+                    next_token._type.label.as_str(),
+                    ")" | "]" | "}" | "," | ";" | ":" | "?" | "=>" | "in"
+                );
+        }
+        None => return false,
+    }
+}
+
+/*
+* <YieldExpression> ::= 'yield'
+*                     | 'yield' [no LineTerminator here] <AssignmentExpression>
+*                     | 'yield' [no LineTerminator here] '*' <AssignmentExpression>
+* 只在生成器中解析，其余位置的 yield 由调用方按标识符处理
+*/
+pub fn yield_expression(
+    tokens: &[Token],
+    cursor: usize,
+    no_in: bool,
+    context: &Context,
+) -> Result<GramAnalysisResult<Expression>, ParseError> {
+    let start_token = match tokens.get(cursor) {
+        Some(token) if is_contextual(tokens, cursor, "yield") => token,
+        _ => return Err(ParseError::unexpected(tokens, cursor, "'yield'")),
+    };
+    if context.in_parameters {
+        return Err(ParseError::syntax(
+            "Yield expression cannot be a default value",
+            start_token.start,
+            start_token.loc.start,
+        ));
+    }
+    let mut next_cursor = cursor + 1;
+    let mut delegate = false;
+    let mut argument = None;
+    if has_argument(tokens, cursor) {
+        delegate = tokens[next_cursor]._type.label == "*";
+        if delegate {
+            next_cursor += 1;
+        }
+        let argument_result = assignment_expression(tokens, next_cursor, no_in, context)?;
+        argument = Some(argument_result.ast);
+        next_cursor = argument_result.next_cursor;
+    }
+    let end_token = &tokens[next_cursor - 1];
+    let ast = YieldExpression {
+        _type: ASTType::YieldExpression,
+        start: start_token.start,
+        end: end_token.end,
+        loc: SourceLocation {
+            start: start_token.loc.start,
+            end: end_token.loc.end,
+        },
+        delegate,
+        argument,
+    };
+    return Ok(GramAnalysisResult {
+        ast: Expression::YieldExpression(Some(Box::new(ast))),
+        next_cursor,
+    });
+}
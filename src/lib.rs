@@ -0,0 +1,101 @@
+// ESTree 风格的命名（如 sourceType、beforeExpr）与 acorn 保持一致
+#![allow(non_snake_case, non_camel_case_types, non_upper_case_globals)]
+#![allow(clippy::needless_return, clippy::large_enum_variant)]
+
+pub mod error;
+pub mod estree;
+pub mod tokenizer;
+pub mod types;
+
+pub mod utils {
+    pub mod escape;
+    pub mod line_index;
+    #[cfg(test)]
+    pub mod test_support;
+}
+
+pub mod gram_analysis {
+    pub mod arguments;
+    pub mod array_expression;
+    pub mod arrow_function;
+    pub mod assignment_expression;
+    pub mod await_expression;
+    pub mod binary_expression;
+    pub mod binding_pattern;
+    pub mod block_statement;
+    pub mod break_statement;
+    pub mod class_declaration;
+    pub mod class_element;
+    pub mod class_expression;
+    pub mod conditional_expression;
+    pub mod continue_statement;
+    pub mod debugger_statement;
+    pub mod directive_prologue;
+    pub mod element_list;
+    pub mod empty_statement;
+    pub mod export_declaration;
+    pub mod expression;
+    pub mod expression_statement;
+    pub mod formal_parameters;
+    pub mod function_body;
+    pub mod function_declaration;
+    pub mod function_expression;
+    pub mod identifier;
+    pub mod if_statement;
+    pub mod import_declaration;
+    pub mod import_expression;
+    pub mod iteration_statement;
+    pub mod labelled_statement;
+    pub mod left_hand_side_expression;
+    pub mod literal_expression;
+    pub mod module_item;
+    pub mod object_expression;
+    pub mod object_property;
+    pub mod primary_expression;
+    pub mod program;
+    pub mod return_statement;
+    pub mod spread_element;
+    pub mod statement;
+    pub mod statement_list;
+    pub mod switch_statement;
+    pub mod template_literal;
+    pub mod throw_statement;
+    pub mod try_statement;
+    pub mod unary_expression;
+    pub mod update_expression;
+    pub mod variable_declaration;
+    pub mod variable_declaration_list;
+    pub mod variable_statement;
+    pub mod with_statement;
+    pub mod yield_expression;
+}
+
+pub use crate::error::{ParseError, ParseErrorKind};
+pub use crate::estree::{to_json, tokens_to_json, JsonOptions};
+pub use crate::gram_analysis::program::program;
+pub use crate::tokenizer::{tokenizer, Token};
+pub use crate::types::{Program, SourceType};
+use crate::utils::line_index::LineIndex;
+
+/// `parse` 的配置项
+pub struct ParseOptions {
+    pub source_type: SourceType,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            source_type: SourceType::module,
+        }
+    }
+}
+
+/// 对源码做词法、语法分析，返回 Program AST
+pub fn parse(source: &str, options: ParseOptions) -> Result<Program, ParseError> {
+    let tokens = tokenizer(source)?;
+    let mut ast = program(&tokens, options.source_type)?;
+    // 与 acorn 一致，Program 覆盖整个源码（包括末尾的空白和注释）
+    ast.end = source.len();
+    ast.loc.end = LineIndex::new(source).position(source.len());
+    return Ok(ast);
+}
@@ -1,37 +1,13 @@
-mod tokenizer;
+#![allow(clippy::needless_return)]
 
-mod types;
+use std::io::Read;
+use std::process::exit;
 
-mod utils {
-    pub mod token_trie;
-}
-
-mod gram_analysis {
-    pub mod array_expression;
-    pub mod assignment_expression;
-    pub mod block_statement;
-    pub mod conditional_expression;
-    pub mod element_list;
-    pub mod expression;
-    pub mod identifier;
-    pub mod literal_expression;
-    pub mod logical_and_expression;
-    pub mod logical_or_expression;
-    pub mod object_expression;
-    pub mod object_property;
-    pub mod program;
-    pub mod statement;
-    pub mod statement_list;
-    pub mod variable_declaration;
-    pub mod variable_declaration_list;
-    pub mod variable_statement;
-    pub mod function_expression;
-}
-
-use crate::gram_analysis::program::program;
-use crate::tokenizer::{tokenizer, TokenValue};
+use l6::{
+    parse, to_json, tokenizer, tokens_to_json, JsonOptions, ParseError, ParseOptions, SourceType,
+};
 
-/**
+/*
 * <Program> ::= <StatementList>
 * <StatementList> ::= <Statement> | <Statement> <StatementList>
 * <Statement> ::= <BlockStatement>
@@ -58,36 +34,170 @@ use crate::tokenizer::{tokenizer, TokenValue};
 * <LogicalOrExpression> ::= ('!')+ <Expression>
 * <Expression> ::= <LiteralExpression> | <ArrayExpression> | <ObjectExpression> | <NewExpression> | <CallExpression> | <ConditionalExpression> | <AssignmentExpression> | <LogicalOrExpression> | <FunctionExpression> | <ArrowFunctionExpression>
 * <Operator> ::= '=' | '+=' | '-=' | '*=' | '/=' | '%=' | '==' | '===' | '!=' | '!==' | '+' | '-' | '*' | '/' | '%' | '>' | '>=' | '<' | '<=' | '&&' | '||' | '^' | '~' | '|' | '&' | '<<' | '>>' | '>>>'
-* <Identifier> ::= /^[a-zA-Z_$\u00A0-\uFFFF][a-zA-Z0-9_$\u00A0-\uFFFF]*$/
+* <Identifier> ::= <IdentifierStart> <IdentifierPart>*
+* <IdentifierStart> ::= <ID_Start> | '$' | '_' | '\' <UnicodeEscapeSequence>
+* <IdentifierPart> ::= <ID_Continue> | '$' | <ZWNJ> | <ZWJ> | '\' <UnicodeEscapeSequence>
 * <ArrayExpression> ::= '[' <ElementList>? ']'
 * <ElementList> ::= (<Identifier> | <Expression>) (',' <ElementList>)*
 * <ObjectExpression> ::= '{' <ObjectProperty>? '}'
 * <ObjectProperty> ::= ('[' <Identifier> ']' ':' | <Identifier> | <string>) <Expression> (',' <ObjectProperty>)*
 */
 
-fn main() {
-    let code = "const t1 = [1, 2];{let t2 = \"abc\"}";
+const USAGE: &str =
+    "usage: l6 <tokens|ast> [file...] [--format json|debug] [--source-type script|module]
+
+Reads from stdin when no file is given or a file is \"-\".
+Exits with a nonzero status if any file fails.";
+
+enum Command {
+    Tokens,
+    Ast,
+}
 
-    match tokenizer(code) {
-        Ok(tokens) => {
-            for token in tokens.iter() {
-                // match &token.value {
-                //     TokenValue::String(string) => {
-                //         println!("tokens: {}", string)
-                //     }
-                //     TokenValue::RegexpValue(regexp_value) => {
-                //         println!("tokens: {}", regexp_value.value);
-                //     }
-                //     TokenValue::None => {
-                //         println!("tokens: {}", token._type.label);
-                //     }
-                // }
-                println!("{}", &token)
+enum Format {
+    Json,
+    Debug,
+}
+
+struct Args {
+    command: Command,
+    files: Vec<String>,
+    format: Format,
+    source_type: SourceType,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let command = match args.next().as_deref() {
+        Some("tokens") => Command::Tokens,
+        Some("ast") => Command::Ast,
+        Some(command) => return Err(format!("unknown command '{}'", command)),
+        None => return Err(String::from("missing command")),
+    };
+    let mut files = Vec::new();
+    let mut format = Format::Json;
+    let mut source_type = SourceType::module;
+    while let Some(arg) = args.next() {
+        // 同时支持 `--format json` 和 `--format=json`
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => {
+                (name.to_string(), Some(value.to_string()))
             }
-            program(&tokens);
+            _ => (arg.clone(), None),
+        };
+        match name.as_str() {
+            "--format" | "--source-type" => {
+                let value = match inline_value.or_else(|| args.next()) {
+                    Some(value) => value,
+                    None => return Err(format!("missing value for {}", name)),
+                };
+                if name == "--format" {
+                    format = match value.as_str() {
+                        "json" => Format::Json,
+                        "debug" => Format::Debug,
+                        _ => return Err(format!("unknown format '{}'", value)),
+                    };
+                } else {
+                    source_type = match value.as_str() {
+                        "script" => SourceType::script,
+                        "module" => SourceType::module,
+                        _ => return Err(format!("unknown source type '{}'", value)),
+                    };
+                }
+            }
+            "-h" | "--help" => return Err(String::new()),
+            _ if name.starts_with("--") => return Err(format!("unknown option '{}'", name)),
+            _ => files.push(arg),
+        }
+    }
+    return Ok(Args {
+        command,
+        files,
+        format,
+        source_type,
+    });
+}
+
+fn read_source(file: Option<&str>) -> Result<(String, String), String> {
+    match file {
+        None | Some("-") => {
+            let mut source = String::new();
+            std::io::stdin()
+                .read_to_string(&mut source)
+                .map_err(|err| format!("failed to read stdin: {}", err))?;
+            return Ok((source, String::from("<stdin>")));
+        }
+        Some(path) => {
+            let source = std::fs::read_to_string(path)
+                .map_err(|err| format!("failed to read {}: {}", path, err))?;
+            return Ok((source, String::from(path)));
+        }
+    }
+}
+
+// 处理单个输入，返回退出码：0 成功，1 分析失败，2 读取失败
+fn run(args: &Args, file: Option<&str>) -> i32 {
+    let (source, file_name) = match read_source(file) {
+        Ok(result) => result,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            return 2;
+        }
+    };
+    let json_options = JsonOptions {
+        locations: true,
+        pretty: true,
+    };
+    let result: Result<String, ParseError> = match args.command {
+        Command::Tokens => tokenizer(&source).map(|tokens| match args.format {
+            Format::Json => tokens_to_json(&tokens, &source, &json_options),
+            Format::Debug => tokens
+                .iter()
+                .map(|token| token.to_string())
+                .collect::<Vec<String>>()
+                .join("\n"),
+        }),
+        Command::Ast => parse(
+            &source,
+            ParseOptions {
+                source_type: args.source_type,
+            },
+        )
+        .map(|ast| match args.format {
+            Format::Json => to_json(&ast, &source, &json_options),
+            Format::Debug => format!("{:#?}", ast),
+        }),
+    };
+    match result {
+        Ok(output) => {
+            println!("{}", output);
+            return 0;
         }
         Err(err) => {
-            println!("{}", err);
+            eprint!("{}", err.render(&source, &file_name));
+            return 1;
         }
     }
 }
+
+fn main() {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            if message.is_empty() {
+                println!("{}", USAGE);
+                return;
+            }
+            eprintln!("error: {}\n\n{}", message, USAGE);
+            exit(2);
+        }
+    };
+    if args.files.is_empty() {
+        exit(run(&args, None));
+    }
+    // 逐个处理，某个文件失败不影响后续文件，退出码取最严重的失败
+    let mut status = 0;
+    for file in &args.files {
+        status = status.max(run(&args, Some(file)));
+    }
+    exit(status);
+}
@@ -1,41 +1,16 @@
 // js词法token解析器
 
-use crate::utils::token_trie::{TrieMethods, TrieNode};
-use regex::Regex;
-use std::{cell::RefCell, fmt::Display, matches};
-
-enum Status {
-    Initial,
-    Keywords,
-    Punctuators,
-    Identifier,
-    Literal,
-    Template,
-    Comment,
-}
-
-enum LiteralStatus {
-    Initial,
-    String,
-    Int,
-    Float,
-    RegularExpression,
-}
+use crate::error::ParseError;
+use crate::types::{Position, SourceLocation};
+use crate::utils::escape::{cook_string, read_unicode_escape};
+use crate::utils::line_index::{is_line_terminator, is_whitespace};
+use std::fmt::Display;
+use unicode_id_start::{is_id_continue, is_id_start};
 
-enum TemplateStatus {
-    Template,
-    Expression,
-}
-
-enum CommentStatus {
-    SingleLine,
-    Multiline,
-    None,
-}
-
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct MyOption<T>(pub Option<T>);
 
+#[derive(Debug)]
 pub struct TokenType {
     pub label: String,
     pub keyword: MyOption<String>,
@@ -77,7 +52,7 @@ impl Display for TokenType {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub enum TokenValue {
     String(String),
     RegexpValue(RegexpValue),
@@ -94,11 +69,19 @@ impl Display for TokenValue {
     }
 }
 
+#[derive(Debug)]
 pub struct Token {
     pub _type: TokenType,
     pub value: TokenValue,
     pub start: usize,
     pub end: usize,
+    pub loc: SourceLocation,
+    // 与上一个 token 之间是否有换行（包括注释中的换行），用于自动插入分号和 [no LineTerminator here] 的限制
+    pub newline_before: bool,
+    // 标识符中是否含有 \uXXXX 转义，含有转义的关键字不能作为关键字使用
+    pub escaped: bool,
+    // 是否是 get、async、let 这样的上下文关键字（不含转义），只有这样的 name 才可能被解释为关键字
+    pub contextual_keyword: bool,
 }
 
 impl Clone for Token {
@@ -106,8 +89,12 @@ impl Clone for Token {
         Token {
             _type: self._type.clone(),
             value: self.value.clone(),
-            start: self.start.clone(),
-            end: self.end.clone(),
+            start: self.start,
+            end: self.end,
+            loc: self.loc,
+            newline_before: self.newline_before,
+            escaped: self.escaped,
+            contextual_keyword: self.contextual_keyword,
         }
     }
 }
@@ -116,13 +103,20 @@ impl Display for Token {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{{\n  _type: {},\n  value: {},\n  start: {},\n  end: {}\n}}",
-            &self._type, &self.value, &self.start, &self.end
+            "{{\n  _type: {},\n  value: {},\n  start: {},\n  end: {},\n  loc: {{ start: {{ line: {}, column: {} }}, end: {{ line: {}, column: {} }} }}\n}}",
+            &self._type,
+            &self.value,
+            &self.start,
+            &self.end,
+            &self.loc.start.line,
+            &self.loc.start.column,
+            &self.loc.end.line,
+            &self.loc.end.column
         )
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct RegexpValue {
     pub pattern: String,
     pub flags: String,
@@ -139,1516 +133,1280 @@ impl Display for RegexpValue {
     }
 }
 
-fn get_literal_status(
-    words: &str,
-    tokens: &Vec<Token>,
-    punctuators_trie_node: &TrieNode,
-) -> LiteralStatus {
-    // 字符串正则
-    let string_regex = Regex::new(r#"^("|'|`)"#).unwrap();
-    // 整型正则
-    let int_regex = Regex::new(r#"^\d+$"#).unwrap();
-    // 浮点数正则
-    let float_regex = Regex::new(r#"^\d+(\.\d*)?$"#).unwrap();
-    // 正则表达式正则
-    let regex_regex = Regex::new(r"^/[^/]?").unwrap();
-
-    if string_regex.is_match(words) {
-        return LiteralStatus::String;
-    } else if int_regex.is_match(words) {
-        return LiteralStatus::Int;
-    } else if float_regex.is_match(words) {
-        return LiteralStatus::Float;
-    } else if punctuators_trie_node.exist(&tokens.last().unwrap()._type.label)
-        && regex_regex.is_match(words)
-    {
-        return LiteralStatus::RegularExpression;
-    } else {
-        return LiteralStatus::Initial;
-    }
+// 以 0 开头的多位整数，如 010、08，其中只含 0-7 的是旧式八进制
+fn is_legacy_octal_like(number: &str) -> bool {
+    let bytes = number.as_bytes();
+    return bytes.len() > 1 && bytes[0] == b'0' && (bytes[1].is_ascii_digit() || bytes[1] == b'_');
 }
 
-fn no_escape(number_of_escape_characters: u16) -> bool {
-    number_of_escape_characters % 2 == 0
+fn is_legacy_octal(number: &str) -> bool {
+    return is_legacy_octal_like(number)
+        && number
+            .bytes()
+            .all(|byte| matches!(byte, b'0'..=b'7' | b'_'));
 }
 
-struct LexTemplateActionExtractParams<'a> {
-    status: &'a mut Status,
-    tokens: &'a mut Vec<Token>,
-    under_template: &'a mut bool,
-    is_start: bool,
+// 字符是否还属于当前的数字字面量，标识符字符也算在内，由 check_numeric_literal 报错
+fn continues_number(number: &str, char: char) -> bool {
+    if char.is_alphanumeric() || char == '_' || char == '$' {
+        return true;
+    }
+    let bytes = number.as_bytes();
+    let has_radix_prefix = matches!(bytes, [b'0', b'x' | b'X' | b'o' | b'O' | b'b' | b'B', ..]);
+    match char {
+        // 1.5、.5e3，旧式八进制没有小数部分
+        '.' => {
+            return !has_radix_prefix
+                && !is_legacy_octal(number)
+                && bytes
+                    .iter()
+                    .all(|byte| byte.is_ascii_digit() || *byte == b'_')
+        }
+        // 1e+5、1e-5
+        '+' | '-' => return !has_radix_prefix && matches!(bytes.last(), Some(b'e' | b'E')),
+        _ => return false,
+    }
 }
 
-fn create_lex_template_action_extract_params<'a>(
-    status: &'a mut Status,
-    tokens: &'a mut Vec<Token>,
-    under_template: &'a mut bool,
-    is_start: bool,
-) -> LexTemplateActionExtractParams<'a> {
-    return LexTemplateActionExtractParams {
-        status,
-        tokens,
-        under_template,
-        is_start,
-    };
+// 读取一段可以用 '_' 分隔的数字，返回结束位置，没有数字时返回 index 本身
+fn read_digits(bytes: &[u8], index: usize, radix: u32) -> Result<usize, (&'static str, usize)> {
+    let mut end = index;
+    while let Some(byte) = bytes.get(end) {
+        if *byte == b'_' {
+            if end == index {
+                return Err((
+                    "Numeric separator is not allowed at the first of digits",
+                    end,
+                ));
+            }
+            if bytes[end - 1] == b'_' {
+                return Err(("Only one underscore is allowed as numeric separator", end));
+            }
+        } else if !(*byte as char).is_digit(radix) {
+            break;
+        }
+        end += 1;
+    }
+    if end > index && bytes[end - 1] == b'_' {
+        return Err((
+            "Numeric separators are not allowed at the end of numeric literals",
+            end - 1,
+        ));
+    }
+    return Ok(end);
 }
 
-fn create_lex_template_action(
-) -> Box<dyn FnMut(char, usize, usize, &mut LexTemplateActionExtractParams) -> Result<(), String>> {
-    let mut template_status = Vec::new();
-    let mut template_stack: Vec<char> = Vec::new();
-    let mut number_of_escape_characters: u16 = 0;
-    let mut cache = String::new();
-    let mut token = String::new();
-
-    let closure = move |char: char,
-                        start: usize,
-                        next_cursor_index: usize,
-                        extract_params: &mut LexTemplateActionExtractParams| {
-        if extract_params.is_start {
-            template_status.push(TemplateStatus::Template);
+/*
+* <NumericLiteral> ::= <DecimalLiteral> | <DecimalBigIntegerLiteral> | <NonDecimalIntegerLiteral> 'n'?
+*                    | <LegacyOctalIntegerLiteral>
+* 检查读取到的数字字面量，出错时返回错误信息和出错字符在字面量中的位置
+*/
+fn check_numeric_literal(number: &str) -> Result<(), (&'static str, usize)> {
+    let bytes = number.as_bytes();
+    let mut index;
+    let radix = match bytes {
+        [b'0', b'x' | b'X', ..] => Some((16, "Expected number in radix 16")),
+        [b'0', b'o' | b'O', ..] => Some((8, "Expected number in radix 8")),
+        [b'0', b'b' | b'B', ..] => Some((2, "Expected number in radix 2")),
+        _ => None,
+    };
+    if let Some((radix, message)) = radix {
+        index = read_digits(bytes, 2, radix)?;
+        if index == 2 {
+            return Err((message, index));
         }
-        let _current_template_status = template_status.last();
-        match _current_template_status {
-            Some(current_template_status) => match current_template_status {
-                TemplateStatus::Template => {
-                    if !cache.is_empty() && char != '{' {
-                        token.push_str(&cache);
-                        cache = String::new();
-                    }
-                    if char == '`' && no_escape(number_of_escape_characters) {
-                        // 判断是模板字符串开始还是结束
-                        // 结束
-                        if !extract_params.is_start {
-                            extract_params.tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("template"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: true,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(String::from(&token)),
-                                start,
-                                end: next_cursor_index - 1,
-                            });
-                            extract_params.tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("`"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: true,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::None,
-                                start: next_cursor_index - 1,
-                                end: next_cursor_index,
-                            });
-
-                            token = String::new();
-                            template_status.pop();
-                            template_stack.pop();
-
-                            if template_stack.is_empty() {
-                                *extract_params.status = Status::Initial;
-                                *extract_params.under_template = false;
-                            } else {
-                                let current_template_status = template_status.last().unwrap();
-                                if let TemplateStatus::Expression = current_template_status {
-                                    *extract_params.status = Status::Initial;
-                                    *extract_params.under_template = true;
-                                }
-                            }
-                        } else {
-                            // 模板字符串开始
-                            template_stack.push('`');
-                            *extract_params.under_template = true;
-                            extract_params.tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("`"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: true,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::None,
-                                start: start,
-                                end: next_cursor_index,
-                            });
-                        }
-
-                        number_of_escape_characters = 0;
-                    } else if char == '\\' {
-                        number_of_escape_characters += 1;
-                        token.push(char);
-                    } else if char == '$' && no_escape(number_of_escape_characters) {
-                        if cache.is_empty() {
-                            cache.push(char);
-                        } else if cache.len() == 1 {
-                            token.push('$');
-                            cache = String::from('$');
-                        } else {
-                            return Err(String::from("Missing } in template expression"));
-                        }
-                    } else if char == '{' && no_escape(number_of_escape_characters) {
-                        if cache.is_empty() {
-                            token.push(char);
-                        } else if cache.len() == 1 {
-                            extract_params.tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("template"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: true,
-                                    startsExpr: true,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(String::from(&token)),
-                                start: start,
-                                end: next_cursor_index - 2,
-                            });
-                            extract_params.tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("${"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: true,
-                                    startsExpr: true,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::None,
-                                start: next_cursor_index - 2,
-                                end: next_cursor_index,
-                            });
-                            template_status.push(TemplateStatus::Expression);
-                            token = String::new();
-                            cache = String::new();
-                            *extract_params.status = Status::Initial;
-                        } else {
-                            return Err(String::from("Unexpected string"));
-                        }
-                    } else {
-                        number_of_escape_characters = 0;
-                        token.push(char);
-                    }
-                }
-                TemplateStatus::Expression => {
-                    if char == '}' && no_escape(number_of_escape_characters) {
-                        number_of_escape_characters = 0;
-                        template_status.pop();
-                        extract_params.tokens.push(Token {
-                            _type: TokenType {
-                                label: String::from("}"),
-                                keyword: MyOption(None),
-                                beforeExpr: false,
-                                startsExpr: false,
-                                isLoop: false,
-                                isAssign: false,
-                                prefix: false,
-                                postfix: false,
-                            },
-                            value: TokenValue::None,
-                            start: start,
-                            end: next_cursor_index,
-                        });
-                    } else if !extract_params.tokens.is_empty()
-                        && extract_params.tokens.last().unwrap()._type.label == "}"
-                    {
-                        template_status.pop();
-                        number_of_escape_characters = 0;
-                        if char == '$' {
-                            if cache.is_empty() {
-                                cache.push(char);
-                            } else if cache.len() == 1 {
-                                token.push('$');
-                                cache = String::from('$');
-                            } else {
-                                return Err(String::from("Missing } in template expression"));
-                            }
-                        } else if char == '`' {
-                            extract_params.tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("template"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: true,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(String::from(&token)),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            token = String::new();
-
-                            template_status.pop();
-                            template_stack.pop();
-
-                            if template_stack.is_empty() {
-                                *extract_params.status = Status::Initial;
-                                *extract_params.under_template = false;
-                            } else {
-                                let current_template_status = template_status.last().unwrap();
-                                if let TemplateStatus::Expression = current_template_status {
-                                    *extract_params.status = Status::Initial;
-                                    *extract_params.under_template = true;
-                                }
-                            }
-
-                            extract_params.tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("`"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: true,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::None,
-                                start: next_cursor_index - 1,
-                                end: next_cursor_index,
-                            });
-                        } else if char == '\\' {
-                            number_of_escape_characters += 1;
-                            token.push(char);
-                        } else {
-                            token.push(char);
-                        }
-                    }
-                }
-            },
-            None => {}
+        if bytes.get(index) == Some(&b'n') {
+            index += 1;
         }
-        return Ok(());
-    };
+    } else if is_legacy_octal_like(number) {
+        // 旧式八进制和 08、09 不能使用分隔符，也不能是 BigInt
+        if bytes[1] == b'_' {
+            return Err(("Numeric separator can not be used after leading 0", 1));
+        }
+        index = read_digits(bytes, 0, 10)?;
+        if let Some(separator) = number[..index].find('_') {
+            return Err((
+                "Numeric separator can not be used after leading 0",
+                separator,
+            ));
+        }
+        // 08.5、09e1 是十进制数，旧式八进制没有小数和指数部分
+        if !is_legacy_octal(&number[..index]) {
+            index = read_decimal_tail(bytes, index)?.0;
+        }
+    } else {
+        index = read_digits(bytes, 0, 10)?;
+        let (end, integer) = read_decimal_tail(bytes, index)?;
+        index = end;
+        if integer && bytes.get(index) == Some(&b'n') {
+            index += 1;
+        }
+    }
+    if index < bytes.len() {
+        return Err(("Identifier directly after number", index));
+    }
+    return Ok(());
+}
 
-    return Box::new(closure);
+// 十进制数的小数和指数部分，返回结束位置以及是否仍是整数
+fn read_decimal_tail(bytes: &[u8], index: usize) -> Result<(usize, bool), (&'static str, usize)> {
+    let mut end = index;
+    let mut integer = true;
+    if bytes.get(end) == Some(&b'.') {
+        integer = false;
+        end = read_digits(bytes, end + 1, 10)?;
+    }
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        integer = false;
+        let mut exponent_start = end + 1;
+        if matches!(bytes.get(exponent_start), Some(b'+' | b'-')) {
+            exponent_start += 1;
+        }
+        end = read_digits(bytes, exponent_start, 10)?;
+        if end == exponent_start {
+            return Err(("Invalid number", 0));
+        }
+    }
+    return Ok((end, integer));
 }
 
-struct LexRegularExpressionActionExtractParams<'a> {
-    status: &'a mut Status,
-    tokens: &'a mut Vec<Token>,
-    outer_token: &'a mut String,
-    punctuators_trie_node: &'a TrieNode,
-    flags_regex: Regex,
-    syntax_error_regex: Regex,
-    num_regex: Regex,
+// 根据上一个 token 判断 '/' 是正则的开始还是除号，')' 和 '}' 之后的情况由 Scanner 根据括号上下文判断
+fn regular_expression_allowed(tokens: &[Token]) -> bool {
+    // a.yield / 2、a.if / 2 中是属性名
+    let after_dot =
+        tokens.len() > 1 && matches!(tokens[tokens.len() - 2]._type.label.as_str(), "." | "?.");
+    match tokens.last() {
+        None => true,
+        Some(_) if after_dot => false,
+        Some(token) => match token._type.label.as_str() {
+            ")" | "]" | "}" | "++" | "--" => false,
+            "${" => true,
+            // yield /re/、await /re/、for (x of /re/)
+            "name" if token.contextual_keyword => {
+                matches!(&token.value, TokenValue::String(value) if matches!(value.as_str(), "await" | "of" | "yield"))
+            }
+            "name" | "number" | "string" | "regexp" | "template" | "`" => false,
+            "this" | "super" | "true" | "false" | "null" => false,
+            label => token._type.keyword.0.is_some() || is_punctuator(label.as_bytes()),
+        },
+    }
 }
 
-fn create_lex_regular_expression_action_extract_params<'a>(
-    status: &'a mut Status,
-    tokens: &'a mut Vec<Token>,
-    outer_token: &'a mut String,
-    punctuators_trie_node: &'a TrieNode,
-) -> LexRegularExpressionActionExtractParams<'a> {
-    return LexRegularExpressionActionExtractParams {
-        status,
-        tokens,
-        outer_token,
-        punctuators_trie_node,
-        flags_regex: Regex::new(r"[igmuy]").unwrap(),
-        syntax_error_regex: Regex::new(r"([(+?*]|\{\d+\})$").unwrap(),
-        num_regex: Regex::new(r"\d").unwrap(),
-    };
+const KEYWORDS: [&str; 36] = [
+    "var",
+    "const",
+    "function",
+    "if",
+    "while",
+    "for",
+    "switch",
+    "case",
+    "break",
+    "continue",
+    "catch",
+    "class",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "finally",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "try",
+    "typeof",
+    "void",
+    "with",
+    "enum",
+    "true",
+    "false",
+    "null",
+];
+
+// 只在特定位置有特殊含义的名称，词法上是普通的 name，由语法分析根据位置决定含义
+const CONTEXTUAL_KEYWORDS: [&str; 12] = [
+    "as", "async", "await", "from", "get", "let", "meta", "of", "set", "static", "target", "yield",
+];
+
+pub fn is_keyword(word: &str) -> bool {
+    return KEYWORDS.contains(&word);
 }
 
-fn create_lex_regular_expression_action() -> Box<
-    dyn FnMut(
-        char,
-        usize,
-        usize,
-        &mut LexRegularExpressionActionExtractParams,
-    ) -> Result<(), String>,
-> {
-    let mut cache = String::new();
-    let mut pattern_start = true;
-    let mut flags_start = false;
-    // 连续转义符数量
-    let mut number_of_escape_characters = 0;
-    // 未闭合的圆括号数量
-    let mut number_of_brackets = 0;
-    // 未闭合的方括号数量
-    let mut number_of_square_brackets = 0;
-    let mut token = String::new();
-    let mut pattern = String::new();
-    let mut flags = String::new();
-
-    let mut clear_cache: Box<dyn FnMut(&mut String, &mut String, &mut String)> = Box::new(
-        |token: &mut String, pattern: &mut String, cache: &mut String| {
-            token.push_str(cache);
-            pattern.push_str(cache);
-            *cache = String::new();
-        },
+fn is_punctuator(text: &[u8]) -> bool {
+    return matches!(
+        text,
+        b"=" | b";"
+            | b"("
+            | b")"
+            | b"{"
+            | b"}"
+            | b"["
+            | b"]"
+            | b"+"
+            | b"-"
+            | b"*"
+            | b"%"
+            | b"."
+            | b"!"
+            | b","
+            | b">"
+            | b"<"
+            | b"/"
+            | b"&"
+            | b"|"
+            | b"^"
+            | b"~"
+            | b"?"
+            | b":"
+            | b"=="
+            | b"!="
+            | b">="
+            | b"<="
+            | b"=>"
+            | b"+="
+            | b"-="
+            | b"*="
+            | b"/="
+            | b"%="
+            | b"&="
+            | b"|="
+            | b"^="
+            | b"&&"
+            | b"||"
+            | b"??"
+            | b"?."
+            | b"**"
+            | b"++"
+            | b"--"
+            | b"<<"
+            | b">>"
+            | b"==="
+            | b"!=="
+            | b"..."
+            | b">>>"
+            | b"**="
+            | b"<<="
+            | b">>="
+            | b"&&="
+            | b"||="
+            | b"??="
+            | b">>>="
     );
+}
 
-    let closure =
-        move |char: char,
-              start: usize,
-              next_cursor_index: usize,
-              extract_params: &mut LexRegularExpressionActionExtractParams| {
-            if char == '/' && extract_params.outer_token.is_empty() && token.is_empty() {
-                token.push(char);
-                pattern_start = true;
-            } else if char == '/'
-                && (!extract_params.outer_token.is_empty() || !token.is_empty())
-                && no_escape(number_of_escape_characters)
-                && pattern_start
-            {
-                if !extract_params.outer_token.is_empty() {
-                    token.push_str(extract_params.outer_token);
-                    pattern.push_str(extract_params.outer_token);
-                    *extract_params.outer_token = String::new();
+// 最长匹配的符号长度，最长的符号是 >>>=
+fn punctuator_length(bytes: &[u8]) -> usize {
+    for length in (1..=bytes.len().min(4)).rev() {
+        if is_punctuator(&bytes[..length]) {
+            return length;
+        }
+    }
+    return 0;
+}
+
+// ASCII 字符的分类，非 ASCII 字符单独按 Unicode 属性判断
+#[derive(Clone, Copy)]
+enum CharClass {
+    Whitespace,
+    LineTerminator,
+    Identifier,
+    Digit,
+    Quote,
+    Backtick,
+    Slash,
+    Punctuator,
+    // 标识符中的 \uXXXX 转义
+    Backslash,
+    // 类的私有名称 #x
+    Hash,
+    Invalid,
+}
+
+const CHAR_CLASSES: [CharClass; 128] = {
+    let mut table = [CharClass::Invalid; 128];
+    let mut byte = 0;
+    while byte < 128 {
+        table[byte] = match byte as u8 {
+            b' ' | b'\t' | 0x0B | 0x0C => CharClass::Whitespace,
+            b'\n' | b'\r' => CharClass::LineTerminator,
+            b'0'..=b'9' => CharClass::Digit,
+            b'"' | b'\'' => CharClass::Quote,
+            b'`' => CharClass::Backtick,
+            b'/' => CharClass::Slash,
+            b'a'..=b'z' | b'A'..=b'Z' | b'$' | b'_' => CharClass::Identifier,
+            b'=' | b';' | b'(' | b')' | b'{' | b'}' | b'[' | b']' | b'+' | b'-' | b'*' | b'%'
+            | b'.' | b'!' | b',' | b'>' | b'<' | b'&' | b'|' | b'^' | b'~' | b'?' | b':' => {
+                CharClass::Punctuator
+            }
+            b'\\' => CharClass::Backslash,
+            b'#' => CharClass::Hash,
+            _ => CharClass::Invalid,
+        };
+        byte += 1;
+    }
+    table
+};
+
+fn char_class(byte: u8) -> CharClass {
+    return CHAR_CLASSES[byte as usize];
+}
+
+// IdentifierStart：ID_Start 以及 $ 和 _
+fn is_identifier_start(char: char) -> bool {
+    return char == '$' || char == '_' || is_id_start(char);
+}
+
+// IdentifierPart：ID_Continue 以及 $、ZWNJ（U+200C）和 ZWJ（U+200D）
+fn is_identifier_part(char: char) -> bool {
+    return char == '$' || char == '\u{200C}' || char == '\u{200D}' || is_id_continue(char);
+}
+
+// 从 UTF-8 的首字节得到字符的字节数
+fn char_length(byte: u8) -> usize {
+    match byte {
+        0x00..=0x7F => 1,
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        _ => 4,
+    }
+}
+
+// U+2028 和 U+2029 的 UTF-8 编码是 E2 80 A8 和 E2 80 A9
+fn is_unicode_line_terminator(bytes: &[u8]) -> bool {
+    return matches!(bytes, [0xE2, 0x80, 0xA8 | 0xA9, ..]);
+}
+
+/*
+* 正则表达式的基本检查：括号是否配对、量词前是否有可以重复的内容
+* 字符类 [...] 和转义的字符不参与检查
+*/
+fn check_regular_expression_pattern(pattern: &str) -> Result<(), &'static str> {
+    let bytes = pattern.as_bytes();
+    let mut index = 0;
+    let mut in_class = false;
+    let mut groups = 0;
+    // 上一个元素是否可以被量词修饰
+    let mut repeatable = false;
+    // 上一个元素是否是量词，量词后的 '?' 表示非贪婪
+    let mut after_quantifier = false;
+    while index < bytes.len() {
+        let byte = bytes[index];
+        index += 1;
+        if in_class {
+            if byte == b'\\' {
+                index += 1;
+            } else if byte == b']' {
+                in_class = false;
+                repeatable = true;
+            }
+            continue;
+        }
+        let mut quantifier = false;
+        match byte {
+            b'\\' => {
+                index += 1;
+                repeatable = true;
+            }
+            b'[' => in_class = true,
+            b'(' => {
+                groups += 1;
+                repeatable = false;
+                // (?:、(?=、(?!、(?<=、(?<!、(?<name>
+                if bytes.get(index) == Some(&b'?') {
+                    index += 1;
                 }
-                if number_of_brackets != 0 {
-                    let mut error_message = String::from("Invalid regular expression: ");
-                    error_message.push_str(&token);
-                    error_message.push_str(":");
-                    error_message.push_str(" Unterminated group");
-                    return Err(error_message);
+            }
+            b')' => {
+                if groups == 0 {
+                    return Err("Unmatched ')'");
                 }
-                if number_of_square_brackets != 0 {
-                    return Err(String::from("Unterminated regular expression"));
+                groups -= 1;
+                repeatable = true;
+            }
+            b'|' | b'^' | b'$' => repeatable = false,
+            b'*' | b'+' | b'?' => {
+                if byte == b'?' && after_quantifier {
+                    after_quantifier = false;
+                    continue;
                 }
-                token.push(char);
-                pattern.push(char);
-                number_of_escape_characters = 0;
-                pattern_start = false;
-                flags_start = true;
-            } else if pattern_start {
-                if !extract_params.outer_token.is_empty() {
-                    token.push_str(extract_params.outer_token);
-                    pattern.push_str(extract_params.outer_token);
-                    *extract_params.outer_token = String::new();
+                if !repeatable {
+                    return Err("Nothing to repeat");
                 }
-                if char == '\\' {
-                    number_of_escape_characters += 1;
-                } else {
-                    if char == '{' && no_escape(number_of_escape_characters) {
-                        if cache.is_empty() {
-                            cache.push('{');
-                        } else {
-                            clear_cache(&mut token, &mut pattern, &mut cache);
-                        }
-                    } else if extract_params.num_regex.is_match(&char.to_string()) {
-                        if !cache.is_empty() {
-                            cache.push(char);
-                        }
-                    } else if char == '}' && no_escape(number_of_escape_characters) {
-                        if cache.len() == 2 {
-                            cache.push('}');
-                            if extract_params
-                                .syntax_error_regex
-                                .is_match(&pattern.to_string())
-                            {
-                                clear_cache(&mut token, &mut pattern, &mut cache);
-                                let mut error_message =
-                                    String::from("Invalid regular expression: ");
-                                error_message.push_str(&token);
-                                error_message.push_str(":");
-                                error_message.push_str(" Nothing to repeat");
-                                return Err(error_message);
-                            } else {
-                                clear_cache(&mut token, &mut pattern, &mut cache);
-                            }
-                        } else if !cache.is_empty() {
-                            clear_cache(&mut token, &mut pattern, &mut cache);
-                        }
-                    } else {
-                        if !cache.is_empty() {
-                            clear_cache(&mut token, &mut pattern, &mut cache);
-                        }
-                        if char == '(' && no_escape(number_of_escape_characters) {
-                            number_of_brackets += 1;
-                        } else if char == ')' && no_escape(number_of_escape_characters) {
-                            number_of_brackets -= 1;
-                        } else if char == '[' && no_escape(number_of_escape_characters) {
-                            number_of_square_brackets += 1;
-                        } else if char == ']' && no_escape(number_of_escape_characters) {
-                            number_of_square_brackets -= 1;
-                        } else if (char == '+' || char == '*')
-                            && extract_params
-                                .syntax_error_regex
-                                .is_match(&pattern.to_string())
-                        {
-                            let mut error_message = String::from("Invalid regular expression: ");
-                            error_message.push_str(&token);
-                            error_message.push_str(":");
-                            error_message.push_str(" Nothing to repeat");
-                            return Err(error_message);
-                        }
+                quantifier = true;
+            }
+            b'{' => {
+                // {n}、{n,}、{n,m} 是量词，其余的 '{' 是普通字符
+                let rest = &pattern[index..];
+                let length = rest.find('}').unwrap_or(0);
+                let body = &rest[..length];
+                let (min, max) = body.split_once(',').unwrap_or((body, "0"));
+                if length > 0
+                    && !min.is_empty()
+                    && min.bytes().all(|byte| byte.is_ascii_digit())
+                    && max.bytes().all(|byte| byte.is_ascii_digit())
+                {
+                    if !repeatable {
+                        return Err("Nothing to repeat");
                     }
-                    number_of_escape_characters = 0;
-                }
-                token.push(char);
-                pattern.push(char);
-            } else if flags_start
-                && char != ' '
-                && char != '\n'
-                && !extract_params
-                    .punctuators_trie_node
-                    .exist(&char.to_string())
-            {
-                number_of_escape_characters = 0;
-                if extract_params.flags_regex.is_match(&char.to_string()) {
-                    token.push(char);
-                    flags.push(char);
-                } else {
-                    return Err(String::from("Invalid regular expression flag"));
-                }
-            } else {
-                flags_start = false;
-                number_of_escape_characters = 0;
-                extract_params.tokens.push(Token {
-                    _type: TokenType {
-                        label: String::from("regexp"),
-                        keyword: MyOption(None),
-                        beforeExpr: false,
-                        startsExpr: true,
-                        isLoop: false,
-                        isAssign: false,
-                        prefix: false,
-                        postfix: false,
-                    },
-                    value: TokenValue::RegexpValue(RegexpValue {
-                        pattern: String::from(&pattern),
-                        flags: String::from(&flags),
-                        value: String::from(token.clone()),
-                    }),
-                    start: start,
-                    end: next_cursor_index - 1,
-                });
-                pattern = String::new();
-                flags = String::new();
-                token = String::new();
-                if char == ' ' || char == '\n' {
-                    *extract_params.outer_token = String::new();
-                    *extract_params.status = Status::Initial;
+                    index += length + 1;
+                    quantifier = true;
                 } else {
-                    *extract_params.status = Status::Punctuators;
-                    *extract_params.outer_token = String::from(char);
+                    repeatable = true;
                 }
             }
-            return Ok(());
-        };
-    return Box::new(closure);
+            _ => repeatable = true,
+        }
+        if quantifier {
+            repeatable = false;
+        }
+        after_quantifier = quantifier;
+    }
+    if groups > 0 {
+        return Err("Unterminated group");
+    }
+    return Ok(());
 }
 
-pub fn tokenizer<'a>(code: &str) -> Result<Vec<Token>, String> {
-    // 分词结果
-    let tokens_ref_cell: RefCell<Vec<Token>> = RefCell::new(Vec::new());
-
-    let mut ketwords_trie_node = TrieNode::new();
-
-    let mut punctuators_trie_node = TrieNode::new();
-
-    // 关键字
-    ketwords_trie_node.insert("var");
-    ketwords_trie_node.insert("let");
-    ketwords_trie_node.insert("const");
-    ketwords_trie_node.insert("function");
-    ketwords_trie_node.insert("if");
-    ketwords_trie_node.insert("while");
-    ketwords_trie_node.insert("for");
-    ketwords_trie_node.insert("switch");
-    ketwords_trie_node.insert("case");
-    ketwords_trie_node.insert("break");
-    ketwords_trie_node.insert("continue");
-    ketwords_trie_node.insert("async");
-    ketwords_trie_node.insert("await");
-    ketwords_trie_node.insert("get");
-    ketwords_trie_node.insert("set");
-    ketwords_trie_node.insert("catch");
-    ketwords_trie_node.insert("class");
-    ketwords_trie_node.insert("debugger");
-    ketwords_trie_node.insert("default");
-    ketwords_trie_node.insert("delete");
-    ketwords_trie_node.insert("do");
-    ketwords_trie_node.insert("else");
-    ketwords_trie_node.insert("export");
-    ketwords_trie_node.insert("extends");
-    ketwords_trie_node.insert("finally");
-    ketwords_trie_node.insert("import");
-    ketwords_trie_node.insert("in");
-    ketwords_trie_node.insert("instanceof");
-    ketwords_trie_node.insert("new");
-    ketwords_trie_node.insert("return");
-    ketwords_trie_node.insert("super");
-    ketwords_trie_node.insert("this");
-    ketwords_trie_node.insert("throw");
-    ketwords_trie_node.insert("try");
-    ketwords_trie_node.insert("typeof");
-    ketwords_trie_node.insert("void");
-    ketwords_trie_node.insert("with");
-    ketwords_trie_node.insert("yield");
-    ketwords_trie_node.insert("enum");
-    ketwords_trie_node.insert("true");
-    ketwords_trie_node.insert("false");
-    ketwords_trie_node.insert("null");
-
-    // punctuators 符号
-    punctuators_trie_node.insert("=");
-    punctuators_trie_node.insert(";");
-    punctuators_trie_node.insert("(");
-    punctuators_trie_node.insert(")");
-    punctuators_trie_node.insert("{");
-    punctuators_trie_node.insert("}");
-    punctuators_trie_node.insert("+");
-    punctuators_trie_node.insert("-");
-    punctuators_trie_node.insert("*");
-    punctuators_trie_node.insert("%");
-    punctuators_trie_node.insert("\\");
-    punctuators_trie_node.insert(".");
-    punctuators_trie_node.insert("!");
-    punctuators_trie_node.insert(",");
-    punctuators_trie_node.insert("==");
-    punctuators_trie_node.insert("===");
-    punctuators_trie_node.insert("!=");
-    punctuators_trie_node.insert("!==");
-    punctuators_trie_node.insert(">");
-    punctuators_trie_node.insert("<");
-    punctuators_trie_node.insert(">=");
-    punctuators_trie_node.insert("<=");
-    punctuators_trie_node.insert("=>");
-    punctuators_trie_node.insert("+=");
-    punctuators_trie_node.insert("-=");
-    punctuators_trie_node.insert("*=");
-    punctuators_trie_node.insert("/=");
-    punctuators_trie_node.insert("%=");
-    punctuators_trie_node.insert("...");
-    punctuators_trie_node.insert("&&");
-    punctuators_trie_node.insert("||");
-    punctuators_trie_node.insert("**");
-    punctuators_trie_node.insert("++");
-    punctuators_trie_node.insert("--");
-    punctuators_trie_node.insert("<<");
-    punctuators_trie_node.insert(">>");
-    punctuators_trie_node.insert(">>>");
-    punctuators_trie_node.insert("&");
-    punctuators_trie_node.insert("|");
-    punctuators_trie_node.insert("^");
-    punctuators_trie_node.insert("~");
-    punctuators_trie_node.insert("?");
-    punctuators_trie_node.insert(":");
-    punctuators_trie_node.insert("**=");
-    punctuators_trie_node.insert("<<=");
-    punctuators_trie_node.insert(">>=");
-    punctuators_trie_node.insert(">>>=");
-    punctuators_trie_node.insert("&=");
-    punctuators_trie_node.insert("|=");
-    punctuators_trie_node.insert("^=");
-    punctuators_trie_node.insert("[");
-    punctuators_trie_node.insert("]");
-
-    let init_string_mode = '\0';
-
-    let mut next_cursor_index: usize = 0;
-    let mut current_column: usize = 0;
-    let mut start: usize = 0;
-    let mut start_column: usize = 0;
-    let mut start: usize = 0;
-    let mut end: usize = 0;
-    let mut next_cursor_index: usize = 0;
-    let status_ref_cell = RefCell::new(Status::Initial);
-    // 0-初始化、1-字符串、2-整型、3-浮点数、4-boolean、5-null、6-undefined
-    let mut literal_status: LiteralStatus = LiteralStatus::Initial;
-    // 字符串的模式：双引号、单引号、模板字符串
-    let mut string_mode = init_string_mode;
-    let under_template_ref_cell = RefCell::new(false);
-    // 字符串中连续转义符的数量
-    let mut number_of_escape_characters: u16 = 0;
-    let token_ref_cell = RefCell::new(String::new());
-    let mut current_keywords_node = &ketwords_trie_node;
-    let mut current_punctuators_node = &punctuators_trie_node;
-
-    let mut tokens = tokens_ref_cell.borrow_mut();
-
-    let mut status = status_ref_cell.borrow_mut();
-
-    let mut under_template = under_template_ref_cell.borrow_mut();
-
-    let mut comment_status = CommentStatus::None;
-
-    let mut token = token_ref_cell.borrow_mut();
-
-    let mut lex_regular_expression_action = create_lex_regular_expression_action();
-
-    let mut lex_template_action = create_lex_template_action();
-
-    for char in code.chars() {
-        // 更新token的开始位置
-        if token.is_empty() {
-            start = next_cursor_index;
+fn token_type(label: &str, keyword: bool, before_expr: bool, starts_expr: bool) -> TokenType {
+    return TokenType {
+        label: String::from(label),
+        keyword: MyOption(keyword.then(|| String::from(label))),
+        beforeExpr: before_expr,
+        startsExpr: starts_expr,
+        isLoop: false,
+        isAssign: false,
+        prefix: false,
+        postfix: false,
+    };
+}
+
+// 未闭合的括号及其所属的结构，决定与之配对的 ')' 或 '}' 之后是语句还是表达式（与 acorn 的 context 对应）
+#[derive(Clone, Copy, PartialEq)]
+enum BraceContext {
+    // 语句块、函数体、类体
+    Block,
+    // 对象字面量和对象解构
+    Object,
+    // 模板字符串中的 '${'
+    Template,
+    // if、while、for、with 的条件
+    StatementParen,
+    ExpressionParen,
+    // function 或 class 关键字之后，直到函数体或类体结束
+    FunctionExpression,
+    FunctionStatement,
+}
+
+impl BraceContext {
+    fn is_expression(self) -> bool {
+        return !matches!(
+            self,
+            BraceContext::Block | BraceContext::StatementParen | BraceContext::FunctionStatement
+        );
+    }
+}
+
+// 按字节扫描源码，ASCII 字符通过 CHAR_CLASSES 查表分派
+struct Scanner<'a> {
+    code: &'a str,
+    bytes: &'a [u8],
+    // 下一个字符的字节偏移
+    pos: usize,
+    tokens: Vec<Token>,
+    // 未闭合的 '{'、'${'、'(' 以及 function、class 关键字
+    context: Vec<BraceContext>,
+    // 最后一个 token 是 ')' 或 '}' 时，之后的 '/' 是否是正则，如 if (a) /re/ 和 {} /re/
+    regexp_after_close: bool,
+    // 自上一个 token 以来是否跳过了换行（包括注释中的换行）
+    newline_before: bool,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(code: &'a str) -> Self {
+        Scanner {
+            code,
+            bytes: code.as_bytes(),
+            pos: 0,
+            tokens: Vec::new(),
+            context: Vec::new(),
+            regexp_after_close: false,
+            newline_before: false,
+        }
+    }
+
+    fn error(&self, message: impl Into<String>, offset: usize) -> ParseError {
+        return ParseError::lexical(message, offset, self.code);
+    }
+
+    // 顶层视为语句块
+    fn current_context(&self) -> BraceContext {
+        return *self.context.last().unwrap_or(&BraceContext::Block);
+    }
+
+    fn regular_expression_allowed(&self) -> bool {
+        match self.tokens.last() {
+            Some(token) if matches!(token._type.label.as_str(), ")" | "}") => {
+                return self.regexp_after_close
+            }
+            _ => return regular_expression_allowed(&self.tokens),
         }
-        next_cursor_index += 1;
-        match *status {
-            // 初始状态
-            Status::Initial => {
-                if let Some(next_keywords_node) = current_keywords_node.reduce_find(&char) {
-                    *status = Status::Keywords;
-                    token.push(char);
-                    current_keywords_node = next_keywords_node;
+    }
+
+    // 即将读入的 '{' 是语句块（包括函数体、类体）还是对象字面量
+    fn brace_is_block(&self) -> bool {
+        let parent = self.current_context();
+        if matches!(
+            parent,
+            BraceContext::FunctionExpression | BraceContext::FunctionStatement
+        ) {
+            return true;
+        }
+        let token = match self.tokens.last() {
+            Some(token) => token,
+            None => return true,
+        };
+        match token._type.label.as_str() {
+            // a: {} 是带标签的语句块，{ a: {} } 是对象
+            ":" if matches!(parent, BraceContext::Block | BraceContext::Object) => {
+                return parent == BraceContext::Block
+            }
+            // return 之后换行会自动插入分号
+            "return" => return self.newline_before,
+            "name" if self.regular_expression_allowed() => return self.newline_before,
+            "else" | ";" | ")" | "=>" => return true,
+            "{" => return parent == BraceContext::Block,
+            "var" | "const" | "name" => return false,
+            _ => return !self.regular_expression_allowed(),
+        }
+    }
+
+    // 即将读入的 function 或 class 关键字开始的是表达式还是声明
+    fn function_is_expression(&self) -> bool {
+        let token = match self.tokens.last() {
+            Some(token) => token,
+            None => return false,
+        };
+        let current = self.current_context();
+        match token._type.label.as_str() {
+            "else" | "export" | ")" | "}" => return false,
+            // for (;function () {};) 中是表达式
+            ";" => return current == BraceContext::StatementParen,
+            "return" => return !self.newline_before,
+            ":" | "{" if current == BraceContext::Block => return false,
+            _ => return self.regular_expression_allowed(),
+        }
+    }
+
+    // a.function 和 { class: 1 } 中的关键字只是属性名
+    fn opens_function(&self) -> bool {
+        let after_dot = matches!(self.tokens.last(), Some(token) if matches!(token._type.label.as_str(), "." | "?."));
+        let next = self.bytes[self.pos..]
+            .iter()
+            .find(|byte| !matches!(byte, b' ' | b'\t' | b'\n' | b'\r'));
+        return !after_dot && next != Some(&b':');
+    }
+
+    fn push(&mut self, _type: TokenType, value: TokenValue, start: usize, end: usize) {
+        self.tokens.push(Token {
+            _type,
+            value,
+            start,
+            end,
+            loc: SourceLocation::default(),
+            newline_before: self.newline_before,
+            escaped: false,
+            contextual_keyword: false,
+        });
+        self.newline_before = false;
+    }
+
+    fn scan(&mut self) -> Result<(), ParseError> {
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            if byte >= 0x80 {
+                let char = self.code[self.pos..].chars().next().unwrap();
+                if is_whitespace(char) {
+                    self.newline_before = self.newline_before || is_line_terminator(char);
+                    self.pos += char.len_utf8();
+                } else if is_identifier_start(char) {
+                    self.scan_identifier()?;
                 } else {
-                    // 模板字符串
-                    if char == '`' {
-                        *status = Status::Template;
-                        let mut lex_template_action_extract_params =
-                            create_lex_template_action_extract_params(
-                                &mut status,
-                                &mut tokens,
-                                &mut under_template,
-                                true,
-                            );
-                        if let Err(error_message) = lex_template_action(
-                            char,
-                            start,
-                            next_cursor_index,
-                            &mut lex_template_action_extract_params,
-                        ) {
-                            return Err(error_message);
-                        }
-                    // 遇到符号
-                    } else if let Some(next_punctuators_node) =
-                        current_punctuators_node.reduce_find(&char)
-                    {
-                        *status = Status::Punctuators;
-                        token.push(char);
-                        current_punctuators_node = next_punctuators_node;
-                    // 遇到空格或换行结束
-                    } else if char != ' ' && char != '\n' {
-                        token.push(char);
-                        literal_status =
-                            get_literal_status(&char.to_string(), &tokens, &punctuators_trie_node);
-                        // 可能是字面量
-                        if !matches!(literal_status, LiteralStatus::Initial) {
-                            *status = Status::Literal;
-                            // 如果是字符串需要确定字符串模式
-                            if char == '"' || char == '\'' {
-                                string_mode = char;
-                            }
-                        // 标识符
-                        } else {
-                            *status = Status::Identifier;
-                        }
-                    }
+                    return Err(self.error("Unexpected character", self.pos));
+                }
+                continue;
+            }
+            match char_class(byte) {
+                CharClass::Whitespace => self.pos += 1,
+                CharClass::LineTerminator => {
+                    self.newline_before = true;
+                    self.pos += 1;
                 }
+                CharClass::Identifier | CharClass::Backslash => self.scan_identifier()?,
+                CharClass::Digit => self.scan_number(self.pos)?,
+                CharClass::Quote => self.scan_string()?,
+                CharClass::Backtick => {
+                    self.push(
+                        token_type("`", false, false, true),
+                        TokenValue::None,
+                        self.pos,
+                        self.pos + 1,
+                    );
+                    self.scan_template(self.pos + 1)?;
+                }
+                CharClass::Slash => self.scan_slash()?,
+                CharClass::Punctuator => self.scan_punctuator()?,
+                CharClass::Hash => self.scan_private_name()?,
+                CharClass::Invalid => return Err(self.error("Unexpected character", self.pos)),
+            }
+        }
+        return Ok(());
+    }
+
+    // 读取一个标识符名，返回转义后的名称以及其中是否含有转义，名称为空时说明当前字符不能开始标识符
+    fn read_word(&mut self) -> Result<(String, bool), ParseError> {
+        let start = self.pos;
+        // 没有转义时名称就是源码文本，遇到转义后才逐个字符拼接
+        let mut cooked: Option<String> = None;
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            let first = self.pos == start;
+            if byte == b'\\' {
+                let (char, length) = self.read_identifier_escape(first)?;
+                cooked
+                    .get_or_insert_with(|| String::from(&self.code[start..self.pos]))
+                    .push(char);
+                self.pos += length;
+                continue;
             }
-            // 关键字状态
-            Status::Keywords => {
-                if let Some(next_keywords_node) = current_keywords_node.reduce_find(&char) {
-                    token.push(char);
-                    current_keywords_node = next_keywords_node;
+            let char = if byte < 0x80 {
+                byte as char
+            } else {
+                self.code[self.pos..].chars().next().unwrap()
+            };
+            let valid = if first {
+                is_identifier_start(char)
+            } else {
+                is_identifier_part(char)
+            };
+            if !valid {
+                break;
+            }
+            if let Some(cooked) = &mut cooked {
+                cooked.push(char);
+            }
+            self.pos += char.len_utf8();
+        }
+        match cooked {
+            Some(cooked) => return Ok((cooked, true)),
+            None => return Ok((String::from(&self.code[start..self.pos]), false)),
+        }
+    }
+
+    // 标识符中的 \uXXXX 或 \u{X...}，转义得到的字符同样要是合法的标识符字符，返回字符和转义的字节数
+    fn read_identifier_escape(&self, first: bool) -> Result<(char, usize), ParseError> {
+        let start = self.pos;
+        if self.bytes.get(start + 1) != Some(&b'u') {
+            return Err(self.error("Expecting Unicode escape sequence \\uXXXX", start + 1));
+        }
+        let chars: Vec<char> = self.code[start + 2..]
+            .chars()
+            .take_while(|char| char.is_ascii_hexdigit() || *char == '{' || *char == '}')
+            .collect();
+        let (code_point, length) =
+            read_unicode_escape(&chars, 0).map_err(|message| self.error(message, start))?;
+        match char::from_u32(code_point) {
+            Some(char) if first && is_identifier_start(char) => return Ok((char, length + 2)),
+            Some(char) if !first && is_identifier_part(char) => return Ok((char, length + 2)),
+            _ => return Err(self.error("Invalid Unicode escape", start)),
+        }
+    }
+
+    // 标识符和关键字，含有转义的关键字（如 v\u0061r）只作为普通名称，由语法分析决定能否使用
+    fn scan_identifier(&mut self) -> Result<(), ParseError> {
+        let start = self.pos;
+        let (word, escaped) = self.read_word()?;
+        if !escaped && is_keyword(&word) {
+            if matches!(word.as_str(), "function" | "class") && self.opens_function() {
+                let context = if self.function_is_expression() {
+                    BraceContext::FunctionExpression
                 } else {
-                    // 已经收集到了完整的关键字
-                    if current_keywords_node.is_end_of_word {
-                        // 有符号则代表结束
-                        if let Some(next_punctuators_node) =
-                            current_punctuators_node.reduce_find(&char)
-                        {
-                            *status = Status::Punctuators;
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from(token.clone()),
-                                    keyword: MyOption(Some(String::from(token.clone()))),
-                                    beforeExpr: false,
-                                    startsExpr: false,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            start = next_cursor_index - 1;
-                            *token = String::from(char);
-                            current_punctuators_node = next_punctuators_node
-
-                        // 有空格或换行则代表结束
-                        } else if char == ' ' || char == '\n' {
-                            *status = Status::Initial;
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from(token.clone()),
-                                    keyword: MyOption(Some(String::from(token.clone()))),
-                                    beforeExpr: false,
-                                    startsExpr: false,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            *token = String::new();
-                        } else if char == '`' {
-                            // 模板字符串
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from(token.clone()),
-                                    keyword: MyOption(Some(String::from(token.clone()))),
-                                    beforeExpr: false,
-                                    startsExpr: false,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            *token = String::new();
-                            *status = Status::Template;
-                            let mut lex_template_action_extract_params =
-                                create_lex_template_action_extract_params(
-                                    &mut status,
-                                    &mut tokens,
-                                    &mut under_template,
-                                    true,
-                                );
-                            if let Err(error_message) = lex_template_action(
-                                char,
-                                start,
-                                next_cursor_index,
-                                &mut lex_template_action_extract_params,
-                            ) {
-                                return Err(error_message);
-                            }
-                        } else {
-                            // 否则是其他情况（前缀和关键字一样）
-                            literal_status =
-                                get_literal_status(&token, &tokens, &punctuators_trie_node);
-                            // 紧接字面量
-                            if !matches!(literal_status, LiteralStatus::Initial) {
-                                *status = Status::Literal;
-                                tokens.push(Token {
-                                    _type: TokenType {
-                                        label: String::from(token.clone()),
-                                        keyword: MyOption(Some(String::from(token.clone()))),
-                                        beforeExpr: false,
-                                        startsExpr: false,
-                                        isLoop: false,
-                                        isAssign: false,
-                                        prefix: false,
-                                        postfix: false,
-                                    },
-                                    value: TokenValue::String(token.clone()),
-                                    start: start,
-                                    end: next_cursor_index - 1,
-                                });
-                                start = next_cursor_index - 1;
-                                *token = String::from(char);
-                                if char == '"' || char == '\'' {
-                                    string_mode = char;
-                                }
-                            } else {
-                                // 标识符
-                                start = next_cursor_index - 1;
-                                *status = Status::Identifier;
-                                token.push(char);
-                            }
-                        }
-                    // 不是关键字，只是前缀和关键字重合
-                    } else {
-                        // 遇到符号结束
-                        if let Some(next_punctuators_node) =
-                            current_punctuators_node.reduce_find(&char)
-                        {
-                            *status = Status::Punctuators;
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("name"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: true,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            start = next_cursor_index - 1;
-                            *token = String::from(char);
-                            current_punctuators_node = next_punctuators_node;
-                        // token有值时遇到空格或换行结束
-                        } else if char == ' ' || char == '\n' {
-                            *status = Status::Initial;
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("name"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: true,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            *token = String::new();
-                        } else if char == '`' {
-                            // 模板字符串
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("name"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: true,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            *token = String::new();
-                            *status = Status::Template;
-                            let mut lex_template_action_extract_params =
-                                create_lex_template_action_extract_params(
-                                    &mut status,
-                                    &mut tokens,
-                                    &mut under_template,
-                                    true,
-                                );
-                            if let Err(error_message) = lex_template_action(
-                                char,
-                                start,
-                                next_cursor_index,
-                                &mut lex_template_action_extract_params,
-                            ) {
-                                return Err(error_message);
-                            }
-                        } else {
-                            // 紧接字符串字面量
-                            if char == '"' || char == '\'' {
-                                *status = Status::Literal;
-                                literal_status = LiteralStatus::String;
-                                tokens.push(Token {
-                                    _type: TokenType {
-                                        label: String::from("name"),
-                                        keyword: MyOption(None),
-                                        beforeExpr: false,
-                                        startsExpr: true,
-                                        isLoop: false,
-                                        isAssign: false,
-                                        prefix: false,
-                                        postfix: false,
-                                    },
-                                    value: TokenValue::String(token.clone()),
-                                    start: start,
-                                    end: next_cursor_index - 1,
-                                });
-                                start = next_cursor_index - 1;
-                                *token = String::from(char);
-                                if char == '"' || char == '\'' {
-                                    string_mode = char;
-                                }
-                            } else {
-                                // 标识符，继续识别
-                                *status = Status::Identifier;
-                                token.push(char);
-                            }
-                        }
+                    BraceContext::FunctionStatement
+                };
+                self.context.push(context);
+            }
+            let value = TokenValue::String(word);
+            let label = &self.code[start..self.pos];
+            self.push(
+                token_type(label, true, false, false),
+                value,
+                start,
+                self.pos,
+            );
+        } else {
+            let contextual_keyword = !escaped && CONTEXTUAL_KEYWORDS.contains(&word.as_str());
+            let value = TokenValue::String(word);
+            self.push(
+                token_type("name", false, false, true),
+                value,
+                start,
+                self.pos,
+            );
+            let token = self.tokens.last_mut().unwrap();
+            token.escaped = escaped;
+            token.contextual_keyword = contextual_keyword;
+        }
+        return Ok(());
+    }
+
+    // 类的私有名称 #x，与 acorn 一致，label 为 privateId，value 不包括 '#'
+    fn scan_private_name(&mut self) -> Result<(), ParseError> {
+        let start = self.pos;
+        self.pos += 1;
+        let (name, escaped) = self.read_word()?;
+        if name.is_empty() {
+            return Err(self.error("Unexpected character", start));
+        }
+        self.push(
+            token_type("privateId", false, false, true),
+            TokenValue::String(name),
+            start,
+            self.pos,
+        );
+        self.tokens.last_mut().unwrap().escaped = escaped;
+        return Ok(());
+    }
+    // 数字字面量，`start` 处是数字或 `.5` 中的 '.'，先读完整个字面量再检查语法
+    fn scan_number(&mut self, start: usize) -> Result<(), ParseError> {
+        self.pos = start + 1;
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            let char = if byte < 0x80 {
+                byte as char
+            } else {
+                self.code[self.pos..].chars().next().unwrap()
+            };
+            if !continues_number(&self.code[start..self.pos], char) {
+                break;
+            }
+            self.pos += char.len_utf8();
+        }
+        let number = &self.code[start..self.pos];
+        if let Err((message, offset)) = check_numeric_literal(number) {
+            return Err(self.error(message, start + offset));
+        }
+        // 数字之后不能紧跟标识符，如 3in、1#
+        let identifier_after = match self.bytes.get(self.pos) {
+            Some(&byte) if byte < 0x80 => {
+                matches!(
+                    char_class(byte),
+                    CharClass::Identifier | CharClass::Backslash
+                )
+            }
+            Some(_) => is_identifier_start(self.code[self.pos..].chars().next().unwrap()),
+            None => false,
+        };
+        if identifier_after {
+            return Err(self.error("Identifier directly after number", self.pos));
+        }
+        let value = TokenValue::String(String::from(number));
+        self.push(
+            token_type("number", false, false, false),
+            value,
+            start,
+            self.pos,
+        );
+        return Ok(());
+    }
+
+    // 字符串只记录原始文本，转义在这里检查，值在语法分析时再取
+    fn scan_string(&mut self) -> Result<(), ParseError> {
+        let start = self.pos;
+        let quote = self.bytes[start];
+        let mut pos = start + 1;
+        loop {
+            match self.bytes.get(pos) {
+                None | Some(b'\n' | b'\r') => {
+                    return Err(self.error("Unterminated string constant", start));
+                }
+                Some(&byte) if byte == quote => break,
+                // 续行的 \r\n 一起跳过
+                Some(b'\\') => {
+                    pos += 2;
+                    if self.bytes.get(pos - 1) == Some(&b'\r')
+                        && self.bytes.get(pos) == Some(&b'\n')
+                    {
+                        pos += 1;
                     }
-                    current_keywords_node = &ketwords_trie_node;
                 }
+                Some(_) => pos += 1,
             }
-            // 符号状态
-            Status::Punctuators => {
-                if std::ptr::eq(current_punctuators_node, &punctuators_trie_node) {
-                    if let Some(char) = token.chars().next() {
-                        if let Some(next_punctuators_node) =
-                            current_punctuators_node.reduce_find(&char)
-                        {
-                            current_punctuators_node = next_punctuators_node;
-                        }
-                    }
+        }
+        if let Err((message, offset)) = cook_string(&self.code[start + 1..pos]) {
+            return Err(self.error(message, start + 1 + offset));
+        }
+        self.pos = pos + 1;
+        let value = TokenValue::String(String::from(&self.code[start..self.pos]));
+        self.push(
+            token_type("string", false, false, false),
+            value,
+            start,
+            self.pos,
+        );
+        return Ok(());
+    }
+
+    // 模板字符串的一段内容，从 '`' 或 '}' 之后开始，到 '`' 或 '${' 结束
+    fn scan_template(&mut self, content_start: usize) -> Result<(), ParseError> {
+        let mut pos = content_start;
+        loop {
+            match self.bytes.get(pos) {
+                None => return Err(self.error("Unterminated template", content_start - 1)),
+                Some(b'`') => {
+                    let value = TokenValue::String(String::from(&self.code[content_start..pos]));
+                    self.push(
+                        token_type("template", false, false, true),
+                        value,
+                        content_start,
+                        pos,
+                    );
+                    self.push(
+                        token_type("`", false, false, true),
+                        TokenValue::None,
+                        pos,
+                        pos + 1,
+                    );
+                    self.pos = pos + 1;
+                    return Ok(());
                 }
-                if let Some(next_punctuators_node) = current_punctuators_node.reduce_find(&char) {
-                    token.push(char);
-                    current_punctuators_node = next_punctuators_node;
-                } else {
-                    if *token == "}" && *under_template {
-                        current_punctuators_node = &punctuators_trie_node;
-                        *status = Status::Template;
-                        tokens.push(Token {
-                            _type: TokenType {
-                                label: String::from("}"),
-                                keyword: MyOption(None),
-                                beforeExpr: false,
-                                startsExpr: false,
-                                isLoop: false,
-                                isAssign: false,
-                                prefix: false,
-                                postfix: false,
-                            },
-                            value: TokenValue::None,
-                            start: start,
-                            end: next_cursor_index - 1,
-                        });
-                        *token = String::new();
-                        let mut lex_template_action_extract_params =
-                            create_lex_template_action_extract_params(
-                                &mut status,
-                                &mut tokens,
-                                &mut under_template,
-                                false,
-                            );
-                        if let Err(error_message) = lex_template_action(
-                            char,
-                            start,
-                            next_cursor_index,
-                            &mut lex_template_action_extract_params,
-                        ) {
-                            return Err(error_message);
-                        }
-                    } else if current_punctuators_node.is_end_of_word {
-                        current_punctuators_node = &punctuators_trie_node;
-                        tokens.push(Token {
-                            _type: TokenType {
-                                label: token.to_string(),
-                                keyword: MyOption(None),
-                                beforeExpr: true,
-                                startsExpr: false,
-                                isLoop: false,
-                                isAssign: false,
-                                prefix: false,
-                                postfix: false,
-                            },
-                            value: TokenValue::String(token.to_string()),
-                            start: start,
-                            end: next_cursor_index - 1,
-                        });
-                        // 以一个新的符号开始
-                        if let Some(next_punctuators_node) =
-                            current_punctuators_node.reduce_find(&char)
-                        {
-                            current_punctuators_node = next_punctuators_node;
-                            *token = String::from(char);
-                        // 后面是关键字
-                        } else if let Some(next_keywords_node) =
-                            current_keywords_node.reduce_find(&char)
-                        {
-                            *status = Status::Keywords;
-                            current_keywords_node = next_keywords_node;
-                            start = next_cursor_index - 1;
-                            *token = String::from(char);
-                        } else if char == ' ' || char == '\n' {
-                            *status = Status::Initial;
-                            *token = String::new();
-                        } else if char == '`' {
-                            // 模板字符串
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: token.to_string(),
-                                    keyword: MyOption(None),
-                                    beforeExpr: true,
-                                    startsExpr: false,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.to_string()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            *token = String::new();
-                            *status = Status::Template;
-                            let mut lex_template_action_extract_params =
-                                create_lex_template_action_extract_params(
-                                    &mut status,
-                                    &mut tokens,
-                                    &mut under_template,
-                                    true,
-                                );
-                            if let Err(error_message) = lex_template_action(
-                                char,
-                                start,
-                                next_cursor_index,
-                                &mut lex_template_action_extract_params,
-                            ) {
-                                return Err(error_message);
-                            }
-                        } else {
-                            *token = String::from(char);
-                            literal_status = get_literal_status(
-                                &char.to_string(),
-                                &tokens,
-                                &punctuators_trie_node,
-                            );
-                            // 后面是字面量
-                            if !matches!(literal_status, LiteralStatus::Initial) {
-                                *status = Status::Literal;
-                                start = next_cursor_index - 1;
-                                if char == '"' || char == '\'' {
-                                    string_mode = char;
-                                }
-                            } else {
-                                // 后面是标识符
-                                *status = Status::Identifier;
-                                start = next_cursor_index - 1;
-                            }
-                        }
-                    } else if *token == "/" {
-                        current_punctuators_node = &punctuators_trie_node;
-                        if char == '/' {
-                            // 单行注释
-                            *status = Status::Comment;
-                            comment_status = CommentStatus::SingleLine;
-                        } else if char == '*' {
-                            // 多行注释
-                            *status = Status::Comment;
-                            comment_status = CommentStatus::Multiline;
-                        } else if punctuators_trie_node.exist(&tokens.last().unwrap()._type.label) {
-                            // 正则
-                            *status = Status::Literal;
-                            token.push(char);
-                            literal_status = LiteralStatus::RegularExpression;
-                        }
-                    } else {
-                        return Err(String::from("unknown error"));
-                    }
+                Some(b'$') if self.bytes.get(pos + 1) == Some(&b'{') => {
+                    let value = TokenValue::String(String::from(&self.code[content_start..pos]));
+                    self.push(
+                        token_type("template", false, true, true),
+                        value,
+                        content_start,
+                        pos,
+                    );
+                    self.push(
+                        token_type("${", false, true, true),
+                        TokenValue::None,
+                        pos,
+                        pos + 2,
+                    );
+                    self.context.push(BraceContext::Template);
+                    self.pos = pos + 2;
+                    return Ok(());
                 }
+                Some(b'\\') => pos += 2,
+                Some(_) => pos += 1,
             }
-            // 标识符状态
-            Status::Identifier => {
-                // 遇到符号结束
-                if let Some(next_punctuators_node) = current_punctuators_node.reduce_find(&char) {
-                    *status = Status::Punctuators;
-                    tokens.push(Token {
-                        _type: TokenType {
-                            label: String::from("name"),
-                            keyword: MyOption(None),
-                            beforeExpr: false,
-                            startsExpr: true,
-                            isLoop: false,
-                            isAssign: false,
-                            prefix: false,
-                            postfix: false,
-                        },
-                        value: TokenValue::String(token.clone()),
-                        start: start,
-                        end: next_cursor_index - 1,
-                    });
-                    start = next_cursor_index - 1;
-                    *token = String::from(char);
-                    current_punctuators_node = next_punctuators_node;
-
-                // 遇到空格或换行结束
-                } else if char == ' ' || char == '\n' {
-                    *status = Status::Initial;
-                    tokens.push(Token {
-                        _type: TokenType {
-                            label: String::from("name"),
-                            keyword: MyOption(None),
-                            beforeExpr: false,
-                            startsExpr: true,
-                            isLoop: false,
-                            isAssign: false,
-                            prefix: false,
-                            postfix: false,
-                        },
-                        value: TokenValue::String(token.clone()),
-                        start: start,
-                        end: next_cursor_index - 1,
-                    });
-                    *token = String::new();
-                } else if char == '`' {
-                    // 模板字符串
-                    tokens.push(Token {
-                        _type: TokenType {
-                            label: String::from("name"),
-                            keyword: MyOption(None),
-                            beforeExpr: false,
-                            startsExpr: true,
-                            isLoop: false,
-                            isAssign: false,
-                            prefix: false,
-                            postfix: false,
-                        },
-                        value: TokenValue::String(token.clone()),
-                        start: start,
-                        end: next_cursor_index - 1,
-                    });
-                    *token = String::new();
-                    *status = Status::Template;
-                    let mut lex_template_action_extract_params =
-                        create_lex_template_action_extract_params(
-                            &mut status,
-                            &mut tokens,
-                            &mut under_template,
-                            true,
-                        );
-                    if let Err(error_message) = lex_template_action(
-                        char,
-                        start,
-                        next_cursor_index,
-                        &mut lex_template_action_extract_params,
-                    ) {
-                        return Err(error_message);
-                    }
-                } else {
-                    literal_status =
-                        get_literal_status(&char.to_string(), &tokens, &punctuators_trie_node);
-                    if !matches!(literal_status, LiteralStatus::Initial) {
-                        *status = Status::Literal;
-                        tokens.push(Token {
-                            _type: TokenType {
-                                label: String::from("name"),
-                                keyword: MyOption(None),
-                                beforeExpr: false,
-                                startsExpr: true,
-                                isLoop: false,
-                                isAssign: false,
-                                prefix: false,
-                                postfix: false,
-                            },
-                            value: TokenValue::String(token.clone()),
-                            start: start,
-                            end: next_cursor_index - 1,
-                        });
-                        start = next_cursor_index - 1;
-                        *token = String::from(char);
-                        if char == '"' || char == '\'' {
-                            string_mode = char;
-                        }
-                    } else {
-                        token.push(char);
+        }
+    }
+
+    // '/' 可能是注释、正则或除号
+    fn scan_slash(&mut self) -> Result<(), ParseError> {
+        let start = self.pos;
+        match self.bytes.get(start + 1) {
+            Some(b'/') => {
+                let mut pos = start + 2;
+                while let Some(&byte) = self.bytes.get(pos) {
+                    if byte == b'\n'
+                        || byte == b'\r'
+                        || is_unicode_line_terminator(&self.bytes[pos..])
+                    {
+                        break;
                     }
+                    pos += 1;
                 }
+                self.pos = pos;
             }
-            // 字面量状态
-            Status::Literal => {
-                match literal_status {
-                    // 字符串字面量
-                    LiteralStatus::String => {
-                        // 普通字符串
-                        if string_mode == '"' || string_mode == '\'' {
-                            token.push(char);
-                            if char == '\\' {
-                                number_of_escape_characters += 1
-                            } else {
-                                // 字符串结束
-                                if number_of_escape_characters % 2 == 0 && char == string_mode {
-                                    *status = Status::Initial;
-                                    literal_status = LiteralStatus::Initial;
-                                    tokens.push(Token {
-                                        _type: TokenType {
-                                            label: String::from("string"),
-                                            keyword: MyOption(None),
-                                            beforeExpr: false,
-                                            startsExpr: false,
-                                            isLoop: false,
-                                            isAssign: false,
-                                            prefix: false,
-                                            postfix: false,
-                                        },
-                                        value: TokenValue::String(token.clone()),
-                                        start: start,
-                                        end: next_cursor_index - 1,
-                                    });
-                                    *token = String::new();
-                                }
-                                number_of_escape_characters = 0
-                            }
-                        }
-                    }
-                    // 整型字面量
-                    LiteralStatus::Int => {
-                        let num_regexp = Regex::new(r"\d").unwrap();
-                        if num_regexp.is_match(&char.to_string()) {
-                            token.push(char);
-                        } else if char == '.' {
-                            token.push(char);
-                            literal_status = LiteralStatus::Float;
-                        } else if let Some(next_punctuators_node) =
-                            current_punctuators_node.reduce_find(&char)
-                        {
-                            *status = Status::Punctuators;
-                            literal_status = LiteralStatus::Initial;
-                            current_punctuators_node = next_punctuators_node;
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("number"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: false,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            start = next_cursor_index - 1;
-                            *token = String::from(char);
-                        } else if let Some(next_keywords_node) =
-                            current_keywords_node.reduce_find(&char)
-                        {
-                            *status = Status::Keywords;
-                            literal_status = LiteralStatus::Initial;
-                            current_keywords_node = next_keywords_node;
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("number"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: false,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            start = next_cursor_index - 1;
-                            *token = String::from(char);
-                        } else if char == ' ' || char == '\n' {
-                            *status = Status::Initial;
-                            literal_status = LiteralStatus::Initial;
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("number"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: false,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            *token = String::new();
-                        } else {
-                            literal_status = get_literal_status(
-                                &char.to_string(),
-                                &tokens,
-                                &punctuators_trie_node,
-                            );
-                            if !matches!(literal_status, LiteralStatus::Initial) {
-                                tokens.push(Token {
-                                    _type: TokenType {
-                                        label: String::from("number"),
-                                        keyword: MyOption(None),
-                                        beforeExpr: false,
-                                        startsExpr: false,
-                                        isLoop: false,
-                                        isAssign: false,
-                                        prefix: false,
-                                        postfix: false,
-                                    },
-                                    value: TokenValue::String(token.clone()),
-                                    start: start,
-                                    end: next_cursor_index - 1,
-                                });
-                                start = next_cursor_index - 1;
-                                *token = String::from(char);
-                            } else {
-                                return Err(String::from("Identifier directly after number"));
-                            }
-                        }
-                    }
-                    // 浮点数字面量
-                    LiteralStatus::Float => {
-                        let num_regexp = Regex::new(r"\d").unwrap();
-                        if num_regexp.is_match(&char.to_string()) {
-                            token.push(char);
-                        } else if char == '.' {
-                            return Err(String::from("Unexpected number"));
-                        } else if let Some(next_punctuators_node) =
-                            current_punctuators_node.reduce_find(&char)
-                        {
-                            *status = Status::Punctuators;
-                            literal_status = LiteralStatus::Initial;
-                            current_punctuators_node = next_punctuators_node;
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("number"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: false,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            start = next_cursor_index - 1;
-                            *token = String::from(char);
-                        } else if let Some(next_keywords_node) =
-                            current_keywords_node.reduce_find(&char)
-                        {
-                            *status = Status::Keywords;
-                            literal_status = LiteralStatus::Initial;
-                            current_keywords_node = next_keywords_node;
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("number"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: false,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            start = next_cursor_index - 1;
-                            *token = String::from(char);
-                        } else if char == ' ' || char == '\n' {
-                            *status = Status::Initial;
-                            literal_status = LiteralStatus::Initial;
-                            tokens.push(Token {
-                                _type: TokenType {
-                                    label: String::from("number"),
-                                    keyword: MyOption(None),
-                                    beforeExpr: false,
-                                    startsExpr: false,
-                                    isLoop: false,
-                                    isAssign: false,
-                                    prefix: false,
-                                    postfix: false,
-                                },
-                                value: TokenValue::String(token.clone()),
-                                start: start,
-                                end: next_cursor_index - 1,
-                            });
-                            *token = String::new();
-                        } else {
-                            literal_status = get_literal_status(
-                                &char.to_string(),
-                                &tokens,
-                                &punctuators_trie_node,
-                            );
-                            if !matches!(literal_status, LiteralStatus::Initial) {
-                                tokens.push(Token {
-                                    _type: TokenType {
-                                        label: String::from("number"),
-                                        keyword: MyOption(None),
-                                        beforeExpr: false,
-                                        startsExpr: false,
-                                        isLoop: false,
-                                        isAssign: false,
-                                        prefix: false,
-                                        postfix: false,
-                                    },
-                                    value: TokenValue::String(token.clone()),
-                                    start: start,
-                                    end: next_cursor_index - 1,
-                                });
-                                start = next_cursor_index - 1;
-                                *token = String::from(char);
-                            } else {
-                                return Err(String::from("Identifier directly after number"));
-                            }
-                        }
-                    }
-                    // 正则字面量
-                    LiteralStatus::RegularExpression => {
-                        let mut lex_regular_expression_action_extract_params =
-                            create_lex_regular_expression_action_extract_params(
-                                &mut status,
-                                &mut tokens,
-                                &mut token,
-                                &punctuators_trie_node,
-                            );
-                        if let Err(error_message) = lex_regular_expression_action(
-                            char,
-                            start,
-                            next_cursor_index,
-                            &mut lex_regular_expression_action_extract_params,
-                        ) {
-                            return Err(error_message);
-                        }
-                    }
-                    LiteralStatus::Initial => {
-                        return Err(String::from("unknown error"));
+            Some(b'*') => match self.code[start + 2..].find("*/") {
+                Some(length) => {
+                    let comment = &self.code[start + 2..start + 2 + length];
+                    self.newline_before =
+                        self.newline_before || comment.chars().any(is_line_terminator);
+                    self.pos = start + 2 + length + 2;
+                }
+                None => return Err(self.error("Unterminated comment", start)),
+            },
+            _ if self.regular_expression_allowed() => self.scan_regular_expression()?,
+            _ => self.scan_punctuator()?,
+        }
+        return Ok(());
+    }
+
+    fn scan_regular_expression(&mut self) -> Result<(), ParseError> {
+        let start = self.pos;
+        let mut pos = start + 1;
+        let mut in_class = false;
+        loop {
+            let rest = &self.bytes[pos.min(self.bytes.len())..];
+            match rest.first() {
+                None | Some(b'\n' | b'\r') => {
+                    return Err(self.error("Unterminated regular expression", start))
+                }
+                _ if is_unicode_line_terminator(rest) => {
+                    return Err(self.error("Unterminated regular expression", start))
+                }
+                Some(b'\\') => {
+                    // 转义的是换行时同样未闭合，由下一轮检查
+                    pos += 1;
+                    match self.bytes.get(pos) {
+                        Some(b'\n' | b'\r') | None => {}
+                        Some(&byte) => pos += char_length(byte),
                     }
                 }
+                Some(b'[') => {
+                    in_class = true;
+                    pos += 1;
+                }
+                Some(b']') => {
+                    in_class = false;
+                    pos += 1;
+                }
+                Some(b'/') if !in_class => break,
+                Some(_) => pos += 1,
             }
-            // 模板字符串状态
-            Status::Template => {
-                let mut lex_template_action_extract_params =
-                    create_lex_template_action_extract_params(
-                        &mut status,
-                        &mut tokens,
-                        &mut under_template,
-                        false,
+        }
+        let pattern = &self.code[start + 1..pos];
+        pos += 1;
+        let flags_start = pos;
+        while let Some(&byte) = self.bytes.get(pos) {
+            if byte >= 0x80 || !matches!(char_class(byte), CharClass::Identifier | CharClass::Digit)
+            {
+                break;
+            }
+            pos += 1;
+        }
+        let flags = &self.code[flags_start..pos];
+        let invalid_flag = flags
+            .char_indices()
+            .any(|(index, flag)| !"dgimsuyv".contains(flag) || flags[..index].contains(flag));
+        if invalid_flag {
+            return Err(self.error("Invalid regular expression flag", flags_start));
+        }
+        if let Err(message) = check_regular_expression_pattern(pattern) {
+            return Err(self.error(
+                format!("Invalid regular expression: /{}/: {}", pattern, message),
+                start,
+            ));
+        }
+        self.pos = pos;
+        let value = TokenValue::RegexpValue(RegexpValue {
+            // 与 acorn 一致，pattern 不包含首尾的 '/'
+            pattern: String::from(pattern),
+            flags: String::from(flags),
+            value: String::from(&self.code[start..pos]),
+        });
+        self.push(token_type("regexp", false, false, true), value, start, pos);
+        return Ok(());
+    }
+
+    fn scan_punctuator(&mut self) -> Result<(), ParseError> {
+        let start = self.pos;
+        let next_is_digit = |offset: usize| {
+            return matches!(self.bytes.get(start + offset), Some(byte) if byte.is_ascii_digit());
+        };
+        // `.5` 是数字
+        if self.bytes[start] == b'.' && next_is_digit(1) {
+            return self.scan_number(start);
+        }
+        let mut length = punctuator_length(&self.bytes[start..]);
+        if length == 0 {
+            return Err(self.error("Unexpected character", start));
+        }
+        // `a?.5:1` 是条件表达式而不是可选链
+        if &self.bytes[start..start + length] == b"?." && next_is_digit(2) {
+            length = 1;
+        }
+        self.pos = start + length;
+        let text = &self.code[start..self.pos];
+        let mut regexp_after_close = false;
+        match text {
+            "{" => {
+                let context = if self.brace_is_block() {
+                    BraceContext::Block
+                } else {
+                    BraceContext::Object
+                };
+                self.context.push(context);
+            }
+            "(" => {
+                let statement = matches!(self.tokens.last(), Some(token) if matches!(token._type.label.as_str(), "if" | "while" | "for" | "with"));
+                let context = if statement {
+                    BraceContext::StatementParen
+                } else {
+                    BraceContext::ExpressionParen
+                };
+                self.context.push(context);
+            }
+            ")" | "}" => {
+                let mut closed = self.context.pop();
+                if closed == Some(BraceContext::Template) {
+                    // 与 '${' 配对的 '}'，之后回到模板字符串
+                    self.push(
+                        token_type("}", false, false, false),
+                        TokenValue::None,
+                        start,
+                        self.pos,
                     );
-                if let Err(error_message) = lex_template_action(
-                    char,
-                    start,
-                    next_cursor_index,
-                    &mut lex_template_action_extract_params,
-                ) {
-                    return Err(error_message);
+                    return self.scan_template(self.pos);
+                }
+                // 函数体或类体结束时，function 或 class 关键字的上下文也随之结束
+                if closed == Some(BraceContext::Block)
+                    && matches!(
+                        self.context.last(),
+                        Some(BraceContext::FunctionExpression | BraceContext::FunctionStatement)
+                    )
+                {
+                    closed = self.context.pop();
                 }
+                regexp_after_close = !closed.is_some_and(BraceContext::is_expression);
             }
-            Status::Comment => match comment_status {
-                CommentStatus::SingleLine => {
-                    if char == '\n' {
-                        *token = String::new();
-                        *status = Status::Initial;
-                        comment_status = CommentStatus::None;
-                    }
+            _ => {}
+        }
+        let value = TokenValue::String(String::from(text));
+        self.push(token_type(text, false, true, false), value, start, self.pos);
+        self.regexp_after_close = regexp_after_close;
+        return Ok(());
+    }
+}
+
+// token 的偏移是递增的，依次推进计算行列位置，避免在很长的一行中每次都从行首数起
+struct PositionCursor<'a> {
+    bytes: &'a [u8],
+    code: &'a str,
+    offset: usize,
+    position: Position,
+}
+
+impl<'a> PositionCursor<'a> {
+    fn new(code: &'a str) -> Self {
+        PositionCursor {
+            bytes: code.as_bytes(),
+            code,
+            offset: 0,
+            position: Position { line: 1, column: 0 },
+        }
+    }
+
+    // 行号从 1 开始，列号从 0 开始并按 UTF-16 码元计数（与 acorn 一致），\r\n 视为一个换行
+    fn advance(&mut self, offset: usize) -> Position {
+        while self.offset < offset {
+            let byte = self.bytes[self.offset];
+            if byte < 0x80 {
+                let line_break = byte == b'\n'
+                    || (byte == b'\r' && self.bytes.get(self.offset + 1) != Some(&b'\n'));
+                if line_break {
+                    self.position.line += 1;
+                    self.position.column = 0;
+                } else {
+                    self.position.column += 1;
                 }
-                CommentStatus::Multiline => {
-                    let len = token.len();
-                    let last_char = token.chars().nth(len - 1).unwrap();
-                    if last_char == '*' && char == '/' {
-                        *token = String::new();
-                        *status = Status::Initial;
-                        comment_status = CommentStatus::None;
-                    } else {
-                        token.push(char);
-                    }
+                self.offset += 1;
+            } else {
+                let char = self.code[self.offset..].chars().next().unwrap();
+                if is_line_terminator(char) {
+                    self.position.line += 1;
+                    self.position.column = 0;
+                } else {
+                    self.position.column += char.len_utf16();
                 }
-                CommentStatus::None => return Err(String::from("unknown error")),
-            },
+                self.offset += char.len_utf8();
+            }
+        }
+        return self.position;
+    }
+}
+
+pub fn tokenizer(code: &str) -> Result<Vec<Token>, ParseError> {
+    let mut scanner = Scanner::new(code);
+    scanner.scan()?;
+    let mut tokens = scanner.tokens;
+    let mut cursor = PositionCursor::new(code);
+    for token in tokens.iter_mut() {
+        token.loc = SourceLocation {
+            start: cursor.advance(token.start),
+            end: cursor.advance(token.end),
+        };
+    }
+    return Ok(tokens);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenizer, TokenValue};
+
+    // 每个 '/' 开始的 token 是正则还是除号
+    fn slashes(source: &str) -> Vec<&'static str> {
+        let tokens = tokenizer(source).unwrap_or_else(|error| panic!("{}: {}", source, error));
+        return tokens
+            .iter()
+            .filter_map(|token| match token._type.label.as_str() {
+                "regexp" => Some("regexp"),
+                "/" => Some("/"),
+                _ => None,
+            })
+            .collect();
+    }
+
+    #[test]
+    fn regular_expression_or_division() {
+        let cases: [(&str, &[&str]); 20] = [
+            ("if (a) /x/.test(b)", &["regexp"]),
+            ("while (a) /x/g.exec(b)", &["regexp"]),
+            ("for (;;) /x/", &["regexp"]),
+            ("function f(){} /x/.test(b)", &["regexp"]),
+            ("{} /x/.test(b)", &["regexp"]),
+            ("a: {} /x/", &["regexp"]),
+            ("class A { m() {} } /x/", &["regexp"]),
+            ("x = {} / 2", &["/"]),
+            ("a = b / c / d", &["/", "/"]),
+            ("(a) / 2", &["/"]),
+            ("a[0] / 2", &["/"]),
+            ("x = function () {} / 2", &["/"]),
+            ("x = class {} / 2", &["/"]),
+            ("o = { a() {} } / 2", &["/"]),
+            ("x = `a${b}c` / 2", &["/"]),
+            ("`${ {a: 1} }` / 2", &["/"]),
+            ("`${ /x/ }`", &["regexp"]),
+            ("a.function / 2", &["/"]),
+            ("yield /x/", &["regexp"]),
+            ("a.yield / 2", &["/"]),
+        ];
+        for (source, expected) in cases {
+            assert_eq!(slashes(source), expected, "{}", source);
+        }
+    }
+
+    // 源码中只有一个 token 时，它的名称和是否含有转义
+    fn single_name(source: &str) -> (String, bool) {
+        let tokens = tokenizer(source).unwrap_or_else(|error| panic!("{}: {}", source, error));
+        assert_eq!(tokens.len(), 1, "{}", source);
+        let token = &tokens[0];
+        assert_eq!(token._type.label, "name", "{}", source);
+        match &token.value {
+            TokenValue::String(name) => return (name.clone(), token.escaped),
+            _ => panic!("{}: not a name", source),
+        }
+    }
+
+    #[test]
+    fn unicode_identifiers() {
+        let cases = [
+            ("_$", "_$", false),
+            ("caf\u{e9}", "caf\u{e9}", false),
+            // 数学字母 U+1D400 和 CJK 扩展 B 的 U+20000 在 BMP 之外
+            ("\u{1D400}bc", "\u{1D400}bc", false),
+            ("\u{20000}", "\u{20000}", false),
+            // U+212E 和 U+2118 属于 Other_ID_Start，U+00B7 属于 Other_ID_Continue
+            ("\u{212E}", "\u{212E}", false),
+            ("\u{2118}", "\u{2118}", false),
+            ("x\u{B7}y", "x\u{B7}y", false),
+            // ZWNJ 和 ZWJ 可以出现在标识符中间
+            ("a\u{200C}b\u{200D}", "a\u{200C}b\u{200D}", false),
+            ("\\u0061bc", "abc", true),
+            ("\\u{62}c", "bc", true),
+            ("a\\u{1D400}", "a\u{1D400}", true),
+            ("v\\u0061r", "var", true),
+        ];
+        for (source, name, escaped) in cases {
+            assert_eq!(
+                single_name(source),
+                (String::from(name), escaped),
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_identifiers() {
+        let cases = [
+            ("\\u0030a", "Invalid Unicode escape"),
+            ("a\\u0020", "Invalid Unicode escape"),
+            ("a\\x41", "Expecting Unicode escape sequence"),
+            ("\u{20AC}", "Unexpected character"),
+            ("\u{B7}a", "Unexpected character"),
+        ];
+        for (source, message) in cases {
+            match tokenizer(source) {
+                Ok(_) => panic!("{}: expected error '{}'", source, message),
+                Err(error) => assert!(
+                    error.description().contains(message),
+                    "{}: {}",
+                    source,
+                    error
+                ),
+            }
+        }
+    }
+
+    // 一个 token 的 (起始行, 起始列, 结束行, 结束列)
+    type Location = (usize, usize, usize, usize);
+
+    fn locations(source: &str) -> Vec<Location> {
+        let tokens = tokenizer(source).unwrap_or_else(|error| panic!("{}: {}", source, error));
+        return tokens
+            .iter()
+            .map(|token| {
+                (
+                    token.loc.start.line,
+                    token.loc.start.column,
+                    token.loc.end.line,
+                    token.loc.end.column,
+                )
+            })
+            .collect();
+    }
+
+    #[test]
+    fn token_locations() {
+        let cases: [(&str, &[Location]); 8] = [
+            ("a\r\nb", &[(1, 0, 1, 1), (2, 0, 2, 1)]),
+            ("a\rb", &[(1, 0, 1, 1), (2, 0, 2, 1)]),
+            ("a\n\rb", &[(1, 0, 1, 1), (3, 0, 3, 1)]),
+            (
+                "a\u{2028}b\u{2029}c",
+                &[(1, 0, 1, 1), (2, 0, 2, 1), (3, 0, 3, 1)],
+            ),
+            // 列号按 UTF-16 码元计数，BMP 之外的字符占两列
+            ("'\u{1D400}' b", &[(1, 0, 1, 4), (1, 5, 1, 6)]),
+            (
+                "\u{1D400}\u{20000} = \u{e9}",
+                &[(1, 0, 1, 4), (1, 5, 1, 6), (1, 7, 1, 8)],
+            ),
+            // 注释和字符串的续行中的换行同样计入行号
+            ("/* \r\n\u{2028} */ a", &[(3, 4, 3, 5)]),
+            ("'a\\\r\nb' c", &[(1, 0, 2, 2), (2, 3, 2, 4)]),
+        ];
+        for (source, expected) in cases {
+            assert_eq!(locations(source), expected, "{:?}", source);
+        }
+    }
+
+    #[test]
+    fn lexical_error_positions() {
+        let cases = [
+            ("a\r\n\u{1D400} = @", 2, 5),
+            ("a\u{2029}  '\\x4'", 2, 3),
+            ("`a\r\rb${#", 3, 3),
+        ];
+        for (source, line, column) in cases {
+            match tokenizer(source) {
+                Ok(_) => panic!("{:?}: expected error", source),
+                Err(error) => assert_eq!(
+                    (error.line, error.column),
+                    (line, column),
+                    "{:?}: {}",
+                    source,
+                    error
+                ),
+            }
         }
     }
-    // TODO:非初始状态下未匹配完就结束的情况
-    return Ok(tokens.clone());
 }
@@ -4,15 +4,33 @@ pub struct GramAnalysisResult<T> {
     pub next_cursor: usize,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Position {
+    // 从 1 开始
+    pub line: usize,
+    // 从 0 开始
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SourceLocation {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SourceType {
     script,
     module,
 }
 
 impl SourceType {
-    const script: &'static str = "script";
-    const module: &'static str = "module";
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceType::script => "script",
+            SourceType::module => "module",
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -23,17 +41,53 @@ pub enum Kind {
 }
 
 impl Kind {
-    const Init: &'static str = "init";
-    const Get: &'static str = "get";
-    const Set: &'static str = "set";
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Kind::Init => "init",
+            Kind::Get => "get",
+            Kind::Set => "set",
+        }
+    }
 }
 
+// 类中的成员
 #[derive(Debug)]
-pub enum PropertyKey<'a> {
-    Identifier(Identifier),
-    ComputedPropertyKey(Expression<'a>),
+pub enum ClassElement {
+    MethodDefinition(MethodDefinition),
+    PropertyDefinition(PropertyDefinition),
+    StaticBlock(StaticBlock),
 }
+
+// 类方法的种类
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MethodKind {
+    Constructor,
+    Method,
+    Get,
+    Set,
+}
+
+impl MethodKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MethodKind::Constructor => "constructor",
+            MethodKind::Method => "method",
+            MethodKind::Get => "get",
+            MethodKind::Set => "set",
+        }
+    }
+}
+
 #[derive(Debug)]
+pub enum PropertyKey {
+    Identifier(Identifier),
+    // 字符串或数字作为属性名，如 { "a": 1, 2: 3 }
+    Literal(Literal),
+    ComputedPropertyKey(Expression),
+    // 只出现在类中，如 class A { #x = 1 }
+    PrivateIdentifier(PrivateIdentifier),
+}
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ASTType {
     Program,
     VariableDeclaration,
@@ -47,128 +101,394 @@ pub enum ASTType {
     FunctionDeclaration,
     BlockStatement,
     Property,
+    ExpressionStatement,
+    AssignmentPattern,
+    MemberExpression,
+    CallExpression,
+    NewExpression,
+    ConditionalExpression,
+    BinaryExpression,
+    LogicalExpression,
+    UnaryExpression,
+    UpdateExpression,
+    AssignmentExpression,
+    ArrayPattern,
+    ChainExpression,
+    ThisExpression,
+    SpreadElement,
+    ObjectPattern,
+    RestElement,
+    IfStatement,
+    ForStatement,
+    ForInStatement,
+    ForOfStatement,
+    WhileStatement,
+    DoWhileStatement,
+    SwitchStatement,
+    SwitchCase,
+    BreakStatement,
+    ContinueStatement,
+    LabeledStatement,
+    EmptyStatement,
+    ReturnStatement,
+    ThrowStatement,
+    TryStatement,
+    CatchClause,
+    DebuggerStatement,
+    WithStatement,
+    ClassDeclaration,
+    ClassExpression,
+    ClassBody,
+    MethodDefinition,
+    PropertyDefinition,
+    StaticBlock,
+    PrivateIdentifier,
+    Super,
+    ImportDeclaration,
+    ImportSpecifier,
+    ImportDefaultSpecifier,
+    ImportNamespaceSpecifier,
+    ImportAttribute,
+    ExportNamedDeclaration,
+    ExportSpecifier,
+    ExportDefaultDeclaration,
+    ExportAllDeclaration,
+    ImportExpression,
+    MetaProperty,
+    TemplateLiteral,
+    TemplateElement,
+    TaggedTemplateExpression,
+    AwaitExpression,
+    YieldExpression,
+    SequenceExpression,
 }
 
 impl ASTType {
-    const Program: &'static str = "Program";
-    const VariableDeclaration: &'static str = "VariableDeclaration";
-    const VariableDeclarator: &'static str = "VariableDeclarator";
-    const Identifier: &'static str = "Identifier";
-    const ArrayExpression: &'static str = "ArrayExpression";
-    const ObjectExpression: &'static str = "ObjectExpression";
-    const FunctionExpression: &'static str = "FunctionExpression";
-    const ArrowFunctionExpression: &'static str = "ArrowFunctionExpression";
-    const Literal: &'static str = "Literal";
-    const FunctionDeclaration: &'static str = "FunctionDeclaration";
-    const BlockStatement: &'static str = "BlockStatement";
-    const Property: &'static str = "Property";
-}
-
-#[derive(Debug)]
-pub enum AST<'a> {
-    Program(Program<'a>),
-    VariableDeclaration(VariableDeclaration<'a>),
-    VariableDeclarator(VariableDeclarator<'a>),
-    Identifier(Identifier),
-    ArrayExpression(ArrayExpression<'a>),
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ASTType::Program => "Program",
+            ASTType::VariableDeclaration => "VariableDeclaration",
+            ASTType::VariableDeclarator => "VariableDeclarator",
+            ASTType::Identifier => "Identifier",
+            ASTType::ArrayExpression => "ArrayExpression",
+            ASTType::ObjectExpression => "ObjectExpression",
+            ASTType::FunctionExpression => "FunctionExpression",
+            ASTType::ArrowFunctionExpression => "ArrowFunctionExpression",
+            ASTType::Literal => "Literal",
+            ASTType::FunctionDeclaration => "FunctionDeclaration",
+            ASTType::BlockStatement => "BlockStatement",
+            ASTType::Property => "Property",
+            ASTType::ExpressionStatement => "ExpressionStatement",
+            ASTType::AssignmentPattern => "AssignmentPattern",
+            ASTType::MemberExpression => "MemberExpression",
+            ASTType::CallExpression => "CallExpression",
+            ASTType::NewExpression => "NewExpression",
+            ASTType::ConditionalExpression => "ConditionalExpression",
+            ASTType::BinaryExpression => "BinaryExpression",
+            ASTType::LogicalExpression => "LogicalExpression",
+            ASTType::UnaryExpression => "UnaryExpression",
+            ASTType::UpdateExpression => "UpdateExpression",
+            ASTType::AssignmentExpression => "AssignmentExpression",
+            ASTType::ArrayPattern => "ArrayPattern",
+            ASTType::ChainExpression => "ChainExpression",
+            ASTType::ThisExpression => "ThisExpression",
+            ASTType::SpreadElement => "SpreadElement",
+            ASTType::ObjectPattern => "ObjectPattern",
+            ASTType::RestElement => "RestElement",
+            ASTType::IfStatement => "IfStatement",
+            ASTType::ForStatement => "ForStatement",
+            ASTType::ForInStatement => "ForInStatement",
+            ASTType::ForOfStatement => "ForOfStatement",
+            ASTType::WhileStatement => "WhileStatement",
+            ASTType::DoWhileStatement => "DoWhileStatement",
+            ASTType::SwitchStatement => "SwitchStatement",
+            ASTType::SwitchCase => "SwitchCase",
+            ASTType::BreakStatement => "BreakStatement",
+            ASTType::ContinueStatement => "ContinueStatement",
+            ASTType::LabeledStatement => "LabeledStatement",
+            ASTType::EmptyStatement => "EmptyStatement",
+            ASTType::ReturnStatement => "ReturnStatement",
+            ASTType::ThrowStatement => "ThrowStatement",
+            ASTType::TryStatement => "TryStatement",
+            ASTType::CatchClause => "CatchClause",
+            ASTType::DebuggerStatement => "DebuggerStatement",
+            ASTType::WithStatement => "WithStatement",
+            ASTType::ClassDeclaration => "ClassDeclaration",
+            ASTType::ClassExpression => "ClassExpression",
+            ASTType::ClassBody => "ClassBody",
+            ASTType::MethodDefinition => "MethodDefinition",
+            ASTType::PropertyDefinition => "PropertyDefinition",
+            ASTType::StaticBlock => "StaticBlock",
+            ASTType::PrivateIdentifier => "PrivateIdentifier",
+            ASTType::Super => "Super",
+            ASTType::ImportDeclaration => "ImportDeclaration",
+            ASTType::ImportSpecifier => "ImportSpecifier",
+            ASTType::ImportDefaultSpecifier => "ImportDefaultSpecifier",
+            ASTType::ImportNamespaceSpecifier => "ImportNamespaceSpecifier",
+            ASTType::ImportAttribute => "ImportAttribute",
+            ASTType::ExportNamedDeclaration => "ExportNamedDeclaration",
+            ASTType::ExportSpecifier => "ExportSpecifier",
+            ASTType::ExportDefaultDeclaration => "ExportDefaultDeclaration",
+            ASTType::ExportAllDeclaration => "ExportAllDeclaration",
+            ASTType::ImportExpression => "ImportExpression",
+            ASTType::MetaProperty => "MetaProperty",
+            ASTType::TemplateLiteral => "TemplateLiteral",
+            ASTType::TemplateElement => "TemplateElement",
+            ASTType::TaggedTemplateExpression => "TaggedTemplateExpression",
+            ASTType::AwaitExpression => "AwaitExpression",
+            ASTType::YieldExpression => "YieldExpression",
+            ASTType::SequenceExpression => "SequenceExpression",
+        }
+    }
 }
 
 #[derive(Debug)]
-pub enum Body<'a> {
-    VariableDeclaration(VariableDeclaration<'a>),
-    FunctionDeclaration(FunctionDeclaration<'a>),
-    ExpressionStatement(ExpressionStatement<'a>),
-    BlockStatement(BlockStatement<'a>),
+pub enum AST {
+    Program(Program),
+    VariableDeclaration(VariableDeclaration),
+    VariableDeclarator(VariableDeclarator),
+    Identifier(Identifier),
+    ArrayExpression(ArrayExpression),
 }
 
 #[derive(Debug)]
-enum FunctionParams<'a> {
+pub enum Body {
+    VariableDeclaration(VariableDeclaration),
+    FunctionDeclaration(FunctionDeclaration),
+    ExpressionStatement(ExpressionStatement),
+    BlockStatement(BlockStatement),
+    IfStatement(Option<Box<IfStatement>>),
+    ForStatement(Option<Box<ForStatement>>),
+    ForInStatement(Option<Box<ForInStatement>>),
+    ForOfStatement(Option<Box<ForOfStatement>>),
+    WhileStatement(Option<Box<WhileStatement>>),
+    DoWhileStatement(Option<Box<DoWhileStatement>>),
+    SwitchStatement(SwitchStatement),
+    BreakStatement(BreakStatement),
+    ContinueStatement(ContinueStatement),
+    LabeledStatement(Option<Box<LabeledStatement>>),
+    EmptyStatement(EmptyStatement),
+    ReturnStatement(ReturnStatement),
+    ThrowStatement(ThrowStatement),
+    TryStatement(TryStatement),
+    DebuggerStatement(DebuggerStatement),
+    WithStatement(Option<Box<WithStatement>>),
+    ClassDeclaration(ClassDeclaration),
+    // 以下只出现在模块的顶层
+    ImportDeclaration(ImportDeclaration),
+    ExportNamedDeclaration(Option<Box<ExportNamedDeclaration>>),
+    ExportDefaultDeclaration(Option<Box<ExportDefaultDeclaration>>),
+    ExportAllDeclaration(ExportAllDeclaration),
+}
+
+// import 和 export 中的名称，可以是标识符或字符串，如 export { a as "b c" }
+#[derive(Debug, Clone)]
+pub enum ModuleExportName {
     Identifier(Identifier),
-    AssignmentPattern(AssignmentPattern<'a>),
+    Literal(Literal),
 }
 
 #[derive(Debug)]
-enum AssignmentPatternRight<'a> {
-    Literal(Literal<'a>),
-    Identifier(Identifier),
-    MemberExpression(MemberExpression<'a>),
-    CallExpression(Option<Box<CallExpression<'a>>>),
-    ArrayExpression(ArrayExpression<'a>),
-    ObjectExpression(ObjectExpression<'a>),
+pub enum ImportDeclarationSpecifier {
+    ImportSpecifier(ImportSpecifier),
+    ImportDefaultSpecifier(ImportDefaultSpecifier),
+    ImportNamespaceSpecifier(ImportNamespaceSpecifier),
 }
 
+// export default 之后的内容，函数和类声明可以省略名称
 #[derive(Debug)]
-enum MemberExpressionObject<'a> {
-    Identifier(Identifier),
-    MemberExpression(Option<Box<MemberExpression<'a>>>),
-    CallExpression(Option<Box<CallExpression<'a>>>),
-    ArrayExpression(ArrayExpression<'a>),
-    ObjectExpression(ObjectExpression<'a>),
+pub enum ExportDefaultDeclarationKind {
+    FunctionDeclaration(FunctionDeclaration),
+    ClassDeclaration(ClassDeclaration),
+    Expression(Expression),
 }
 
+// for 语句括号中的第一部分，如 for (let i = 0; ...) 或 for (i = 0; ...)
 #[derive(Debug)]
-enum MemberExpressionProperty<'a> {
-    Identifier(Identifier),
-    Literal(Literal<'a>),
-    CallExpression(Option<Box<CallExpression<'a>>>),
-    ObjectExpression(ObjectExpression<'a>),
-    ArrayExpression(ArrayExpression<'a>),
-    MemberExpression(Option<Box<MemberExpression<'a>>>),
+pub enum ForStatementInit {
+    VariableDeclaration(VariableDeclaration),
+    Expression(Expression),
 }
 
+// for-in / for-of 的左侧，如 for (const x of y) 或 for (x.y in z)
 #[derive(Debug)]
-enum CallExpressionCallee<'a> {
-    Identifier(Identifier),
-    MemberExpression(Option<Box<MemberExpression<'a>>>),
-    CallExpression(Option<Box<CallExpression<'a>>>),
+pub enum ForInOfLeft {
+    VariableDeclaration(VariableDeclaration),
+    Pattern(Pattern),
+}
+
+// 语法分析的上下文，用于检查 break/continue/return 等是否合法，进入函数体时除 strict 外重新开始
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    pub labels: Vec<Label>,
+    pub in_iteration: bool,
+    pub in_switch: bool,
+    pub in_function: bool,
+    // 严格模式，模块代码和类始终是严格模式
+    pub strict: bool,
+    // 是否在子类的构造函数中，允许 super()
+    pub allow_super_call: bool,
+    // 是否在方法、类字段或静态块中，允许 super.x 和 super[x]
+    pub allow_super_property: bool,
+    // 是否是模块代码，允许 import.meta
+    pub module: bool,
+    // 是否在非箭头函数、类字段初始值或静态块中，允许 new.target
+    pub allow_new_target: bool,
+    // 是否在异步函数或模块的顶层，此时 await 是运算符
+    pub in_async: bool,
+    // 是否在生成器中，此时 yield 是运算符
+    pub in_generator: bool,
+    // 是否在参数列表中，此时不能使用 await 和 yield 表达式
+    pub in_parameters: bool,
+    // 是否在类字段初始值或静态块中，此时不能引用 arguments，箭头函数中同样如此
+    pub in_class_initializer: bool,
+    // 是否直接在静态块中，此时 await 不能用作标识符
+    pub in_static_block: bool,
+    // 外层各个类中声明的私有名称，#x 只能引用其中的名称
+    pub private_names: Vec<String>,
+    // 语句、表达式和绑定模式的嵌套层数，见 nested_context
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub name: String,
+    // 标签直接标记循环语句时才能被 continue 使用
+    pub is_loop: bool,
 }
 
 #[derive(Debug)]
-enum CallExpressionArgument<'a> {
+pub enum Expression {
     Identifier(Identifier),
-    Literal(Literal<'a>),
-    MemberExpression(Option<Box<MemberExpression<'a>>>),
-    CallExpression(Option<Box<CallExpression<'a>>>),
-    ObjectExpression(ObjectExpression<'a>),
-    ArrayExpression(ArrayExpression<'a>),
+    ConditionalExpression(Option<Box<ConditionalExpression>>),
+    BinaryExpression(Option<Box<BinaryExpression>>),
+    LogicalExpression(Option<Box<LogicalExpression>>),
+    UnaryExpression(Option<Box<UnaryExpression>>),
+    UpdateExpression(Option<Box<UpdateExpression>>),
+    AssignmentExpression(Option<Box<AssignmentExpression>>),
+    ChainExpression(Option<Box<ChainExpression>>),
+    ThisExpression(ThisExpression),
+    CallExpression(Option<Box<CallExpression>>),
+    MemberExpression(Option<Box<MemberExpression>>),
+    ArrayExpression(Option<Box<ArrayExpression>>),
+    ObjectExpression(Option<Box<ObjectExpression>>),
+    Literal(Option<Box<Literal>>),
+    NewExpression(Option<Box<NewExpression>>),
+    FunctionExpression(Option<Box<FunctionExpression>>),
+    ArrowFunctionExpression(Option<Box<ArrowFunctionExpression>>),
+    ClassExpression(Option<Box<ClassExpression>>),
+    // 只出现在成员表达式的属性和 #x in obj 中
+    PrivateIdentifier(PrivateIdentifier),
+    Super(Super),
+    // 动态导入，如 import("./a.js")
+    ImportExpression(Option<Box<ImportExpression>>),
+    // import.meta 和 new.target
+    MetaProperty(Option<Box<MetaProperty>>),
+    TemplateLiteral(Option<Box<TemplateLiteral>>),
+    // 标签模板，如 tag`a${b}`
+    TaggedTemplateExpression(Option<Box<TaggedTemplateExpression>>),
+    AwaitExpression(Option<Box<AwaitExpression>>),
+    YieldExpression(Option<Box<YieldExpression>>),
+    // 逗号运算符，如 a, b
+    SequenceExpression(Option<Box<SequenceExpression>>),
+}
+
+impl Expression {
+    // 表达式在源码中的起始位置，解析结果中不会出现为 None 的 Option<Box<..>>
+    pub fn start(&self) -> usize {
+        match self {
+            Expression::Identifier(ast) => return ast.start,
+            Expression::ConditionalExpression(ast) => {
+                return ast.as_ref().map_or(0, |ast| ast.start)
+            }
+            Expression::BinaryExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::LogicalExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::UnaryExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::UpdateExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::AssignmentExpression(ast) => {
+                return ast.as_ref().map_or(0, |ast| ast.start)
+            }
+            Expression::ChainExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::ThisExpression(ast) => return ast.start,
+            Expression::CallExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::MemberExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::ArrayExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::ObjectExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::Literal(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::NewExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::FunctionExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::ArrowFunctionExpression(ast) => {
+                return ast.as_ref().map_or(0, |ast| ast.start)
+            }
+            Expression::ClassExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::PrivateIdentifier(ast) => return ast.start,
+            Expression::Super(ast) => return ast.start,
+            Expression::ImportExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::MetaProperty(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::TemplateLiteral(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::TaggedTemplateExpression(ast) => {
+                return ast.as_ref().map_or(0, |ast| ast.start)
+            }
+            Expression::AwaitExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::YieldExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+            Expression::SequenceExpression(ast) => return ast.as_ref().map_or(0, |ast| ast.start),
+        }
+    }
 }
 
+// 赋值表达式的左侧、函数参数和解构中的绑定目标
 #[derive(Debug)]
-pub enum Expression<'a> {
+pub enum Pattern {
     Identifier(Identifier),
-    ConditionalExpression(Option<Box<ConditionalExpression<'a>>>),
-    BinaryExpression(Option<Box<BinaryExpression<'a>>>),
-    CallExpression(Option<Box<CallExpression<'a>>>),
-    MemberExpression(Option<Box<MemberExpression<'a>>>),
-    ArrayExpression(Option<Box<ArrayExpression<'a>>>),
-    Literal(Literal<'a>),
-    NewExpression(Option<Box<NewExpression<'a>>>),
-    FunctionExpression(FunctionExpression<'a>),
-    ArrowFunctionExpression(ArrowFunctionExpression<'a>),
+    // 只出现在赋值表达式的左侧，不能用于声明和参数
+    MemberExpression(Option<Box<MemberExpression>>),
+    ArrayPattern(ArrayPattern),
+    ObjectPattern(ObjectPattern),
+    RestElement(Option<Box<RestElement>>),
+    AssignmentPattern(Option<Box<AssignmentPattern>>),
+}
+
+#[derive(Debug)]
+pub enum ObjectPatternProperty {
+    Property(AssignmentProperty),
+    RestElement(RestElement),
 }
 
 #[derive(Debug)]
-pub struct Program<'a> {
+pub enum ArrowFunctionBody {
+    BlockStatement(BlockStatement),
+    // 简写的函数体，如 x => x + 1
+    Expression(Expression),
+}
+
+#[derive(Debug)]
+pub struct Program {
     pub _type: ASTType,
-    pub body: Vec<Body<'a>>,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub body: Vec<Body>,
     pub sourceType: SourceType,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Identifier {
     pub _type: ASTType,
     pub start: usize,
     pub end: usize,
+    pub loc: SourceLocation,
     pub name: String,
 }
 
 #[derive(Debug)]
-pub struct VariableDeclarator<'a> {
+pub struct VariableDeclarator {
     pub _type: ASTType,
     pub start: usize,
     pub end: usize,
-    pub id: Identifier,
-    pub init: Expression<'a>,
+    pub loc: SourceLocation,
+    pub id: Pattern,
+    // 没有初始值时为 None，如 let a; 或 for (const x of y)
+    pub init: Option<Expression>,
 }
 
 #[derive(Debug)]
@@ -179,50 +499,81 @@ pub enum DeclarationKind {
 }
 
 impl DeclarationKind {
-    const Const: &'static str = "const";
-    const Let: &'static str = "let";
-    const Var: &'static str = "var";
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DeclarationKind::Const => "const",
+            DeclarationKind::Let => "let",
+            DeclarationKind::Var => "var",
+        }
+    }
 }
 
 #[derive(Debug)]
-pub struct VariableDeclaration<'a> {
+pub struct VariableDeclaration {
     pub _type: ASTType,
     pub start: usize,
     pub end: usize,
-    pub declarations: Vec<VariableDeclarator<'a>>,
+    pub loc: SourceLocation,
+    pub declarations: Vec<VariableDeclarator>,
     pub kind: DeclarationKind,
 }
 
 #[derive(Debug)]
-pub struct ArrayExpression<'a> {
+pub struct ArrayExpression {
     pub _type: ASTType,
     pub start: usize,
     pub end: usize,
-    pub elements: Vec<Expression<'a>>,
+    pub loc: SourceLocation,
+    // 空位为 None，如 [a, , b]
+    pub elements: Vec<Option<ExpressionOrSpread>>,
 }
 
 #[derive(Debug)]
-struct ObjectExpression<'a> {
-    _type: ASTType,
-    start: usize,
-    end: usize,
-    properties: Vec<Property<'a>>,
+pub struct ObjectExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub properties: Vec<ObjectExpressionProperty>,
+}
+
+#[derive(Debug)]
+pub enum ObjectExpressionProperty {
+    Property(Property),
+    SpreadElement(SpreadElement),
+}
+
+// 数组元素和调用参数，可以是 ...展开
+#[derive(Debug)]
+pub enum ExpressionOrSpread {
+    Expression(Expression),
+    SpreadElement(SpreadElement),
+}
+
+#[derive(Debug)]
+pub struct SpreadElement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub argument: Expression,
 }
 
 #[derive(Debug)]
-pub struct Property<'a> {
+pub struct Property {
     pub _type: ASTType,
     pub start: usize,
     pub end: usize,
+    pub loc: SourceLocation,
     pub method: bool,
     pub shorthand: bool,
     pub computed: bool,
-    pub key: PropertyKey<'a>,
-    pub value: Expression<'a>,
+    pub key: PropertyKey,
+    pub value: Expression,
     pub kind: Kind,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum LiteralType {
     STRING,
     NUMBER,
@@ -232,139 +583,726 @@ pub enum LiteralType {
 }
 
 impl LiteralType {
-    const STRING: &'static str = "string";
-    const NUMBER: &'static str = "number";
-    const BOOLEAN: &'static str = "boolean";
-    const NULL: &'static str = "null";
-    const REGEXP: &'static str = "regexp";
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LiteralType::STRING => "string",
+            LiteralType::NUMBER => "number",
+            LiteralType::BOOLEAN => "boolean",
+            LiteralType::NULL => "null",
+            LiteralType::REGEXP => "regexp",
+        }
+    }
+}
+
+// 字面量的值，正则和 BigInt 的值无法用 JSON 表示，分别记录在 regex 和 bigint 中
+#[derive(Debug, Clone)]
+pub enum LiteralValue {
+    String(String),
+    Number(f64),
+    Boolean(bool),
+    Null,
+    RegExp,
+    BigInt,
+}
+
+#[derive(Debug, Clone)]
+pub struct Regex {
+    pub pattern: String,
+    pub flags: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Literal {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub value: LiteralValue,
+    pub raw: String,
+    pub regex: Option<Regex>,
+    // BigInt 字面量去掉后缀 n 和分隔符后的文本，如 0x1_0n 为 "0x10"
+    pub bigint: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct FunctionDeclaration {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    // 只有 export default function () {} 可以省略函数名
+    pub id: Option<Identifier>,
+    pub expression: bool,
+    pub generator: bool,
+    pub _async: bool,
+    pub params: Vec<Pattern>,
+    pub body: BlockStatement,
 }
 
 #[derive(Debug)]
-pub struct Regex<'a> {
-    pub pattern: &'a str,
-    pub flags: &'a str,
+pub struct BlockStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub body: Vec<Body>,
 }
 
 #[derive(Debug)]
-pub struct Literal<'a> {
+pub struct AssignmentPattern {
     pub _type: ASTType,
     pub start: usize,
     pub end: usize,
-    pub value: LiteralType,
-    pub raw: &'a str,
-    pub regex: Option<Regex<'a>>,
+    pub loc: SourceLocation,
+    pub left: Pattern,
+    pub right: Expression,
 }
 
 #[derive(Debug)]
-struct FunctionDeclaration<'a> {
-    _type: ASTType,
-    start: usize,
-    end: usize,
-    id: Identifier,
-    expression: bool,
-    generator: bool,
-    _async: bool,
-    params: Vec<FunctionParams<'a>>,
-    body: BlockStatement<'a>,
+pub struct MemberExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub object: Expression,
+    pub property: Expression,
+    pub computed: bool,
+    pub optional: bool,
 }
 
 #[derive(Debug)]
-pub struct BlockStatement<'a> {
+pub struct CallExpression {
     pub _type: ASTType,
     pub start: usize,
     pub end: usize,
-    pub body: Vec<Body<'a>>,
+    pub loc: SourceLocation,
+    pub callee: Expression,
+    pub arguments: Vec<ExpressionOrSpread>,
+    pub optional: bool,
 }
 
 #[derive(Debug)]
-struct AssignmentPattern<'a> {
-    _type: ASTType,
-    start: usize,
-    end: usize,
-    left: Identifier,
-    right: AssignmentPatternRight<'a>,
+pub struct ExpressionStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub expression: Expression,
+    // 指令序言中的字符串，如 "use strict"，其他语句为 None
+    pub directive: Option<String>,
 }
 
 #[derive(Debug)]
-struct MemberExpression<'a> {
-    _type: ASTType,
-    start: usize,
-    end: usize,
-    object: Expression<'a>,
-    property: Expression<'a>,
-    computed: bool,
-    optional: bool,
+pub struct ConditionalExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub test: Expression,
+    pub consequent: Expression,
+    pub alternate: Expression,
 }
 
 #[derive(Debug)]
-struct CallExpression<'a> {
-    _type: ASTType,
-    start: usize,
-    end: usize,
-    callee: Expression<'a>,
-    arguments: Vec<Expression<'a>>,
-    optional: bool,
+pub struct BinaryExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub left: Expression,
+    pub operator: &'static str,
+    pub right: Expression,
 }
 
 #[derive(Debug)]
-struct ExpressionStatement<'a> {
-    _type: ASTType,
-    start: usize,
-    end: usize,
-    expression: Expression<'a>,
+pub struct LogicalExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub left: Expression,
+    pub operator: &'static str,
+    pub right: Expression,
 }
 
 #[derive(Debug)]
-struct ConditionalExpression<'a> {
-    _type: ASTType,
-    start: usize,
-    end: usize,
-    test: Expression<'a>,
-    consequent: Expression<'a>,
-    alternate: Expression<'a>,
+pub struct UnaryExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub operator: &'static str,
+    pub prefix: bool,
+    pub argument: Expression,
 }
 
 #[derive(Debug)]
-struct BinaryExpression<'a> {
-    _type: ASTType,
-    start: usize,
-    end: usize,
-    left: Expression<'a>,
-    operator: &'static str,
-    right: Expression<'a>,
+pub struct UpdateExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub operator: &'static str,
+    pub prefix: bool,
+    pub argument: Expression,
 }
 
 #[derive(Debug)]
-struct NewExpression<'a> {
-    _type: ASTType,
-    start: usize,
-    end: usize,
-    callee: Expression<'a>,
-    arguments: Vec<Expression<'a>>,
+pub struct AssignmentExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub operator: &'static str,
+    pub left: Pattern,
+    pub right: Expression,
 }
 
 #[derive(Debug)]
-pub struct FunctionExpression<'a> {
+pub struct ArrayPattern {
     pub _type: ASTType,
     pub start: usize,
     pub end: usize,
+    pub loc: SourceLocation,
+    // 空位为 None，如 [a, , b]
+    pub elements: Vec<Option<Pattern>>,
+}
+
+#[derive(Debug)]
+pub struct ObjectPattern {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub properties: Vec<ObjectPatternProperty>,
+}
+
+// ObjectPattern 中的属性，type 与普通属性一样为 Property
+#[derive(Debug)]
+pub struct AssignmentProperty {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub method: bool,
+    pub shorthand: bool,
+    pub computed: bool,
+    pub key: PropertyKey,
+    pub value: Pattern,
+    pub kind: Kind,
+}
+
+#[derive(Debug)]
+pub struct RestElement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub argument: Pattern,
+}
+
+// 包裹含有 `?.` 的整条可选链
+#[derive(Debug)]
+pub struct ChainExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub expression: Expression,
+}
+
+#[derive(Debug)]
+pub struct ThisExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+}
+
+#[derive(Debug)]
+pub struct NewExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub callee: Expression,
+    pub arguments: Vec<ExpressionOrSpread>,
+}
+
+#[derive(Debug)]
+pub struct FunctionExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
     pub id: Option<Identifier>,
     pub expression: bool,
     pub generator: bool,
     pub _async: bool,
-    pub params: Vec<Identifier>,
-    pub body: BlockStatement<'a>,
+    pub params: Vec<Pattern>,
+    pub body: BlockStatement,
 }
 
 #[derive(Debug)]
-pub struct ArrowFunctionExpression<'a> {
+pub struct ArrowFunctionExpression {
     pub _type: ASTType,
     pub start: usize,
     pub end: usize,
+    pub loc: SourceLocation,
     pub id: Option<Identifier>,
     pub expression: bool,
     pub generator: bool,
     pub _async: bool,
-    pub params: Vec<Identifier>,
-    pub body: BlockStatement<'a>,
+    pub params: Vec<Pattern>,
+    pub body: ArrowFunctionBody,
+}
+
+#[derive(Debug)]
+pub struct IfStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub test: Expression,
+    pub consequent: Body,
+    pub alternate: Option<Body>,
+}
+
+#[derive(Debug)]
+pub struct ForStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub init: Option<ForStatementInit>,
+    pub test: Option<Expression>,
+    pub update: Option<Expression>,
+    pub body: Body,
+}
+
+#[derive(Debug)]
+pub struct ForInStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub left: ForInOfLeft,
+    pub right: Expression,
+    pub body: Body,
+}
+
+#[derive(Debug)]
+pub struct ForOfStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub _await: bool,
+    pub left: ForInOfLeft,
+    pub right: Expression,
+    pub body: Body,
+}
+
+#[derive(Debug)]
+pub struct WhileStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub test: Expression,
+    pub body: Body,
+}
+
+#[derive(Debug)]
+pub struct DoWhileStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub body: Body,
+    pub test: Expression,
+}
+
+#[derive(Debug)]
+pub struct SwitchStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub discriminant: Expression,
+    pub cases: Vec<SwitchCase>,
+}
+
+#[derive(Debug)]
+pub struct SwitchCase {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    // default 分支为 None
+    pub test: Option<Expression>,
+    pub consequent: Vec<Body>,
+}
+
+#[derive(Debug)]
+pub struct BreakStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub label: Option<Identifier>,
+}
+
+#[derive(Debug)]
+pub struct ContinueStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub label: Option<Identifier>,
+}
+
+#[derive(Debug)]
+pub struct LabeledStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub label: Identifier,
+    pub body: Body,
+}
+
+#[derive(Debug)]
+pub struct EmptyStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+}
+
+#[derive(Debug)]
+pub struct ReturnStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub argument: Option<Expression>,
+}
+
+#[derive(Debug)]
+pub struct ThrowStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub argument: Expression,
+}
+
+#[derive(Debug)]
+pub struct TryStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub block: BlockStatement,
+    pub handler: Option<CatchClause>,
+    pub finalizer: Option<BlockStatement>,
+}
+
+#[derive(Debug)]
+pub struct CatchClause {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    // 省略绑定时为 None，如 try {} catch {}
+    pub param: Option<Pattern>,
+    pub body: BlockStatement,
+}
+
+#[derive(Debug)]
+pub struct DebuggerStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+}
+
+#[derive(Debug)]
+pub struct WithStatement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub object: Expression,
+    pub body: Body,
+}
+
+#[derive(Debug)]
+pub struct ClassDeclaration {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    // 只有 export default class {} 可以省略类名
+    pub id: Option<Identifier>,
+    pub superClass: Option<Expression>,
+    pub body: ClassBody,
+}
+
+#[derive(Debug)]
+pub struct ClassExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub id: Option<Identifier>,
+    pub superClass: Option<Expression>,
+    pub body: ClassBody,
+}
+
+#[derive(Debug)]
+pub struct ClassBody {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub body: Vec<ClassElement>,
+}
+
+#[derive(Debug)]
+pub struct MethodDefinition {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub _static: bool,
+    pub computed: bool,
+    pub key: PropertyKey,
+    pub kind: MethodKind,
+    pub value: FunctionExpression,
+}
+
+#[derive(Debug)]
+pub struct PropertyDefinition {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub _static: bool,
+    pub computed: bool,
+    pub key: PropertyKey,
+    pub value: Option<Expression>,
+}
+
+#[derive(Debug)]
+pub struct StaticBlock {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub body: Vec<Body>,
+}
+
+#[derive(Debug)]
+pub struct PrivateIdentifier {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    // 不包括开头的 '#'
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct Super {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+}
+
+#[derive(Debug)]
+pub struct ImportDeclaration {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub specifiers: Vec<ImportDeclarationSpecifier>,
+    pub source: Literal,
+    pub attributes: Vec<ImportAttribute>,
+}
+
+#[derive(Debug)]
+pub struct ImportSpecifier {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub imported: ModuleExportName,
+    pub local: Identifier,
+}
+
+#[derive(Debug)]
+pub struct ImportDefaultSpecifier {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub local: Identifier,
+}
+
+#[derive(Debug)]
+pub struct ImportNamespaceSpecifier {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub local: Identifier,
+}
+
+// 导入属性，如 with { type: "json" } 中的 type: "json"
+#[derive(Debug)]
+pub struct ImportAttribute {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub key: ModuleExportName,
+    pub value: Literal,
+}
+
+#[derive(Debug)]
+pub struct ExportNamedDeclaration {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    // export const a = 1 这样直接导出声明时，specifiers 为空
+    pub declaration: Option<Body>,
+    pub specifiers: Vec<ExportSpecifier>,
+    pub source: Option<Literal>,
+    pub attributes: Vec<ImportAttribute>,
+}
+
+#[derive(Debug)]
+pub struct ExportSpecifier {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub local: ModuleExportName,
+    pub exported: ModuleExportName,
+}
+
+#[derive(Debug)]
+pub struct ExportDefaultDeclaration {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub declaration: ExportDefaultDeclarationKind,
+}
+
+#[derive(Debug)]
+pub struct ExportAllDeclaration {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    // export * as ns from "a" 中的 ns，export * from "a" 时为 None
+    pub exported: Option<ModuleExportName>,
+    pub source: Literal,
+    pub attributes: Vec<ImportAttribute>,
+}
+
+#[derive(Debug)]
+pub struct ImportExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub source: Expression,
+    // import(source, options) 的第二个参数
+    pub options: Option<Expression>,
+}
+
+#[derive(Debug)]
+pub struct MetaProperty {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub meta: Identifier,
+    pub property: Identifier,
+}
+
+// 模板字符串，quasis 总是比 expressions 多一个
+#[derive(Debug)]
+pub struct TemplateLiteral {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub quasis: Vec<TemplateElement>,
+    pub expressions: Vec<Expression>,
+}
+
+// 模板字符串中的一段文本，位置只覆盖文本本身，不含 '`'、'${' 和 '}'
+#[derive(Debug)]
+pub struct TemplateElement {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub value: TemplateElementValue,
+    // 是否是最后一段
+    pub tail: bool,
+}
+
+#[derive(Debug)]
+pub struct TemplateElementValue {
+    // 源码中的文本，换行统一为 \n
+    pub raw: String,
+    // 处理转义之后的文本，标签模板中含有非法转义时为 None
+    pub cooked: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct TaggedTemplateExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub tag: Expression,
+    pub quasi: TemplateLiteral,
+}
+
+#[derive(Debug)]
+pub struct AwaitExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub argument: Expression,
+}
+
+#[derive(Debug)]
+pub struct YieldExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    // yield* 委托给另一个可迭代对象
+    pub delegate: bool,
+    pub argument: Option<Expression>,
+}
+
+#[derive(Debug)]
+pub struct SequenceExpression {
+    pub _type: ASTType,
+    pub start: usize,
+    pub end: usize,
+    pub loc: SourceLocation,
+    pub expressions: Vec<Expression>,
 }
@@ -0,0 +1,224 @@
+// 字符串和模板字符串共用的转义序列处理
+
+// 读取 \x 或 \u 之后固定长度的十六进制数字
+pub fn read_hex(chars: &[char], index: usize, length: usize) -> Option<u32> {
+    let digits: String = chars.get(index..index + length)?.iter().collect();
+    if digits.chars().all(|char| char.is_ascii_hexdigit()) {
+        return u32::from_str_radix(&digits, 16).ok();
+    }
+    return None;
+}
+
+// 读取 \u 之后的 XXXX 或 {X...}，返回码点和读取的字符数
+pub fn read_unicode_escape(chars: &[char], index: usize) -> Result<(u32, usize), &'static str> {
+    let bad_escape = "Bad character escape sequence";
+    if chars.get(index) == Some(&'{') {
+        let length = chars[index + 1..]
+            .iter()
+            .position(|char| *char == '}')
+            .ok_or(bad_escape)?;
+        let digits: String = chars[index + 1..index + 1 + length].iter().collect();
+        if digits.is_empty() || !digits.chars().all(|char| char.is_ascii_hexdigit()) {
+            return Err(bad_escape);
+        }
+        match u32::from_str_radix(&digits, 16) {
+            Ok(code_point) if code_point <= 0x10FFFF => return Ok((code_point, length + 2)),
+            _ => return Err("Code point out of bounds"),
+        }
+    }
+    return read_hex(chars, index, 4)
+        .map(|code_point| (code_point, 4))
+        .ok_or(bad_escape);
+}
+
+// 高位代理之后紧跟 \uDC00-\uDFFF 时组合成一个码点，如 😀，返回码点和额外读取的字符数
+pub fn combine_surrogate_pair(chars: &[char], index: usize, code_point: u32) -> (u32, usize) {
+    if (0xD800..0xDC00).contains(&code_point)
+        && chars.get(index) == Some(&'\\')
+        && chars.get(index + 1) == Some(&'u')
+    {
+        if let Some(low) = read_hex(chars, index + 2, 4) {
+            if (0xDC00..0xE000).contains(&low) {
+                return (0x10000 + ((code_point - 0xD800) << 10) + (low - 0xDC00), 6);
+            }
+        }
+    }
+    return (code_point, 0);
+}
+
+// 单独的代理项无法放进 String，用替换字符代替
+pub fn push_code_point(value: &mut String, code_point: u32) {
+    value.push(char::from_u32(code_point).unwrap_or('\u{FFFD}'));
+}
+
+pub struct CookedString {
+    pub value: String,
+    // 第一个旧式八进制转义（如 \01）或 \8、\9 的字节偏移和对应的错误信息，严格模式下不允许
+    pub octal: Option<(usize, &'static str)>,
+}
+
+/*
+* 处理字符串字面量（不含引号）中的转义，出错时返回错误信息和出错字符的字节偏移
+* <EscapeSequence> ::= <CharacterEscapeSequence> | '0' | <LegacyOctalEscapeSequence>
+*                    | <NonOctalDecimalEscapeSequence> | <HexEscapeSequence> | <UnicodeEscapeSequence>
+*/
+pub fn cook_string(body: &str) -> Result<CookedString, (&'static str, usize)> {
+    let chars: Vec<char> = body.chars().collect();
+    let byte_offset = |index: usize| -> usize {
+        return chars[..index].iter().map(|char| char.len_utf8()).sum();
+    };
+    let mut value = String::new();
+    let mut octal = None;
+    let mut index = 0;
+    while index < chars.len() {
+        let char = chars[index];
+        index += 1;
+        if char == '\n' || char == '\r' {
+            return Err(("Unterminated string constant", byte_offset(index - 1)));
+        }
+        if char != '\\' {
+            value.push(char);
+            continue;
+        }
+        let escape_start = byte_offset(index - 1);
+        let escaped = match chars.get(index) {
+            Some(escaped) => *escaped,
+            None => return Err(("Unterminated string constant", escape_start)),
+        };
+        index += 1;
+        match escaped {
+            'n' => value.push('\n'),
+            't' => value.push('\t'),
+            'r' => value.push('\r'),
+            'b' => value.push('\u{8}'),
+            'f' => value.push('\u{c}'),
+            'v' => value.push('\u{b}'),
+            // 续行，反斜杠和换行都不出现在值中
+            '\r' => {
+                if chars.get(index) == Some(&'\n') {
+                    index += 1;
+                }
+            }
+            '\n' | '\u{2028}' | '\u{2029}' => {}
+            '0'..='7' => {
+                // \0 之后不是数字时是空字符，否则是旧式八进制，最大为 \377
+                if escaped == '0'
+                    && !matches!(chars.get(index), Some(next) if next.is_ascii_digit())
+                {
+                    value.push('\0');
+                    continue;
+                }
+                let mut code_point = escaped.to_digit(8).unwrap();
+                let max_length = if escaped <= '3' { 3 } else { 2 };
+                let mut length = 1;
+                while length < max_length {
+                    match chars.get(index).and_then(|next| next.to_digit(8)) {
+                        Some(digit) => {
+                            code_point = code_point * 8 + digit;
+                            index += 1;
+                            length += 1;
+                        }
+                        None => break,
+                    }
+                }
+                push_code_point(&mut value, code_point);
+                octal = octal.or(Some((
+                    escape_start,
+                    "Octal escape sequences are not allowed in strict mode",
+                )));
+            }
+            '8' | '9' => {
+                value.push(escaped);
+                octal = octal.or(Some((
+                    escape_start,
+                    "\\8 and \\9 are not allowed in strict mode",
+                )));
+            }
+            'x' => {
+                let code_point = read_hex(&chars, index, 2)
+                    .ok_or(("Bad character escape sequence", escape_start))?;
+                push_code_point(&mut value, code_point);
+                index += 2;
+            }
+            'u' => {
+                let (code_point, length) = read_unicode_escape(&chars, index)
+                    .map_err(|message| (message, escape_start))?;
+                index += length;
+                let (code_point, length) = combine_surrogate_pair(&chars, index, code_point);
+                index += length;
+                push_code_point(&mut value, code_point);
+            }
+            escaped => value.push(escaped),
+        }
+    }
+    return Ok(CookedString { value, octal });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cook_string;
+
+    #[test]
+    fn cooked_values() {
+        let cases = [
+            ("a\\nb\\tc", "a\nb\tc"),
+            ("\\b\\f\\v\\r", "\u{8}\u{c}\u{b}\r"),
+            ("\\x41\\x62", "Ab"),
+            ("\\u0041", "A"),
+            ("\\u{1F600}", "\u{1F600}"),
+            ("\\uD83D\\uDE00", "\u{1F600}"),
+            // 单独的代理项用替换字符代替
+            ("\\uD83D", "\u{FFFD}"),
+            ("a\\\nb", "ab"),
+            ("a\\\r\nb", "ab"),
+            ("a\\\u{2028}b", "ab"),
+            ("\\0", "\0"),
+            ("\\'\\\"\\\\", "'\"\\"),
+            ("\\q\\-", "q-"),
+        ];
+        for (body, expected) in cases {
+            let cooked = cook_string(body).unwrap();
+            assert_eq!(cooked.value, expected, "{}", body);
+            assert!(cooked.octal.is_none(), "{}", body);
+        }
+    }
+
+    #[test]
+    fn legacy_octal_escapes() {
+        let octal = "Octal escape sequences are not allowed in strict mode";
+        let non_octal = "\\8 and \\9 are not allowed in strict mode";
+        let cases = [
+            ("\\01", "\u{1}", 0, octal),
+            ("\\101", "A", 0, octal),
+            ("\\377", "\u{FF}", 0, octal),
+            ("\\400", "\u{20}0", 0, octal),
+            ("a\\7", "a\u{7}", 1, octal),
+            ("\\08", "\u{0}8", 0, octal),
+            ("\\8", "8", 0, non_octal),
+            ("ab\\9\\01", "ab9\u{1}", 2, non_octal),
+        ];
+        for (body, expected, offset, message) in cases {
+            let cooked = cook_string(body).unwrap();
+            assert_eq!(cooked.value, expected, "{}", body);
+            assert_eq!(cooked.octal, Some((offset, message)), "{}", body);
+        }
+    }
+
+    #[test]
+    fn escape_errors() {
+        let cases = [
+            ("\\x4", "Bad character escape sequence", 0),
+            ("ab\\xZZ", "Bad character escape sequence", 2),
+            ("\\u12", "Bad character escape sequence", 0),
+            ("\\u{}", "Bad character escape sequence", 0),
+            ("\\u{12", "Bad character escape sequence", 0),
+            ("\\u{G}", "Bad character escape sequence", 0),
+            ("\\u{110000}", "Code point out of bounds", 0),
+            ("a\nb", "Unterminated string constant", 1),
+            ("ab\\", "Unterminated string constant", 2),
+        ];
+        for (body, message, offset) in cases {
+            assert_eq!(cook_string(body).err(), Some((message, offset)), "{}", body);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::types::Position;
+
+// ECMAScript 中的行终止符：\n、\r、\u2028、\u2029（\r\n 视为一个）
+pub fn is_line_terminator(char: char) -> bool {
+    matches!(char, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+// ECMAScript 中的空白符与行终止符
+pub fn is_whitespace(char: char) -> bool {
+    is_line_terminator(char)
+        || matches!(
+            char,
+            ' ' | '\t' | '\u{b}' | '\u{c}' | '\u{a0}' | '\u{feff}' | '\u{1680}' | '\u{2000}'
+                ..='\u{200a}' | '\u{202f}' | '\u{205f}' | '\u{3000}'
+        )
+}
+
+/// 记录每一行起始的字节偏移，用于把偏移换算成行号和列号
+pub struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        let mut chars = source.char_indices().peekable();
+        while let Some((index, char)) = chars.next() {
+            if char == '\r' {
+                // \r\n 只算一次换行
+                if let Some((_, '\n')) = chars.peek() {
+                    continue;
+                }
+            }
+            if is_line_terminator(char) {
+                line_starts.push(index + char.len_utf8());
+            }
+        }
+        LineIndex {
+            source,
+            line_starts,
+        }
+    }
+
+    /// 行号从 1 开始，列号从 0 开始并按 UTF-16 码元计数（与 acorn 一致）
+    pub fn position(&self, offset: usize) -> Position {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let line_start = self.line_starts[line];
+        let end = offset.min(self.source.len());
+        let column = if line_start < end {
+            self.source[line_start..end].encode_utf16().count()
+        } else {
+            0
+        };
+        return Position {
+            line: line + 1,
+            column,
+        };
+    }
+}
@@ -0,0 +1,60 @@
+// 单元测试共用的辅助函数：解析源码并返回紧凑 JSON（默认不含 loc），或者返回错误信息
+use crate::estree::{to_json, JsonOptions};
+use crate::types::SourceType;
+use crate::{parse, ParseOptions};
+
+fn parse_json(source: &str, source_type: SourceType, locations: bool) -> Result<String, String> {
+    let options = JsonOptions {
+        locations,
+        pretty: false,
+    };
+    match parse(source, ParseOptions { source_type }) {
+        Ok(program) => return Ok(to_json(&program, source, &options)),
+        Err(error) => return Err(error.description()),
+    }
+}
+
+pub fn script(source: &str) -> Result<String, String> {
+    return parse_json(source, SourceType::script, false);
+}
+
+/// 与 script 相同，但 JSON 中包含每个节点的 loc
+pub fn script_with_locations(source: &str) -> Result<String, String> {
+    return parse_json(source, SourceType::script, true);
+}
+
+pub fn module(source: &str) -> Result<String, String> {
+    return parse_json(source, SourceType::module, false);
+}
+
+/// 每个用例都能解析，并且 JSON 中包含期望的片段
+pub fn assert_json(parse: fn(&str) -> Result<String, String>, cases: &[(&str, &str)]) {
+    for (source, expected) in cases {
+        match parse(source) {
+            Ok(json) => assert!(
+                json.contains(expected),
+                "{}\n{}\nnot in {}",
+                source,
+                expected,
+                json
+            ),
+            Err(message) => panic!("{}\nunexpected error: {}", source, message),
+        }
+    }
+}
+
+/// 每个用例都解析失败，并且错误信息包含期望的内容
+pub fn assert_errors(parse: fn(&str) -> Result<String, String>, cases: &[(&str, &str)]) {
+    for (source, expected) in cases {
+        match parse(source) {
+            Ok(json) => panic!("{}\nexpected error '{}', got {}", source, expected, json),
+            Err(message) => assert!(
+                message.contains(expected),
+                "{}\n{}\nnot in {}",
+                source,
+                expected,
+                message
+            ),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+// 命令行的退出码和错误诊断：0 成功，1 分析失败，2 读取失败或参数错误
+#![allow(clippy::needless_return)]
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+// 在 cargo 为集成测试准备的临时目录中写入源码文件
+fn source_file(name: &str, source: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::write(&path, source).unwrap();
+    return path;
+}
+
+fn l6(args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_l6"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    return child.wait_with_output().unwrap();
+}
+
+fn text(bytes: &[u8]) -> String {
+    return String::from_utf8_lossy(bytes).into_owned();
+}
+
+#[test]
+fn successful_parse() {
+    let good = source_file("cli_good.js", "let a = 1;\n");
+    let output = l6(&["ast", good.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(0), "{}", text(&output.stderr));
+    assert!(text(&output.stdout).starts_with("{\n  \"type\": \"Program\""));
+    assert!(output.stderr.is_empty());
+
+    // 没有文件参数时从 stdin 读取
+    let output = l6(&["tokens", "--format", "debug"], "a + b");
+    assert_eq!(output.status.code(), Some(0), "{}", text(&output.stderr));
+    assert_eq!(text(&output.stdout).matches("label:").count(), 3);
+}
+
+#[test]
+fn parse_error_diagnostic() {
+    let bad = source_file("cli_bad.js", "let a = 1;\nlet b = (;\n");
+    let path = bad.to_str().unwrap();
+    let output = l6(&["ast", path], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(
+        text(&output.stderr),
+        format!(
+            "error: expected expression, found ';'\n --> {}:2:10\n  |\n2 | let b = (;\n  |          ^\n",
+            path
+        )
+    );
+
+    // 模块中 await 是保留字，脚本中可以用作标识符
+    let output = l6(&["ast", "-"], "var await;");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(text(&output.stderr).contains(" --> <stdin>:1:5"));
+    let output = l6(&["ast", "--source-type=script", "-"], "var await;");
+    assert_eq!(output.status.code(), Some(0), "{}", text(&output.stderr));
+}
+
+#[test]
+fn multiple_files() {
+    let good = source_file("cli_multiple_good.js", "a;\n");
+    let bad = source_file("cli_multiple_bad.js", "a b;\n");
+    let missing = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("cli_missing.js");
+    // 某个文件失败不影响后续文件，退出码取最严重的失败
+    let output = l6(&["ast", bad.to_str().unwrap(), good.to_str().unwrap()], "");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(text(&output.stdout).contains("\"type\": \"Program\""));
+    assert!(text(&output.stderr).contains("cli_multiple_bad.js:1:3"));
+
+    let output = l6(
+        &["ast", missing.to_str().unwrap(), bad.to_str().unwrap()],
+        "",
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = text(&output.stderr);
+    assert!(stderr.contains("error: failed to read"), "{}", stderr);
+    assert!(stderr.contains("cli_multiple_bad.js:1:3"), "{}", stderr);
+}
+
+#[test]
+fn usage_errors() {
+    for args in [
+        &["parse"][..],
+        &["ast", "--bogus"],
+        &["ast", "--format", "yaml"],
+        &["ast", "--source-type"],
+    ] {
+        let output = l6(args, "");
+        assert_eq!(output.status.code(), Some(2), "{:?}", args);
+        assert!(text(&output.stderr).contains("usage: l6"), "{:?}", args);
+    }
+}
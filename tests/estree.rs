@@ -0,0 +1,22 @@
+// ESTree JSON 的完整输出（包括 loc）与 golden 文件逐字比较，golden 文件由 `l6 ast` 生成后人工核对过
+use l6::{parse, to_json, JsonOptions, ParseOptions, SourceType};
+
+const SOURCE: &str = include_str!("golden/estree.js");
+const EXPECTED: &str = include_str!("golden/estree.json");
+
+#[test]
+fn estree_golden() {
+    let program = parse(
+        SOURCE,
+        ParseOptions {
+            source_type: SourceType::module,
+        },
+    )
+    .unwrap_or_else(|error| panic!("estree.js: {}", error));
+    let json_options = JsonOptions {
+        locations: true,
+        pretty: true,
+    };
+    let json = to_json(&program, SOURCE, &json_options);
+    assert_eq!(json.trim_end(), EXPECTED.trim_end());
+}
@@ -0,0 +1,47 @@
+// 基准测试使用的真实第三方库源码，确认词法分析和语法分析都能完整通过
+use l6::{parse, to_json, tokenizer, JsonOptions, ParseOptions, SourceType};
+
+const FIXTURES: [(&str, &str); 3] = [
+    ("jquery.js", include_str!("../benches/fixtures/jquery.js")),
+    (
+        "jquery.min.js",
+        include_str!("../benches/fixtures/jquery.min.js"),
+    ),
+    (
+        "underscore.js",
+        include_str!("../benches/fixtures/underscore.js"),
+    ),
+];
+
+#[test]
+fn fixtures_tokenize() {
+    for (name, source) in FIXTURES {
+        let tokens = tokenizer(source).unwrap_or_else(|error| panic!("{}: {}", name, error));
+        assert!(!tokens.is_empty(), "{}: no tokens", name);
+    }
+}
+
+#[test]
+fn fixtures_parse() {
+    let json_options = JsonOptions {
+        locations: true,
+        pretty: false,
+    };
+    for (name, source) in FIXTURES {
+        let program = parse(
+            source,
+            ParseOptions {
+                source_type: SourceType::script,
+            },
+        )
+        .unwrap_or_else(|error| panic!("{}: {}", name, error));
+        assert!(!program.body.is_empty(), "{}: empty program", name);
+        let json = to_json(&program, source, &json_options);
+        assert!(
+            json.starts_with(r#"{"type":"Program""#),
+            "{}: {}",
+            name,
+            &json[..40]
+        );
+    }
+}
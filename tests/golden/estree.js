@@ -0,0 +1,17 @@
+import { readFile } from "fs";
+
+export const answer = 42n;
+
+export default class Point extends Base {
+  #x = 0;
+  static of(...args) {
+    return new Point(...args);
+  }
+  get x() {
+    return this.#x;
+  }
+}
+
+const { a, b: [c = 1, ...d] } = await readFile?.(`${name}.json`);
+let label = "\u{1D400} 𝐀";
+for (const [key, value] of Object.entries(d)) if (/^k\d+$/u.test(key)) break;